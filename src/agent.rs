@@ -0,0 +1,203 @@
+use std::time::Duration;
+use rand::Rng;
+use crate::components::game::Game;
+use crate::components::grid::Grid;
+use crate::components::domino::Domino;
+use crate::components::grid_domino::GridDomino;
+use crate::components::queendomino::{Building, DRAGON_COST};
+use crate::components::variant::Variant;
+use crate::controller::Phase;
+use crate::external_agent::ExternalAgent;
+
+
+/// Something that can sit in a seat and make moves without a mouse
+pub(crate) trait Agent {
+
+    /// Name used in logs and tournament results
+    fn name(&self) -> &str;
+
+    /// Returns the index of the pick draft to put a king on. Only asked during Phase::Picking.
+    fn pick(&mut self, game: &Game) -> usize;
+
+    /// Returns where to put the active player's domino, or None to discard it. Only asked during Phase::Placing.
+    fn place(&mut self, game: &Game) -> Option<GridDomino>;
+
+    /// Queendomino: returns a building to buy as (board slot, x, y), or None to save the coins.
+    /// Asked at the start of each turn.
+    fn build(&mut self, _game: &Game) -> Option<(usize, u8, u8)> {None}
+
+    /// Queendomino: returns a board slot for the dragon to burn, or None to leave him be.
+    /// Asked at the start of each turn, after build.
+    fn burn(&mut self, _game: &Game) -> Option<usize> {None}
+
+    /// Called once the game has ended, for the agent sitting in the given seat
+    fn game_over(&mut self, _game: &Game, _seat: u8) {}
+}
+
+
+/// Builds an agent from its name on the command line. "exec:<command>" starts an external bot,
+/// which gets move_timeout to answer each time it has to move.
+pub(crate) fn agent_from_spec(spec: &str, move_timeout: Duration) -> Result<Box<dyn Agent>, String> {
+    if let Some(command) = spec.strip_prefix("exec:") {
+        return ExternalAgent::spawn(command, move_timeout)
+            .map(|agent| Box::new(agent) as Box<dyn Agent>)
+            .map_err(|e| format!("could not start bot '{}': {}", command, e));
+    }
+    match builtin_agent(spec) {
+        Some(agent) => Ok(agent),
+        None => Err(format!("unknown agent '{}'", spec)),
+    }
+}
+
+
+/// The bots that live in this crate. Unlike agent_from_spec this never starts a process,
+/// so it is safe to call with a name that came over the network.
+pub(crate) fn builtin_agent(name: &str) -> Option<Box<dyn Agent + Send>> {
+    match name {
+        "random" => Some(Box::new(RandomAgent::new())),
+        "greedy" => Some(Box::new(GreedyAgent::new())),
+        _ => None,
+    }
+}
+
+
+/// Finds the placement that scores the most right now, along with that score
+pub(crate) fn best_placement(grid: &Grid, domino: &Domino) -> Option<(GridDomino, u32)> {
+    let mut best: Option<(GridDomino, u32)> = None;
+    for grid_domino in grid.legal_placements(domino) {
+        let score = grid.score_with(grid_domino, domino);
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((grid_domino, score));
+        }
+    }
+    best
+}
+
+
+/// Plays the game to the end. seats[i] plays for the player with id i + 1.
+/// Illegal moves are logged and replaced with a random legal one.
+pub(crate) fn play_out(game: &mut Game, seats: &mut [&mut dyn Agent]) {
+    while let Some(phase) = game.phase() {
+        let seat = (game.active_player().id() - 1) as usize;
+        let agent = &mut seats[seat];
+        if game.variant() == Variant::Queendomino {
+            if let Some((slot, x, y)) = agent.build(game) && let Err(e) = game.build(slot, x, y) {
+                eprintln!("{} (seat {}) tried to build from slot {} at ({}, {}): {}", agent.name(), seat + 1, slot, x, y, e);
+            }
+            if let Some(slot) = agent.burn(game) && let Err(e) = game.burn(slot) {
+                eprintln!("{} (seat {}) tried to burn slot {}: {}", agent.name(), seat + 1, slot, e);
+            }
+        }
+        match phase {
+            Phase::Picking => {
+                let idx = agent.pick(game);
+                if let Err(e) = game.pick(idx) {
+                    eprintln!("{} (seat {}) tried to pick slot {}: {}. Picking at random instead.", agent.name(), seat + 1, idx, e);
+                    let idx = RandomAgent::new().pick(game);
+                    game.pick(idx).expect("random agent picked an unpickable domino");
+                }
+            }
+            Phase::Placing => {
+                let grid_domino = agent.place(game);
+                if let Err(e) = game.place(grid_domino) {
+                    eprintln!("{} (seat {}) tried an illegal placement: {}. Placing at random instead.", agent.name(), seat + 1, e);
+                    let grid_domino = RandomAgent::new().place(game);
+                    game.place(grid_domino).expect("random agent made an illegal placement");
+                }
+            }
+        }
+    }
+
+    for (seat, agent) in seats.iter_mut().enumerate() {
+        agent.game_over(game, seat as u8 + 1);
+    }
+}
+
+
+/// Picks and places completely at random (among legal moves)
+pub(crate) struct RandomAgent {}
+
+impl RandomAgent {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Agent for RandomAgent {
+    fn name(&self) -> &str {"random"}
+
+    fn pick(&mut self, game: &Game) -> usize {
+        let picks = game.legal_picks();
+        picks[rand::rng().random_range(0..picks.len())]
+    }
+
+    fn place(&mut self, game: &Game) -> Option<GridDomino> {
+        let placements = game.legal_placements();
+        if placements.is_empty() {
+            return None;
+        }
+        Some(placements[rand::rng().random_range(0..placements.len())])
+    }
+}
+
+
+/// Always takes whatever is worth the most points immediately
+pub(crate) struct GreedyAgent {}
+
+impl GreedyAgent {
+    pub(crate) fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Agent for GreedyAgent {
+    fn name(&self) -> &str {"greedy"}
+
+    fn pick(&mut self, game: &Game) -> usize {
+        let grid = game.active_player().grid();
+        let current = grid.score();
+
+        // Ties go to the lower index, which also places earlier next round
+        let mut best_idx = game.legal_picks()[0];
+        let mut best_gain = 0;
+        for idx in game.legal_picks() {
+            let gain = best_placement(grid, &game.pick_draft().domino(idx))
+                .map_or(0, |(_, score)| score.saturating_sub(current));
+            if gain > best_gain {
+                best_idx = idx;
+                best_gain = gain;
+            }
+        }
+        best_idx
+    }
+
+    fn place(&mut self, game: &Game) -> Option<GridDomino> {
+        let player = game.active_player();
+        best_placement(player.grid(), &player.placing()).map(|(grid_domino, _)| grid_domino)
+    }
+
+    /// Buys whatever building is worth the most
+    fn build(&mut self, game: &Game) -> Option<(usize, u8, u8)> {
+        game.legal_builds().into_iter()
+            .max_by_key(|&(slot, _, _)| game.board().get(slot).map_or(0, building_value))
+    }
+
+    /// Burns the best building on the board when it has the coins to spare but not enough to buy it,
+    /// so nobody else gets it first. Never burns while holding the queen, the dragon won't come anyway.
+    fn burn(&mut self, game: &Game) -> Option<usize> {
+        let player = game.active_player();
+        if game.queen() == Some(player.id()) || player.coins() <= DRAGON_COST {
+            return None;
+        }
+        let (slot, best) = game.board().slots().iter().enumerate()
+            .filter_map(|(slot, building)| building.map(|b| (slot, b)))
+            .max_by_key(|&(_, building)| building_value(building))?;
+        (best.cost > player.coins()).then_some(slot)
+    }
+}
+
+
+/// Rough worth of a building in points. Crowns multiply a property so they count extra.
+fn building_value(building: Building) -> u32 {
+    building.crowns as u32 * 3 + building.points + building.knights as u32 * 2 + building.towers as u32
+}
@@ -0,0 +1,65 @@
+// Timing for the gui's animations: how long they take at each speed setting, and how they ease in.
+// Positions come from the layout, this only says how far along the way something is.
+
+
+/// Seconds for a newly dealt domino to slide from the deck into the draft
+pub const DEAL_SECONDS: f64 = 0.45;
+/// Extra delay for each slot further down the draft, so the dominoes come in one after another
+pub const DEAL_STAGGER: f64 = 0.08;
+/// Seconds for a king to walk onto the domino they picked
+pub const KING_SECONDS: f64 = 0.4;
+/// Seconds for a placed domino to fly from the draft to its kingdom
+pub const PLACE_SECONDS: f64 = 0.5;
+
+
+/// How fast the animations play, set with `--animations`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnimationSpeed {
+    /// Everything snaps into place
+    Off,
+    #[default]
+    Normal,
+    Fast,
+}
+
+impl AnimationSpeed {
+
+    /// The setting by name: off, normal or fast
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "off" => Some(AnimationSpeed::Off),
+            "normal" => Some(AnimationSpeed::Normal),
+            "fast" => Some(AnimationSpeed::Fast),
+            _ => None,
+        }
+    }
+
+    /// How long an animation that takes `seconds` at normal speed takes at this one
+    pub fn scale(&self, seconds: f64) -> f64 {
+        match self {
+            AnimationSpeed::Off => 0.0,
+            AnimationSpeed::Normal => seconds,
+            AnimationSpeed::Fast => seconds * 0.4,
+        }
+    }
+}
+
+
+/// How far along (0 to 1) an animation that starts at `started` and lasts `duration` is at `now`, in seconds.
+/// 0 before it starts, and 1 once it is over or if it takes no time at all.
+pub fn progress(started: f64, now: f64, duration: f64) -> f32 {
+    if duration <= 0.0 {
+        return 1.0;
+    }
+    (((now - started) / duration) as f32).clamp(0.0, 1.0)
+}
+
+/// Fast at first and slowing down to a stop, for things landing in place
+pub fn ease_out(t: f32) -> f32 {
+    1.0 - (1.0 - t.clamp(0.0, 1.0)).powi(3)
+}
+
+/// The point `t` of the way from `from` to `to`
+pub fn lerp(from: (f32, f32), to: (f32, f32), t: f32) -> (f32, f32) {
+    (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t)
+}
@@ -1,129 +1,294 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use macroquad::miniquad::FilterMode;
-use macroquad::prelude::{load_texture, Texture2D};
-use crate::components::deck::DECK_SIZE;
-use crate::components::turn::NUM_PLAYERS;
+use macroquad::prelude::{Image, Texture2D};
+use crate::components::domino::CASTLE_ID;
+use crate::components::domino_set::DominoSet;
+use crate::components::turn::MAX_PLAYERS;
+use crate::sound;
 
-pub(crate) struct Assets {
+// Every picture the game ships with is compiled into the binary, so it runs from any working directory.
+// A theme pack is a folder laid out like res/img: whatever pictures it has replace the built-in ones,
+// and the rest stay as they are. Assets are named by their path inside that folder ("kings/king_1.png").
+// Sounds work the same way from its sounds folder, see crate::sound.
 
-    // dictionary with u8 key between 0-47 representing the
-    domino_by_id:   HashMap<u8, Texture2D>,
-    king_by_turn: HashMap<u8, Texture2D>,
-    draft_scroll: Texture2D,
-    score_scroll: Texture2D,
-    hand: Texture2D,
-    socket: Texture2D
-}
 
+macro_rules! embed {
+    ($name:literal) => {
+        ($name, include_bytes!(concat!("../res/img/", $name)) as &[u8])
+    };
+}
 
-impl Assets {
+/// The built-in pictures, by name
+const EMBEDDED: &[(&str, &[u8])] = &[
+    embed!("dominoes/domino_1.png"),
+    embed!("dominoes/domino_2.png"),
+    embed!("dominoes/domino_3.png"),
+    embed!("dominoes/domino_4.png"),
+    embed!("dominoes/domino_5.png"),
+    embed!("dominoes/domino_6.png"),
+    embed!("dominoes/domino_7.png"),
+    embed!("dominoes/domino_8.png"),
+    embed!("dominoes/domino_9.png"),
+    embed!("dominoes/domino_10.png"),
+    embed!("dominoes/domino_11.png"),
+    embed!("dominoes/domino_12.png"),
+    embed!("dominoes/domino_13.png"),
+    embed!("dominoes/domino_14.png"),
+    embed!("dominoes/domino_15.png"),
+    embed!("dominoes/domino_16.png"),
+    embed!("dominoes/domino_17.png"),
+    embed!("dominoes/domino_18.png"),
+    embed!("dominoes/domino_19.png"),
+    embed!("dominoes/domino_20.png"),
+    embed!("dominoes/domino_21.png"),
+    embed!("dominoes/domino_22.png"),
+    embed!("dominoes/domino_23.png"),
+    embed!("dominoes/domino_24.png"),
+    embed!("dominoes/domino_25.png"),
+    embed!("dominoes/domino_26.png"),
+    embed!("dominoes/domino_27.png"),
+    embed!("dominoes/domino_28.png"),
+    embed!("dominoes/domino_29.png"),
+    embed!("dominoes/domino_30.png"),
+    embed!("dominoes/domino_31.png"),
+    embed!("dominoes/domino_32.png"),
+    embed!("dominoes/domino_33.png"),
+    embed!("dominoes/domino_34.png"),
+    embed!("dominoes/domino_35.png"),
+    embed!("dominoes/domino_36.png"),
+    embed!("dominoes/domino_37.png"),
+    embed!("dominoes/domino_38.png"),
+    embed!("dominoes/domino_39.png"),
+    embed!("dominoes/domino_40.png"),
+    embed!("dominoes/domino_41.png"),
+    embed!("dominoes/domino_42.png"),
+    embed!("dominoes/domino_43.png"),
+    embed!("dominoes/domino_44.png"),
+    embed!("dominoes/domino_45.png"),
+    embed!("dominoes/domino_46.png"),
+    embed!("dominoes/domino_47.png"),
+    embed!("dominoes/domino_48.png"),
+    embed!("dominoes/domino_49.png"),
+    embed!("kings/king_1.png"),
+    embed!("kings/king_2.png"),
+    embed!("kings/king_3.png"),
+    embed!("kings/king_4.png"),
+    embed!("kings/king_5.png"),
+    embed!("scroll_draft.png"),
+    embed!("scroll_score.png"),
+    embed!("hand.png"),
+    embed!("socket.png"),
+];
 
 
+/// Where a loaded picture goes
+enum Slot {
+    Domino(u8),
+    King(u8),
+    DraftScroll,
+    ScoreScroll,
+    Hand,
+    Socket,
+}
 
-    /// Loads all the assets. Ideally, this should only ever be called once.
-    pub(crate) async fn load() -> Self {
 
-        let king_by_turn = Self::load_king_textures().await;
-        let domino_by_id = Self::load_domino_textures().await;
-        let draft_scroll = Self::load_scroll_draft_textures().await;
-        let score_scroll = Self::load_scroll_score_textures().await;
-        let hand = Self::load_hand_textures().await;
-        let socket = Self::load_socket_textures().await;
+/// Loads the assets one picture at a time, so there is something to show while it happens
+pub(crate) struct AssetLoader {
+    source: AssetSource,
+    queue: Vec<(Slot, String)>, // every picture to load, by name
+    loaded: usize, // how far into the queue we are
+    assets: Assets,
+    problems: Vec<AssetError>, // everything that could not be loaded, in the order it was tried
+}
 
+impl AssetLoader {
 
-        Self {
-            king_by_turn,
-            domino_by_id,
-            draft_scroll,
-            score_scroll,
-            hand,
-            socket
+    /// Queues up every picture the gui needs, with the domino pictures of the given set
+    pub(crate) fn new(set: &DominoSet, source: &AssetSource) -> Self {
+        let mut problems = Vec::new();
+        if let Err(e) = source.check() {
+            eprintln!("asset problem: {}", e);
+            problems.push(e);
         }
-    }
-
-    async fn load_domino_textures() -> HashMap<u8, Texture2D> {
-        let mut domino_by_id = HashMap::new();
 
-        // Creates the dictionary to be able to fetch the domino texture from the domino id
-        for id in 1..DECK_SIZE + 2 { //I did +2 not +1 because I want this function to load the castle texture which is domino 49
+        // The castle is not part of any set, but it is drawn like a domino
+        let mut queue: Vec<(Slot, String)> = set.dominoes().iter()
+            .filter_map(|domino| set.texture(domino.id()).map(|name| (Slot::Domino(domino.id()), name.to_string())))
+            .collect();
+        queue.push((Slot::Domino(CASTLE_ID), format!("dominoes/domino_{}.png", CASTLE_ID)));
+        queue.extend((1..=MAX_PLAYERS).map(|turn| (Slot::King(turn), format!("kings/king_{}.png", turn))));
+        queue.push((Slot::DraftScroll, "scroll_draft.png".to_string()));
+        queue.push((Slot::ScoreScroll, "scroll_score.png".to_string()));
+        queue.push((Slot::Hand, "hand.png".to_string()));
+        queue.push((Slot::Socket, "socket.png".to_string()));
 
-            let path = format!("res/img/dominoes/domino_{}.png", id); // +1 because our img names start with 1
+        Self { source: source.clone(), queue, loaded: 0, assets: Assets::empty(), problems }
+    }
 
-            let texture = load_texture(&path).await.unwrap();
-            texture.set_filter(FilterMode::Nearest);
+    /// Loads the next picture. False once there is nothing left to load.
+    pub(crate) fn load_next(&mut self) -> bool {
+        let Some((slot, name)) = self.queue.get(self.loaded) else { return false };
+        self.loaded += 1;
 
-            domino_by_id.insert(id as u8, texture);
+        let texture = match self.source.image(name) {
+            Ok(image) => {
+                let texture = Texture2D::from_image(&image);
+                texture.set_filter(FilterMode::Nearest);
+                texture
+            }
+            Err(e) => {
+                eprintln!("asset problem: {}", e);
+                self.problems.push(e);
+                return true;
+            }
+        };
+        match *slot {
+            Slot::Domino(id) => { self.assets.domino_by_id.insert(id, texture); }
+            Slot::King(turn) => { self.assets.king_by_turn.insert(turn, texture); }
+            Slot::DraftScroll => self.assets.draft_scroll = Some(texture),
+            Slot::ScoreScroll => self.assets.score_scroll = Some(texture),
+            Slot::Hand => self.assets.hand = Some(texture),
+            Slot::Socket => self.assets.socket = Some(texture),
         }
-        domino_by_id
+        true
     }
 
-    async fn load_king_textures() -> HashMap<u8, Texture2D> {
-        let mut player_by_turn = HashMap::new();
-        for turn in 1..NUM_PLAYERS + 1 {
-            let path = format!("res/img/kings/king_{}.png", turn);
+    /// (pictures loaded or given up on, pictures in total)
+    pub(crate) fn progress(&self) -> (usize, usize) {(self.loaded, self.queue.len())}
+
+    /// The name of the picture load_next() will try next
+    pub(crate) fn current(&self) -> Option<&str> {
+        self.queue.get(self.loaded).map(|(_, name)| name.as_str())
+    }
 
-            let texture = load_texture(&path).await.unwrap();
-            texture.set_filter(FilterMode::Nearest);
+    pub(crate) fn problems(&self) -> &[AssetError] {&self.problems}
 
-            player_by_turn.insert(turn, texture);
+    /// The assets, with whatever could not be loaded left out
+    pub(crate) fn finish(self) -> Assets {self.assets}
+}
+
+
+/// A picture (or sound) that could not be loaded. The gui draws something plainer in its place, or plays nothing.
+#[derive(Debug)]
+pub enum AssetError {
+    /// Neither the theme pack nor the built-in pictures have it
+    Missing(String),
+    /// The file is there, but it is not a picture we can read
+    Malformed(PathBuf, String),
+    /// The theme pack folder itself is not there
+    NoOverrideDir(PathBuf),
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetError::Missing(name) => write!(f, "{} is missing", name),
+            AssetError::Malformed(path, e) => write!(f, "{} is not readable: {}", path.display(), e),
+            AssetError::NoOverrideDir(dir) => write!(f, "the asset folder {} does not exist", dir.display()),
         }
-        player_by_turn
     }
+}
 
-    /// Fetches the king texture given a player's id (turn) between 1 and 4
-    pub(crate) fn fetch_king_texture_by_turn(&self, id: u8) -> Option<&Texture2D> {
 
-        // We should never fetch an impossible id
-        assert!(self.king_by_turn.contains_key(&id), "id: {id}");
+/// Where pictures come from: the theme pack folder first, then the ones built into the binary
+#[derive(Debug, Clone, Default)]
+pub struct AssetSource {
+    overrides: Option<PathBuf>,
+}
 
-        self.king_by_turn.get(&id)
+impl AssetSource {
+
+    /// Only the built-in pictures, or those with the pictures in `overrides` on top
+    pub fn new(overrides: Option<&Path>) -> Self {
+        Self { overrides: overrides.map(Path::to_path_buf) }
     }
 
-    /// Fetches the domino texture given an id between 1 and 48
-    pub(crate) fn fetch_domino_texture_by_id(&self, id: u8) -> Option<&Texture2D> {
+    /// Checks the theme pack folder is there at all
+    pub fn check(&self) -> Result<(), AssetError> {
+        match &self.overrides {
+            Some(dir) if !dir.is_dir() => Err(AssetError::NoOverrideDir(dir.clone())),
+            _ => Ok(()),
+        }
+    }
 
-        if id != 49 {
-            // We should never fetch an impossible id... Unless its the castle! :) castle has id 99
-            assert!(self.domino_by_id.contains_key(&id), "id: {id}");
+    /// Reads and decodes the picture of the given name
+    pub fn image(&self, name: &str) -> Result<Image, AssetError> {
+        if let Some(path) = self.overrides.as_ref().map(|dir| dir.join(name)).filter(|path| path.is_file()) {
+            let bytes = std::fs::read(&path).map_err(|e| AssetError::Malformed(path.clone(), e.to_string()))?;
+            return Image::from_file_with_format(&bytes, None).map_err(|e| AssetError::Malformed(path, e.to_string()));
         }
-        
+        let (_, bytes) = EMBEDDED.iter().find(|(embedded, _)| *embedded == name)
+            .ok_or_else(|| AssetError::Missing(name.to_string()))?;
+        Image::from_file_with_format(bytes, None).map_err(|e| AssetError::Malformed(PathBuf::from(name), e.to_string()))
+    }
 
-        self.domino_by_id.get(&id)
+    /// Reads the sound of the given name ("sounds/pick"), from a .ogg or .wav file of that name in the theme pack
+    /// or else the built-in one. Checked well enough that the audio backend can play it.
+    pub fn sound(&self, name: &str) -> Result<Vec<u8>, AssetError> {
+        let file = self.overrides.iter()
+            .flat_map(|dir| ["ogg", "wav"].map(|extension| dir.join(format!("{}.{}", name, extension))))
+            .find(|path| path.is_file());
+        if let Some(path) = file {
+            let bytes = std::fs::read(&path).map_err(|e| AssetError::Malformed(path.clone(), e.to_string()))?;
+            sound::check_format(&bytes).map_err(|e| AssetError::Malformed(path, e))?;
+            return Ok(bytes);
+        }
+        sound::built_in(name).ok_or_else(|| AssetError::Missing(name.to_string()))
     }
 
-    // Loads the textures for the draft scroll (just for cosmetics)
-    async fn load_scroll_draft_textures() -> Texture2D {
-        let path = format!("res/img/scroll_draft.png");
-        let texture = load_texture(&path).await.unwrap();
-        texture.set_filter(FilterMode::Nearest);
-        texture
+    /// Names of every picture built into the binary
+    pub fn embedded_names() -> impl Iterator<Item = &'static str> {
+        EMBEDDED.iter().map(|(name, _)| *name)
     }
-    // Loads the textures for the score scroll (just for cosmetics)
-    async fn load_scroll_score_textures() -> Texture2D {
-        let path = format!("res/img/scroll_score.png");
-        let texture = load_texture(&path).await.unwrap();
-        texture.set_filter(FilterMode::Nearest);
-        texture
+}
+
+
+pub(crate) struct Assets {
+
+    // domino id -> picture, for every domino that has one. The rest are painted by tile_art
+    domino_by_id:   HashMap<u8, Texture2D>,
+    king_by_turn: HashMap<u8, Texture2D>,
+    // None when the picture could not be loaded
+    draft_scroll: Option<Texture2D>,
+    score_scroll: Option<Texture2D>,
+    hand: Option<Texture2D>,
+    socket: Option<Texture2D>
+}
+
+
+impl Assets {
+
+    /// No pictures at all yet. AssetLoader fills them in.
+    fn empty() -> Self {
+        Self {
+            domino_by_id: HashMap::new(),
+            king_by_turn: HashMap::new(),
+            draft_scroll: None,
+            score_scroll: None,
+            hand: None,
+            socket: None,
+        }
     }
 
-    // Loads hand texture
-    async fn load_hand_textures() -> Texture2D {
-        let path = format!("res/img/hand.png");
-        let texture = load_texture(&path).await.unwrap();
-        texture.set_filter(FilterMode::Nearest);
-        texture
+    /// Fetches the king texture given a player's id (turn) between 1 and 5
+    pub(crate) fn fetch_king_texture_by_turn(&self, id: u8) -> Option<&Texture2D> {
+
+        // We should never fetch an impossible id
+        assert!((1..=MAX_PLAYERS).contains(&id), "id: {id}");
+
+        self.king_by_turn.get(&id)
     }
 
-    // Loads socket texture
-    async fn load_socket_textures() -> Texture2D {
-        let path = format!("res/img/socket.png");
-        let texture = load_texture(&path).await.unwrap();
-        texture.set_filter(FilterMode::Nearest);
-        texture
+    /// Fetches the domino texture given the id of a domino in the set (or the castle).
+    /// None if the domino has no picture, in which case it gets painted from its tiles.
+    pub(crate) fn fetch_domino_texture_by_id(&self, id: u8) -> Option<&Texture2D> {
+        self.domino_by_id.get(&id)
     }
 
-    pub(crate) fn fetch_score_scroll(&self) -> Option<&Texture2D>{Some(&self.score_scroll)}
-    pub(crate) fn fetch_draft_scroll(&self) -> Option<&Texture2D>{Some(&self.draft_scroll)}
-    pub(crate) fn fetch_hand(&self) -> Option<&Texture2D>{Some(&self.hand)}
-    pub(crate) fn fetch_socket(&self) -> Option<&Texture2D>{Some(&self.socket)}
-}
\ No newline at end of file
+    pub(crate) fn fetch_score_scroll(&self) -> Option<&Texture2D>{self.score_scroll.as_ref()}
+    pub(crate) fn fetch_draft_scroll(&self) -> Option<&Texture2D>{self.draft_scroll.as_ref()}
+    pub(crate) fn fetch_hand(&self) -> Option<&Texture2D>{self.hand.as_ref()}
+    pub(crate) fn fetch_socket(&self) -> Option<&Texture2D>{self.socket.as_ref()}
+}
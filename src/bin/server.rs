@@ -0,0 +1,53 @@
+use std::process::ExitCode;
+use tokio::net::TcpListener;
+use kingdomino::components::variant::Variant;
+use kingdomino::server::{self, DEFAULT_PORT};
+
+const USAGE: &str = "usage: server [--port PORT] [--variant kingdomino|queendomino|age-of-giants] [--players 4|5]";
+
+
+/// Hosts a game on the local network and waits for its players to join
+#[tokio::main]
+async fn main() -> ExitCode {
+    let mut port = DEFAULT_PORT;
+    let mut variant = Variant::Kingdomino;
+    let mut players = 4;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let parsed = match (arg.as_str(), args.next()) {
+            ("--port", Some(value)) => value.parse().map(|value| port = value).is_ok(),
+            ("--variant", Some(value)) => Variant::from_name(&value).map(|value| variant = value).is_some(),
+            ("--players", Some(value)) => value.parse().map(|value| players = value).is_ok(),
+            _ => false,
+        };
+        if !parsed {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    }
+    if !(4..=variant.max_players()).contains(&players) {
+        eprintln!("{} can't be played by {} players", variant.name(), players);
+        return ExitCode::FAILURE;
+    }
+
+    let listener = match TcpListener::bind(("0.0.0.0", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("could not listen on port {}: {}", port, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("hosting a game of {} for {} players on port {}", variant.name(), players, port);
+
+    match server::serve(listener, variant, players).await {
+        Ok(scores) => {
+            println!("game over! scores by seat: {:?}", scores);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("server stopped: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+use kingdomino::components::domino_set::DominoSet;
+use kingdomino::components::variant::Variant;
+use kingdomino::external_agent::DEFAULT_MOVE_TIMEOUT;
+use kingdomino::tournament::{self, Format, TournamentConfig};
+
+const USAGE: &str = "usage: tournament --agents greedy,random,exec:<command>,... [--swiss ROUNDS] [--games N] [--out results.csv] [--timeout-ms MS] [--variant kingdomino|queendomino|age-of-giants] [--players 4|5] [--set SET.json]";
+
+
+/// Headless tournament between bots. Round robin unless --swiss is given.
+fn main() -> ExitCode {
+    let mut config = TournamentConfig {
+        agents: Vec::new(),
+        format: Format::RoundRobin,
+        games_per_seating: 1,
+        results_path: PathBuf::from("tournament_results.csv"),
+        move_timeout: DEFAULT_MOVE_TIMEOUT,
+        variant: Variant::Kingdomino,
+        table_size: 4,
+        domino_set: None,
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args.next();
+        let parsed = match (arg.as_str(), value) {
+            ("--agents", Some(value)) => {
+                config.agents = value.split(',').map(|spec| spec.trim().to_string()).collect();
+                true
+            }
+            ("--swiss", Some(value)) => value.parse().map(|rounds| config.format = Format::Swiss { rounds }).is_ok(),
+            ("--games", Some(value)) => value.parse().map(|games| config.games_per_seating = games).is_ok(),
+            ("--timeout-ms", Some(value)) => value.parse().map(|ms| config.move_timeout = Duration::from_millis(ms)).is_ok(),
+            ("--players", Some(value)) => value.parse().map(|players| config.table_size = players).is_ok(),
+            ("--variant", Some(value)) => Variant::from_name(&value).map(|variant| config.variant = variant).is_some(),
+            ("--set", Some(value)) => match DominoSet::load(Path::new(&value)) {
+                Ok(set) => {
+                    config.domino_set = Some(set);
+                    true
+                }
+                Err(e) => {
+                    eprintln!("could not load the domino set {}: {}", value, e);
+                    return ExitCode::FAILURE;
+                }
+            },
+            ("--out", Some(value)) => {
+                config.results_path = PathBuf::from(value);
+                true
+            }
+            _ => false,
+        };
+        if !parsed {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    match tournament::run(&config) {
+        Ok(standings) => {
+            println!("{:<4}{:<20}{:>8}{:>7}{:>6}{:>7}", "#", "agent", "elo", "games", "wins", "draws");
+            for (rank, standing) in standings.iter().enumerate() {
+                println!("{:<4}{:<20}{:>8.1}{:>7}{:>6}{:>7}", rank + 1, standing.name, standing.rating, standing.games, standing.wins,
+                         standing.draws);
+            }
+            println!("results written to {}", config.results_path.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("tournament failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,439 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use crate::components::draft::DRAFT_SIZE;
+use crate::components::game::Game;
+use crate::components::giants::Quest;
+use crate::components::grid_domino::GridDomino;
+use crate::components::queendomino::BuildersBoard;
+use crate::components::turn::{Turn, TurnState};
+use crate::components::variant::Variant;
+use crate::controller::Phase;
+use crate::json::Json;
+use crate::protocol::{decode_board, decode_deck, decode_draft, decode_player, encode_build, encode_placement};
+
+// Plays one seat of a game hosted by server.rs. The server is the only one who knows the rules;
+// this side keeps a copy of the last state it was sent and asks before every move.
+// Reading happens on a thread of its own, so poll() never blocks the frame.
+
+
+/// Everything on the table as of the last state from the server
+#[derive(Clone)]
+pub(crate) struct Table {
+    pub(crate) game: Game,
+    started: bool,
+    host_seat: Option<u8>,
+    lobby: Vec<LobbySeat>,
+    legal: Vec<Json>,
+}
+
+impl Table {
+
+    fn decode(state: &Json) -> Option<Self> {
+        let seat_of = |json: &Json| json.as_u64().and_then(|seat| u8::try_from(seat).ok());
+
+        let mut by_seat = Vec::new();
+        match state.get("players")? {
+            Json::Array(players) => {
+                for player in players {
+                    by_seat.push(decode_player(player)?);
+                }
+            }
+            _ => return None,
+        }
+        let mut in_order = Vec::new();
+        match state.get("turn_order")? {
+            Json::Array(order) => {
+                for seat in order {
+                    let seat = seat_of(seat)?;
+                    in_order.push(by_seat.iter().find(|p| p.id() == seat)?.clone());
+                }
+            }
+            _ => return None,
+        }
+
+        let mut lobby = Vec::new();
+        match state.get("seats")? {
+            Json::Array(seats) => {
+                for seat in seats {
+                    lobby.push(LobbySeat::decode(seat)?);
+                }
+            }
+            _ => return None,
+        }
+
+        // Four seats, or five when the variant has room for them
+        let variant = Variant::from_name(state.get("variant")?.as_str()?)?;
+        if !(DRAFT_SIZE..=variant.max_players()).contains(&in_order.len()) {
+            return None;
+        }
+        let players = in_order;
+        let pick_draft = decode_draft(state.get("pick_draft")?)?;
+        let place_draft = decode_draft(state.get("place_draft")?)?;
+        let phase = match state.get("phase")? {
+            Json::Null => None,
+            phase => match phase.as_str()? {
+                "pick" => Some(Phase::Picking),
+                "place" => Some(Phase::Placing),
+                _ => return None,
+            },
+        };
+        let king = match state.get("active_seat")? {
+            Json::Null => Turn::default(),
+            seat => {
+                let seat = seat_of(seat)?;
+                let idx = players.iter().position(|p| p.id() == seat)?;
+                Turn::try_from(idx as u8).ok()?
+            }
+        };
+        let turn_state = TurnState::resume(phase, king, !place_draft.is_null(), !pick_draft.is_null());
+
+        let built: Vec<u8> = players.iter().flat_map(|p| p.buildings().iter().map(|(b, _, _)| b.id)).collect();
+        let board = BuildersBoard::restore(decode_board(state.get("board")?)?, &built);
+        let queen = match state.get("queen")? {
+            Json::Null => None,
+            seat => Some(seat_of(seat)?),
+        };
+        let mut quests = Vec::new();
+        match state.get("quests")? {
+            Json::Array(names) => {
+                for name in names {
+                    quests.push(Quest::from_name(name.as_str()?)?);
+                }
+            }
+            _ => return None,
+        }
+        let giants_left = u8::try_from(state.get("giants_left")?.as_u64()?).ok()?;
+
+        let deck = decode_deck(state.get("deck")?)?;
+        let mut game = Game::restore(variant, players, pick_draft, place_draft, deck, turn_state);
+        game.restore_queendomino(board, queen, state.get("built")?.as_bool()?, state.get("dragon_called")?.as_bool()?);
+        game.restore_giants(giants_left, quests);
+
+        Some(Self {
+            game,
+            started: state.get("started")?.as_bool()?,
+            host_seat: match state.get("host_seat")? {
+                Json::Null => None,
+                seat => Some(seat_of(seat)?),
+            },
+            lobby,
+            legal: match state.get("legal")? {
+                Json::Array(legal) => legal.clone(),
+                _ => return None,
+            },
+        })
+    }
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeatKind {
+    Open,
+    Player,
+    Bot,
+}
+
+/// One seat of the lobby, as the server lists it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LobbySeat {
+    pub seat: u8,
+    pub color: String,
+    pub name: Option<String>,
+    pub kind: SeatKind,
+    pub ready: bool,
+    pub away: bool, // the player dropped out and a bot is holding the seat for them
+}
+
+impl LobbySeat {
+    fn decode(json: &Json) -> Option<Self> {
+        Some(Self {
+            seat: u8::try_from(json.get("seat")?.as_u64()?).ok()?,
+            color: json.get("color")?.as_str()?.to_string(),
+            name: json.get("name")?.as_str().map(str::to_string),
+            kind: match json.get("kind")?.as_str()? {
+                "open" => SeatKind::Open,
+                "player" => SeatKind::Player,
+                "bot" => SeatKind::Bot,
+                _ => return None,
+            },
+            ready: json.get("ready")?.as_bool()?,
+            away: json.get("away")?.as_bool()?,
+        })
+    }
+}
+
+
+/// A connection to a game server, sitting in one seat
+pub struct RemoteGame {
+    stream: TcpStream,
+    inbox: Receiver<String>,
+    connected: bool,
+    seat: Option<u8>,
+    table: Option<Table>,
+    waiting: bool, // a move was sent and the server has not answered yet
+    last_error: Option<String>,
+}
+
+impl RemoteGame {
+
+    /// Connects to a server (like "localhost:7878") and asks for the seat of the given color,
+    /// or the first free one. Without a name the server calls us by our color.
+    pub fn connect(address: &str, color: Option<&str>, name: Option<&str>) -> io::Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        stream.set_nodelay(true)?;
+        let reader = BufReader::new(stream.try_clone()?);
+
+        let (outbox, inbox) = mpsc::channel();
+        thread::spawn(move || {
+            for line in reader.lines() {
+                match line {
+                    Ok(line) => if outbox.send(line).is_err() { return },
+                    Err(_) => return,
+                }
+            }
+        });
+
+        let mut remote = Self {
+            stream,
+            inbox,
+            connected: true,
+            seat: None,
+            table: None,
+            waiting: false,
+            last_error: None,
+        };
+        remote.send(&Json::object([
+            ("type", Json::from("join")),
+            ("color", Json::from(color)),
+            ("name", Json::from(name)),
+        ]))?;
+        Ok(remote)
+    }
+
+    /// Reads whatever the server sent since the last call. Returns true if the table changed.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        loop {
+            match self.inbox.try_recv() {
+                Ok(line) => changed |= self.handle_line(&line),
+                Err(TryRecvError::Empty) => return changed,
+                Err(TryRecvError::Disconnected) => {
+                    if self.connected {
+                        self.connected = false;
+                        if !self.is_over() {
+                            self.last_error = Some("lost the connection to the server".to_string());
+                        }
+                    }
+                    return changed;
+                }
+            }
+        }
+    }
+
+    fn handle_line(&mut self, line: &str) -> bool {
+        let message = match Json::parse(line) {
+            Ok(message) => message,
+            Err(e) => {
+                eprintln!("could not read a message from the server: {}", e);
+                return false;
+            }
+        };
+
+        match message.get("type").and_then(Json::as_str) {
+            Some("welcome") => {
+                self.seat = message.get("seat").and_then(Json::as_u64).map(|seat| seat as u8);
+                false
+            }
+            Some("error") => {
+                self.waiting = false;
+                self.last_error = message.get("message").and_then(Json::as_str).map(str::to_string);
+                false
+            }
+            Some("state") => match Table::decode(&message) {
+                Some(table) => {
+                    self.table = Some(table);
+                    self.waiting = false;
+                    self.last_error = None;
+                    true
+                }
+                None => {
+                    eprintln!("the server sent a state that could not be read");
+                    false
+                }
+            },
+            _ => false,
+        }
+    }
+
+    fn send(&mut self, message: &Json) -> io::Result<()> {
+        // one write per message, so it is not held back waiting for the rest of the line
+        self.stream.write_all(format!("{}\n", message).as_bytes())
+    }
+
+    /// The seat the server gave us, once it has answered the join
+    pub fn seat(&self) -> Option<u8> {self.seat}
+
+    pub fn is_connected(&self) -> bool {self.connected}
+
+    /// True once every seat is filled and ready, so moves will be accepted
+    pub fn has_started(&self) -> bool {
+        self.table.as_ref().is_some_and(|t| t.started)
+    }
+
+    /// True if we are the one who can fill the lobby with bots
+    pub fn is_host(&self) -> bool {
+        self.seat.is_some() && self.table.as_ref().and_then(|t| t.host_seat) == self.seat
+    }
+
+    /// The seats as of the last state, in seat order
+    pub fn lobby(&self) -> &[LobbySeat] {
+        self.table.as_ref().map_or(&[], |t| t.lobby.as_slice())
+    }
+
+    /// Whether we said we are ready to start
+    pub fn is_ready(&self) -> bool {
+        self.seat.is_some_and(|seat| self.lobby().iter().any(|s| s.seat == seat && s.ready))
+    }
+
+    pub fn set_name(&mut self, name: &str) -> Result<(), String> {
+        self.send(&Json::object([
+            ("type", Json::from("name")),
+            ("name", Json::from(name)),
+        ])).map_err(|e| e.to_string())
+    }
+
+    pub fn set_ready(&mut self, ready: bool) -> Result<(), String> {
+        self.send(&Json::object([
+            ("type", Json::from("ready")),
+            ("ready", Json::from(ready)),
+        ])).map_err(|e| e.to_string())
+    }
+
+    /// Asks the server to seat a bot ("random" or "greedy") in every open seat. Only the host may.
+    pub fn fill_with_bots(&mut self, bot: &str) -> Result<(), String> {
+        if !self.is_host() {
+            return Err("only the host can fill seats with bots".to_string());
+        }
+        self.send(&Json::object([
+            ("type", Json::from("fill")),
+            ("bot", Json::from(bot)),
+        ])).map_err(|e| e.to_string())
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.table.as_ref().is_some_and(|t| t.game.is_over())
+    }
+
+    /// True if the server is waiting on us and we have not answered yet
+    pub fn is_my_turn(&self) -> bool {
+        match (&self.table, self.seat) {
+            (Some(table), Some(seat)) => {
+                self.connected && !self.waiting && self.has_started() && !table.game.is_over() && table.game.active_player().id() == seat
+            }
+            _ => false,
+        }
+    }
+
+    /// The scores by seat, as of the last state
+    pub fn scores(&self) -> Option<Vec<u32>> {
+        self.table.as_ref().map(|t| t.game.scores())
+    }
+
+    /// How many dominoes are left to draft, as of the last state
+    pub fn deck_len(&self) -> Option<usize> {
+        self.table.as_ref().map(|t| t.game.deck_len())
+    }
+
+    /// The rules the server plays by, once it has sent its first state
+    pub fn variant(&self) -> Option<Variant> {
+        self.table.as_ref().map(|t| t.game.variant())
+    }
+
+    /// The last thing the server refused, or why the connection dropped
+    pub fn last_error(&self) -> Option<&str> {self.last_error.as_deref()}
+
+    /// The moves the server would accept from the active player right now: slots while picking,
+    /// placements (see protocol.rs) while placing. An empty list while placing means discard.
+    pub fn legal_moves(&self) -> &[Json] {
+        self.table.as_ref().map_or(&[], |t| t.legal.as_slice())
+    }
+
+    /// Asks the server to put our king on the given slot of the pick draft
+    pub fn pick(&mut self, slot: usize) -> Result<(), String> {
+        self.expect_turn(Some(Phase::Picking))?;
+        self.send(&Json::object([
+            ("type", Json::from("pick")),
+            ("slot", Json::from(slot)),
+        ])).map_err(|e| e.to_string())?;
+        self.waiting = true;
+        Ok(())
+    }
+
+    /// Asks the server to place our domino. None (or a null placement) throws it away.
+    pub fn place(&mut self, placement: Option<&Json>) -> Result<(), String> {
+        self.expect_turn(Some(Phase::Placing))?;
+        self.send(&Json::object([
+            ("type", Json::from("place")),
+            ("placement", placement.cloned().unwrap_or(Json::Null)),
+        ])).map_err(|e| e.to_string())?;
+        self.waiting = true;
+        Ok(())
+    }
+
+    /// Queendomino: asks the server to buy the building in the board slot and put it on the town tile at (x, y)
+    pub fn build(&mut self, slot: usize, x: u8, y: u8) -> Result<(), String> {
+        self.expect_turn(None)?;
+        let mut message = encode_build(slot, x, y);
+        if let Json::Object(pairs) = &mut message {
+            pairs.insert(0, ("type".to_string(), Json::from("build")));
+        }
+        self.send(&message).map_err(|e| e.to_string())?;
+        self.waiting = true;
+        Ok(())
+    }
+
+    /// Queendomino: asks the server to have the dragon burn the building in the board slot
+    pub fn burn(&mut self, slot: usize) -> Result<(), String> {
+        self.expect_turn(None)?;
+        self.send(&Json::object([
+            ("type", Json::from("burn")),
+            ("slot", Json::from(slot)),
+        ])).map_err(|e| e.to_string())?;
+        self.waiting = true;
+        Ok(())
+    }
+
+    /// Queendomino: the builds we could make right now, as (board slot, x, y)
+    pub fn legal_builds(&self) -> Vec<(usize, u8, u8)> {
+        match &self.table {
+            Some(table) if self.is_my_turn() => table.game.legal_builds(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub(crate) fn place_domino(&mut self, grid_domino: Option<GridDomino>) -> Result<(), String> {
+        self.place(grid_domino.as_ref().map(encode_placement).as_ref())
+    }
+
+    /// Checks it is our turn, and time for a move of the given phase. Building and burning fit in either one.
+    fn expect_turn(&self, phase: Option<Phase>) -> Result<(), String> {
+        if !self.is_my_turn() {
+            return Err("it is not your turn".to_string());
+        }
+        if phase.is_some() && self.table.as_ref().and_then(|t| t.game.phase()) != phase {
+            return Err("it is not time for that move".to_string());
+        }
+        Ok(())
+    }
+
+    pub(crate) fn table(&self) -> Option<&Table> {self.table.as_ref()}
+}
+
+impl Drop for RemoteGame {
+    fn drop(&mut self) {
+        // The reader thread holds a clone of the stream, so dropping ours alone would not hang up
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
@@ -1,29 +1,42 @@
-use std::array;
 use rand::Rng;
-use crate::components::tile::Tile;
-use crate::components::tile::Types::{Forest, Grass, Mine, Swamp, Water, Wheat};
 use crate::components::domino::Domino;
-use crate::components::draft::{Draft, DRAFT_SIZE};
-
-pub(crate) const DECK_SIZE: usize = 48;
+use crate::components::domino_set::DominoSet;
+use crate::components::draft::Draft;
 
+#[derive(Clone)]
 pub(crate) struct Deck {
-    deck: [Domino; DECK_SIZE],
-    len: usize,
+    deck: Vec<Domino>,
 }
 
 impl Deck {
-    pub(crate) fn initial() -> Self {
-        Self {
-            deck: DOMINO_SET,
-            len: DECK_SIZE,
+    /// A full deck of every domino in the set, dealt in drafts of draft_size.
+    /// Whatever would be left over after the last full draft goes back in the box unseen.
+    pub(crate) fn from_set(set: &DominoSet, draft_size: usize) -> Self {
+        let mut deck = Self { deck: set.dominoes().to_vec() };
+        let leftover = deck.len() % draft_size;
+        for _ in 0..leftover {
+            deck.pick_random();
         }
+        deck
+    }
+
+    /// Puts back a deck holding these dominoes, like the one a server says is left
+    pub(crate) fn restore(dominoes: Vec<Domino>) -> Self {
+        Self { deck: dominoes }
     }
 
     pub(crate) fn is_empty(&self) -> bool {
-        self.len == 0
+        self.deck.is_empty()
+    }
+
+    /// How many dominoes are left to be drafted
+    pub(crate) fn len(&self) -> usize {
+        self.deck.len()
     }
 
+    /// The dominoes left to be drafted, in no particular order
+    pub(crate) fn dominoes(&self) -> &[Domino] {&self.deck}
+
     /// Picks a random domino from the remaining list (0 - len)
     pub(crate) fn pick_random(&mut self) -> Domino {
 
@@ -31,86 +44,23 @@ impl Deck {
         debug_assert!(!self.is_empty());
 
         let mut rng = rand::rng();
-        let num: usize = rng.random_range(0..self.len);
-        self.len -= 1;
-        self.deck.swap(num, self.len);
-
-        // We've moved the picked domino to the end
-        let picked = self.deck[self.len];
+        let num: usize = rng.random_range(0..self.deck.len());
+        let picked = self.deck.swap_remove(num);
 
         debug_assert!(!picked.is_null(), "Picked a null domino at idx {num}.");
 
-        // Optional but useful for debugging set the "used" domino to null
-        self.deck[self.len] = Domino::null();
-
         picked
     }
 
 
-    /// Picks the new dominoes for the draft from the deck
-    pub(crate) fn new_draft(&mut self) -> Draft {
+    /// Picks the new dominoes for the draft from the deck, one per king
+    pub(crate) fn new_draft(&mut self, size: usize) -> Draft {
 
-        // make sure we have at least 4 dominoes in the deck
-        debug_assert!(self.len >= DRAFT_SIZE);
+        // make sure we have enough dominoes in the deck
+        debug_assert!(self.len() >= size);
 
-        Draft::new(array::from_fn(|_| self.pick_random()))
+        let dominoes: Vec<Domino> = (0..size).map(|_| self.pick_random()).collect();
+        Draft::new(&dominoes)
     }
 
 }
-
-
-pub const DOMINO_SET: [Domino; 48] =
-[
-    Domino::new(1, Tile::new(Wheat, 0), Tile::new(Wheat, 0)),
-    Domino::new(2, Tile::new(Wheat, 0), Tile::new(Wheat, 0)),
-    Domino::new(3, Tile::new(Forest, 0), Tile::new(Forest, 0)),
-    Domino::new(4, Tile::new(Forest, 0), Tile::new(Forest, 0)),
-    Domino::new(5, Tile::new(Forest, 0), Tile::new(Forest, 0)),
-    Domino::new(6, Tile::new(Forest, 0), Tile::new(Forest, 0)),
-    Domino::new(7, Tile::new(Water, 0), Tile::new(Water, 0)),
-    Domino::new(8, Tile::new(Water, 0), Tile::new(Water, 0)),
-    Domino::new(9, Tile::new(Water, 0), Tile::new(Water, 0)),
-    Domino::new(10, Tile::new(Grass, 0), Tile::new(Grass, 0)),
-    Domino::new(11, Tile::new(Grass, 0), Tile::new(Grass, 0)),
-    Domino::new(12, Tile::new(Swamp, 0), Tile::new(Swamp, 0)),
-
-    Domino::new(13, Tile::new(Wheat, 0), Tile::new(Forest, 0)),
-    Domino::new(14, Tile::new(Wheat, 0), Tile::new(Water, 0)),
-    Domino::new(15, Tile::new(Wheat, 0), Tile::new(Grass, 0)),
-    Domino::new(16, Tile::new(Wheat, 0), Tile::new(Swamp, 0)),
-    Domino::new(17, Tile::new(Forest, 0), Tile::new(Water, 0)),
-    Domino::new(18, Tile::new(Forest, 0), Tile::new(Grass, 0)),
-    Domino::new(19, Tile::new(Wheat, 1), Tile::new(Forest, 0)),
-    Domino::new(20, Tile::new(Wheat, 1), Tile::new(Water, 0)),
-    Domino::new(21, Tile::new(Wheat, 1), Tile::new(Grass, 0)),
-    Domino::new(22, Tile::new(Wheat, 1), Tile::new(Swamp, 0)),
-    Domino::new(23, Tile::new(Wheat, 1), Tile::new(Mine, 0)),
-    Domino::new(24, Tile::new(Forest, 1), Tile::new(Wheat, 0)),
-
-
-    Domino::new(25, Tile::new(Forest, 1), Tile::new(Wheat, 0)),
-    Domino::new(26, Tile::new(Forest, 1), Tile::new(Wheat, 0)),
-    Domino::new(27, Tile::new(Forest, 1), Tile::new(Wheat, 0)),
-    Domino::new(28, Tile::new(Forest, 1), Tile::new(Water, 0)),
-    Domino::new(29, Tile::new(Forest, 1), Tile::new(Wheat, 0)),
-    Domino::new(30, Tile::new(Water, 1), Tile::new(Wheat, 0)),
-    Domino::new(31, Tile::new(Water, 1), Tile::new(Wheat, 0)),
-    Domino::new(32, Tile::new(Water, 1), Tile::new(Forest, 0)),
-    Domino::new(33, Tile::new(Water, 1), Tile::new(Forest, 0)),
-    Domino::new(34, Tile::new(Water, 1), Tile::new(Forest, 0)),
-    Domino::new(35, Tile::new(Water, 1), Tile::new(Forest, 0)),
-    Domino::new(36, Tile::new(Wheat, 0), Tile::new(Grass, 1)),
-
-    Domino::new(37, Tile::new(Water, 0), Tile::new(Grass, 1)),
-    Domino::new(38, Tile::new(Wheat, 0), Tile::new(Swamp, 1)),
-    Domino::new(39, Tile::new(Grass, 0), Tile::new(Swamp, 1)),
-    Domino::new(40, Tile::new(Mine, 1), Tile::new(Wheat, 0)),
-    Domino::new(41, Tile::new(Wheat, 0), Tile::new(Grass, 2)),
-    Domino::new(42, Tile::new(Water, 0), Tile::new(Grass, 2)),
-    Domino::new(43, Tile::new(Wheat, 0), Tile::new(Swamp, 2)),
-    Domino::new(44, Tile::new(Grass, 0), Tile::new(Swamp, 2)),
-    Domino::new(45, Tile::new(Mine, 2), Tile::new(Wheat, 0)),
-    Domino::new(46, Tile::new(Swamp, 0), Tile::new(Mine, 2)),
-    Domino::new(47, Tile::new(Swamp, 0), Tile::new(Mine, 2)),
-    Domino::new(48, Tile::new(Wheat, 0), Tile::new(Mine, 3)),
-];
\ No newline at end of file
@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use crate::components::giants::Mark;
 use crate::components::tile::Tile;
 use crate::components::tile::Types;
 
@@ -8,6 +9,7 @@ pub(crate) struct Domino
     id: u8,
     tile1: Tile,
     tile2: Tile,
+    mark: Option<Mark>, // Age of Giants
 }
 
 
@@ -25,7 +27,10 @@ impl PartialOrd for Domino {
 }
 
 
-const NULL_ID: u8 = 100;
+pub(crate) const NULL_ID: u8 = 100;
+
+/// The castle sits in every domino_map under this id, but it is not a real domino
+pub(crate) const CASTLE_ID: u8 = 49;
 
 impl Domino
 {
@@ -34,10 +39,16 @@ impl Domino
         Self {
             id,
             tile1,
-            tile2
+            tile2,
+            mark: None,
         }
     }
 
+    /// The same domino with a giant or a footprint on it
+    pub(crate) fn with_mark(self, mark: Mark) -> Self {
+        Self { mark: Some(mark), ..self }
+    }
+
     /// Checks if the current domino is valid
     pub(crate) fn is_null(&self) -> bool {
         self.id == NULL_ID
@@ -49,6 +60,7 @@ impl Domino
             id: NULL_ID,
             tile1: Tile::default(),
             tile2: Tile::default(),
+            mark: None,
         }
     }
 
@@ -57,6 +69,8 @@ impl Domino
     /// Grabs the id of the domino
     pub(crate) fn id(&self) -> u8 { self.id }
 
+    pub(crate) fn mark(&self) -> Option<Mark> { self.mark }
+
 
     // takes id (1 or 2) and returns the tile type of tile1 or tile2 respectivly
     pub(crate) fn get_tile_type(&self, id: usize) -> Types{
@@ -0,0 +1,190 @@
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+use crate::components::domino::{Domino, CASTLE_ID, NULL_ID};
+use crate::components::giants::Mark;
+use crate::components::tile::{Tile, Types};
+use crate::components::variant::Variant;
+use crate::json::Json;
+
+// Domino sets live in JSON files, so fan-made sets and tiny test decks need no recompiling:
+//
+//   {"name": "Kingdomino", "dominoes": [
+//       {"id": 1, "tiles": [{"terrain": "wheat", "crowns": 0}, {"terrain": "wheat", "crowns": 0}],
+//        "texture": "dominoes/domino_1.png"},
+//       ...]}
+//
+// Tiles are written the way protocol writes them. Terrains are grass, wheat, forest, swamp, water, mine and town,
+// with 0 to 3 crowns. "texture" is optional, and so is "mark" ("giant" or "footprint", for Age of Giants).
+// Textures are named like every other asset, by their path inside res/img or a theme pack folder.
+// Ids go from 1 to 255, except 49 (the castle) and 100 (the null domino), and every id is used once.
+
+/// The most crowns a single tile can show
+const MAX_CROWNS: u64 = 3;
+
+const KINGDOMINO: &str = include_str!("../../res/sets/kingdomino.json");
+const QUEENDOMINO: &str = include_str!("../../res/sets/queendomino.json");
+const AGE_OF_GIANTS: &str = include_str!("../../res/sets/age_of_giants.json");
+
+
+/// What can go wrong reading a domino set
+#[derive(Debug)]
+pub enum SetError {
+    Io(io::Error),
+    /// The file is not JSON
+    Parse(String),
+    /// The file is JSON, but not a domino set we can play with
+    Invalid(String),
+}
+
+impl fmt::Display for SetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetError::Io(e) => write!(f, "could not read the set: {}", e),
+            SetError::Parse(e) => write!(f, "the set is not valid JSON: {}", e),
+            SetError::Invalid(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+
+/// Every domino a game can be dealt, along with where their pictures are
+#[derive(Debug, Clone)]
+pub struct DominoSet {
+    name: String,
+    dominoes: Vec<Domino>,
+    textures: Vec<Option<String>>, // textures[i] belongs to dominoes[i]
+}
+
+impl DominoSet {
+
+    /// Reads and checks a set file
+    pub fn load(path: &Path) -> Result<Self, SetError> {
+        let text = std::fs::read_to_string(path).map_err(SetError::Io)?;
+        Self::parse(&text)
+    }
+
+    /// Reads and checks a set from the text of a set file
+    pub fn parse(text: &str) -> Result<Self, SetError> {
+        let json = Json::parse(text).map_err(SetError::Parse)?;
+        let name = match json.get("name") {
+            None => "unnamed set".to_string(),
+            Some(name) => name.as_str().ok_or_else(|| invalid("\"name\" has to be a string"))?.to_string(),
+        };
+        let entries = match json.get("dominoes") {
+            Some(Json::Array(entries)) => entries,
+            _ => return Err(invalid("a set needs a \"dominoes\" list")),
+        };
+
+        let mut set = Self { name, dominoes: Vec::new(), textures: Vec::new() };
+        for (idx, entry) in entries.iter().enumerate() {
+            let (domino, texture) = parse_domino(entry).map_err(|e| invalid(&format!("domino #{}: {}", idx + 1, e)))?;
+            set.push(domino, texture)?;
+        }
+        if set.is_empty() {
+            return Err(invalid("the set has no dominoes"));
+        }
+        Ok(set)
+    }
+
+    /// The sets that ship with the game, one per variant
+    pub fn builtin(variant: Variant) -> &'static DominoSet {
+        static SETS: OnceLock<[DominoSet; 3]> = OnceLock::new();
+        let sets = SETS.get_or_init(|| {
+            let builtin = |text| DominoSet::parse(text).expect("built-in domino set is broken");
+            let mut giants = builtin(KINGDOMINO);
+            giants.extend(&builtin(AGE_OF_GIANTS)).expect("Age of Giants ids clash with the base set");
+            giants.name = "Age of Giants".to_string();
+            [builtin(KINGDOMINO), builtin(QUEENDOMINO), giants]
+        });
+        match variant {
+            Variant::Kingdomino => &sets[0],
+            Variant::Queendomino => &sets[1],
+            Variant::AgeOfGiants => &sets[2],
+        }
+    }
+
+    /// Adds the dominoes of another set, like an expansion shuffled into the base game
+    pub fn extend(&mut self, other: &DominoSet) -> Result<(), SetError> {
+        for (domino, texture) in other.dominoes.iter().zip(&other.textures) {
+            self.push(*domino, texture.clone())?;
+        }
+        Ok(())
+    }
+
+    pub fn name(&self) -> &str {&self.name}
+
+    pub fn len(&self) -> usize {self.dominoes.len()}
+
+    pub fn is_empty(&self) -> bool {self.dominoes.is_empty()}
+
+    pub(crate) fn dominoes(&self) -> &[Domino] {&self.dominoes}
+
+    /// Where the picture of the domino is, if the set says
+    pub(crate) fn texture(&self, id: u8) -> Option<&str> {
+        let idx = self.dominoes.iter().position(|d| d.id() == id)?;
+        self.textures[idx].as_deref()
+    }
+
+    fn push(&mut self, domino: Domino, texture: Option<String>) -> Result<(), SetError> {
+        if self.dominoes.iter().any(|d| d.id() == domino.id()) {
+            return Err(invalid(&format!("domino id {} is used twice", domino.id())));
+        }
+        self.dominoes.push(domino);
+        self.textures.push(texture);
+        Ok(())
+    }
+}
+
+
+fn invalid(reason: &str) -> SetError {
+    SetError::Invalid(reason.to_string())
+}
+
+/// One entry of the "dominoes" list
+fn parse_domino(json: &Json) -> Result<(Domino, Option<String>), String> {
+    let id = json.get("id").and_then(Json::as_u64).ok_or("needs a whole number \"id\"")?;
+    if id == 0 || id > u8::MAX as u64 || [CASTLE_ID, NULL_ID].contains(&(id as u8)) {
+        return Err(format!("id {} is not allowed, ids go from 1 to 255 but 49 and 100 are taken", id));
+    }
+
+    let tiles = match json.get("tiles") {
+        Some(Json::Array(tiles)) if tiles.len() == 2 => tiles,
+        _ => return Err(format!("id {} needs exactly two \"tiles\"", id)),
+    };
+    let first = parse_tile(&tiles[0]).map_err(|e| format!("id {}: {}", id, e))?;
+    let second = parse_tile(&tiles[1]).map_err(|e| format!("id {}: {}", id, e))?;
+
+    let texture = match json.get("texture") {
+        None | Some(Json::Null) => None,
+        Some(texture) => Some(texture.as_str().ok_or(format!("id {}: \"texture\" has to be a path", id))?.to_string()),
+    };
+    let mut domino = Domino::new(id as u8, first, second);
+    match json.get("mark").map(|mark| mark.as_str()) {
+        None | Some(Some("none")) => {}
+        Some(Some("giant")) => domino = domino.with_mark(Mark::Giant),
+        Some(Some("footprint")) => domino = domino.with_mark(Mark::Footprint),
+        Some(_) => return Err(format!("id {}: \"mark\" is giant, footprint or none", id)),
+    }
+    Ok((domino, texture))
+}
+
+fn parse_tile(json: &Json) -> Result<Tile, String> {
+    let terrain = json.get("terrain").and_then(Json::as_str).ok_or("every tile needs a \"terrain\"")?;
+    let tile_type = match terrain {
+        "grass" => Types::Grass,
+        "wheat" => Types::Wheat,
+        "forest" => Types::Forest,
+        "swamp" => Types::Swamp,
+        "water" => Types::Water,
+        "mine" => Types::Mine,
+        "town" => Types::Town,
+        _ => return Err(format!("unknown terrain \"{}\"", terrain)),
+    };
+    let crowns = json.get("crowns").and_then(Json::as_u64).ok_or("every tile needs a whole number of \"crowns\"")?;
+    if crowns > MAX_CROWNS {
+        return Err(format!("{} crowns on one tile, the most is {}", crowns, MAX_CROWNS));
+    }
+    Ok(Tile::new(tile_type, crowns as u8))
+}
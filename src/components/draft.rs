@@ -1,45 +1,74 @@
 use crate::components::domino::Domino;
 use crate::components::player::Player;
 
+/// Dominoes per draft in a four king game, the size of every table outside Age of Giants
 pub(crate) const DRAFT_SIZE: usize = 4;
 
+/// The biggest draft there is: five kings, five dominoes
+pub(crate) const MAX_DRAFT_SIZE: usize = 5;
+
 
 #[derive(Clone)]
 pub(crate) struct Draft {
 
 
-    been_selected_by: [Option<u8>; DRAFT_SIZE],
-    draft: [Domino; DRAFT_SIZE],
+    been_selected_by: [Option<u8>; MAX_DRAFT_SIZE],
+    draft: [Domino; MAX_DRAFT_SIZE],
+    len: usize, // one domino per king, only the first len slots are used
     picked: usize,
 }
 
 impl Draft {
 
     /// Creates a new draft from sorted list of dominoes
-    pub(crate) fn new(unsorted_draft: [Domino; DRAFT_SIZE]) -> Self {
-        let mut draft = unsorted_draft;
-        draft.sort();
+    pub(crate) fn new(unsorted_draft: &[Domino]) -> Self {
+        debug_assert!(unsorted_draft.len() <= MAX_DRAFT_SIZE);
+
+        let mut draft = [Domino::null(); MAX_DRAFT_SIZE];
+        draft[..unsorted_draft.len()].copy_from_slice(unsorted_draft);
+        draft[..unsorted_draft.len()].sort();
 
         Self {
-            been_selected_by: [None; DRAFT_SIZE],
+            been_selected_by: [None; MAX_DRAFT_SIZE],
             draft,
+            len: unsorted_draft.len(),
             picked: 0,
         }
     }
 
 
 
+    /// Puts a draft back together exactly as someone else saw it: same order, same kings
+    pub(crate) fn restore(dominoes: &[Domino], kings: &[Option<u8>]) -> Self {
+        debug_assert!(dominoes.len() == kings.len() && dominoes.len() <= MAX_DRAFT_SIZE);
+
+        let mut draft = [Domino::null(); MAX_DRAFT_SIZE];
+        let mut been_selected_by = [None; MAX_DRAFT_SIZE];
+        draft[..dominoes.len()].copy_from_slice(dominoes);
+        been_selected_by[..kings.len()].copy_from_slice(kings);
+        Self {
+            been_selected_by,
+            draft,
+            len: dominoes.len(),
+            picked: kings.iter().filter(|slot| slot.is_some()).count(),
+        }
+    }
+
     /// Creates a new empty draft with len 0
     pub(crate) fn null() -> Self {
 
         Self {
-            been_selected_by: [None; DRAFT_SIZE],
-            draft: [Domino::null(); DRAFT_SIZE],
-            picked: DRAFT_SIZE,
+            been_selected_by: [None; MAX_DRAFT_SIZE],
+            draft: [Domino::null(); MAX_DRAFT_SIZE],
+            len: 0,
+            picked: 0,
         }
 
     }
 
+    /// How many dominoes the draft has, picked or not
+    pub(crate) fn len(&self) -> usize {self.len}
+
     /// Checks if the draft is null
     pub(crate) fn is_null(&self) -> bool {
         // draft is null iff the first domino inside it is null
@@ -48,7 +77,7 @@ impl Draft {
 
 
     /// Returns the new player order. Called on the pick draft as it becomes the place draft.
-    pub(crate) fn apply_new_order(&self, players: &mut [Player; DRAFT_SIZE]) {
+    pub(crate) fn apply_new_order(&self, players: &mut [Player]) {
         debug_assert!(self.is_empty());
         players.sort_by_key(|p| {
             self.been_selected_by
@@ -60,7 +89,7 @@ impl Draft {
 
     /// Returns the id of the player on the domino index of the draft (or none, if no player sits on the domino)
     pub(crate) fn player_on(&self, idx: usize) -> Option<u8> {
-        debug_assert!(self.picked <= DRAFT_SIZE);
+        debug_assert!(self.picked <= self.len);
 
         self.been_selected_by[idx]
     }
@@ -70,15 +99,15 @@ impl Draft {
     pub(crate) fn is_empty(&self) -> bool {
 
         // Make sure we haven't somehow picked more dominoes than exist in the draft
-        debug_assert!(self.picked <= DRAFT_SIZE);
+        debug_assert!(self.picked <= self.len);
 
         // Every domino in the draft has been picked.
-        self.picked == DRAFT_SIZE
+        self.picked == self.len
 
     }
 
     pub(crate) fn pickable(&self, idx: usize) -> bool {
-        debug_assert!(idx < DRAFT_SIZE);
+        debug_assert!(idx < self.len);
 
         self.been_selected_by[idx].is_none()
     }
@@ -87,7 +116,7 @@ impl Draft {
     /// Picks the domino at the index from the draft
     pub(crate) fn pick(&mut self, idx: usize, player_id: u8) -> Domino {
         // make sure we pick a valid index, and there are still dominoes left to be picked
-        debug_assert!(idx < DRAFT_SIZE && !self.is_empty());
+        debug_assert!(idx < self.len && !self.is_empty());
 
         // make sure we haven't already picked the domino
         debug_assert!(self.pickable(idx));
@@ -99,9 +128,16 @@ impl Draft {
         self.draft[idx]
     }
 
+    /// Returns the domino at the index of the draft, picked or not
+    pub(crate) fn domino(&self, idx: usize) -> Domino {
+        debug_assert!(idx < self.len);
+
+        self.draft[idx]
+    }
+
     /// Iterates through the list of dominoes in the draft
     pub fn iter(&self) -> impl Iterator<Item = &Domino> {
 
-        self.draft[..DRAFT_SIZE].iter()
+        self.draft[..self.len].iter()
     }
 }
\ No newline at end of file
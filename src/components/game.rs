@@ -0,0 +1,482 @@
+use std::fmt;
+use crate::components::deck::Deck;
+use crate::components::domino::Domino;
+use crate::components::draft::{Draft, DRAFT_SIZE};
+use crate::components::domino_set::{DominoSet, SetError};
+use crate::components::giants::{Mark, Quest, GIANT_SUPPLY};
+use crate::components::grid::{BuildRotation, PlacementError};
+use crate::components::grid_domino::GridDomino;
+use crate::components::player::Player;
+use crate::components::queendomino::{BuildersBoard, COINS_PER_POINT, DRAGON_COST};
+use crate::components::tile::Types;
+use crate::components::turn::{TurnState, MAX_PLAYERS};
+use crate::components::variant::Variant;
+use crate::controller::Phase;
+
+
+/// Why the game refused a move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MoveError {
+    GameOver,
+    WrongPhase,
+    NotPickable,
+    WrongDomino,
+    MustPlace,
+    IllegalPlacement(PlacementError),
+    WrongVariant,
+    AlreadyBuilt,
+    NoSuchBuilding,
+    NotATown,
+    CannotAfford,
+    DragonUnavailable,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::GameOver => write!(f, "the game is already over"),
+            MoveError::WrongPhase => write!(f, "it is not time for that move"),
+            MoveError::NotPickable => write!(f, "that domino can't be picked"),
+            MoveError::WrongDomino => write!(f, "that is not the domino being placed"),
+            MoveError::MustPlace => write!(f, "the domino fits somewhere, so it can't be discarded"),
+            MoveError::IllegalPlacement(reason) => write!(f, "illegal placement: {}", reason),
+            MoveError::WrongVariant => write!(f, "that move is not part of this game"),
+            MoveError::AlreadyBuilt => write!(f, "you already built this turn"),
+            MoveError::NoSuchBuilding => write!(f, "there is no building in that slot"),
+            MoveError::NotATown => write!(f, "buildings go on an empty town tile"),
+            MoveError::CannotAfford => write!(f, "not enough coins"),
+            MoveError::DragonUnavailable => write!(f, "the dragon can't be called right now"),
+        }
+    }
+}
+
+
+/// Seat colors, by player id. The fifth only plays in Age of Giants.
+pub(crate) const PLAYER_COLORS: [&str; MAX_PLAYERS as usize] = ["Blue", "Green", "Red", "Yellow", "Purple"];
+
+
+/// A headless game of Kingdomino (or one of its variants). Knows the rules and whose turn it is, but nothing about
+/// drawing or input, so bots, tournaments and the server can drive it directly.
+#[derive(Clone)]
+pub(crate) struct Game {
+    players: Vec<Player>,
+    deck: Deck,
+    pick_draft: Draft,
+    place_draft: Draft,
+    state: TurnState,
+    variant: Variant,
+
+    // Queendomino only
+    board: BuildersBoard,
+    queen: Option<u8>, // id of the player with the queen
+    built: bool, // the active king already built this turn
+    dragon_called: bool, // the dragon burns once per round
+
+    // Age of Giants only
+    giants_left: u8, // in the supply
+    quests: Vec<Quest>,
+}
+
+impl Game {
+
+    /// Creates a game with the four colored players and a freshly shuffled deck
+    pub(crate) fn new() -> Self {
+        Self::with_variant(Variant::Kingdomino)
+    }
+
+    /// Creates a four player game played by the rules of the given variant
+    pub(crate) fn with_variant(variant: Variant) -> Self {
+        Self::with_players(variant, DRAFT_SIZE)
+    }
+
+    /// Creates a game for this many players. Four, or five when the variant has room for it (Age of Giants).
+    pub(crate) fn with_players(variant: Variant, count: usize) -> Self {
+        Self::with_set(variant, count, DominoSet::builtin(variant)).expect("the built-in sets deal at least one draft")
+    }
+
+    /// Creates a game dealt from the given domino set instead of the one the variant comes with.
+    /// Fails if the set can't deal a single draft for this many players.
+    pub(crate) fn with_set(variant: Variant, count: usize, set: &DominoSet) -> Result<Self, SetError> {
+        assert!((DRAFT_SIZE..=variant.max_players()).contains(&count), "{} can't be played by {} players", variant.name(), count);
+        if set.len() < count {
+            let reason = format!("{} has {} dominoes, not enough for a draft of {}", set.name(), set.len(), count);
+            return Err(SetError::Invalid(reason));
+        }
+
+        let mut players: Vec<Player> = PLAYER_COLORS[..count].iter().enumerate()
+            .map(|(idx, color)| Player::new(idx as u8 + 1, color))
+            .collect();
+        let board = match variant {
+            Variant::Queendomino => {
+                players.iter_mut().for_each(Player::start_queendomino);
+                BuildersBoard::new()
+            }
+            Variant::Kingdomino | Variant::AgeOfGiants => BuildersBoard::default(),
+        };
+        let quests = match variant {
+            Variant::AgeOfGiants => Quest::draw(),
+            Variant::Kingdomino | Variant::Queendomino => Vec::new(),
+        };
+
+        let mut deck = Deck::from_set(set, count);
+        let pick_draft = deck.new_draft(count);
+        let state = TurnState::Setup.next(count, deck.is_empty());
+
+        Ok(Self {
+            players,
+            deck,
+            pick_draft,
+            place_draft: Draft::null(),
+            state,
+            variant,
+            board,
+            queen: None,
+            built: false,
+            dragon_called: false,
+            giants_left: GIANT_SUPPLY,
+            quests,
+        })
+    }
+
+    /// Puts a game of the given variant back together from what everyone at the table can see. Players have
+    /// to be in turn order. The deck is the dominoes left to draft, whatever set the game was dealt from.
+    pub(crate) fn restore(variant: Variant, players: Vec<Player>, pick_draft: Draft, place_draft: Draft, deck: Vec<Domino>,
+                          state: TurnState) -> Self {
+        Self {
+            players,
+            deck: Deck::restore(deck),
+            pick_draft,
+            place_draft,
+            state,
+            variant,
+            board: BuildersBoard::default(),
+            queen: None,
+            built: false,
+            dragon_called: false,
+            giants_left: 0,
+            quests: Vec::new(),
+        }
+    }
+
+    /// Queendomino: puts back the builders' board, the queen and what the active king and the dragon did this turn
+    pub(crate) fn restore_queendomino(&mut self, board: BuildersBoard, queen: Option<u8>, built: bool, dragon_called: bool) {
+        self.board = board;
+        self.queen = queen;
+        self.built = built;
+        self.dragon_called = dragon_called;
+    }
+
+    /// Age of Giants: puts back the giant supply and the quests. The giants on the kingdoms come with their players.
+    pub(crate) fn restore_giants(&mut self, giants_left: u8, quests: Vec<Quest>) {
+        self.giants_left = giants_left;
+        self.quests = quests;
+    }
+
+    pub(crate) fn players(&self) -> &[Player] {&self.players}
+    pub(crate) fn pick_draft(&self) -> &Draft {&self.pick_draft}
+    pub(crate) fn place_draft(&self) -> &Draft {&self.place_draft}
+    pub(crate) fn deck_len(&self) -> usize {self.deck.len()}
+    pub(crate) fn deck(&self) -> &Deck {&self.deck}
+
+    /// The move the active player has to make, or None once the game is over
+    pub(crate) fn phase(&self) -> Option<Phase> {self.state.phase()}
+
+    pub(crate) fn state(&self) -> TurnState {self.state}
+
+    pub(crate) fn variant(&self) -> Variant {self.variant}
+
+    /// Queendomino: the buildings for sale
+    pub(crate) fn board(&self) -> &BuildersBoard {&self.board}
+
+    /// Queendomino: the id of the player holding the queen
+    pub(crate) fn queen(&self) -> Option<u8> {self.queen}
+
+    /// Queendomino: true once the dragon has burned a building this round
+    pub(crate) fn dragon_called(&self) -> bool {self.dragon_called}
+
+    /// Queendomino: true once the active king has built this turn
+    pub(crate) fn built(&self) -> bool {self.built}
+
+    /// Age of Giants: the quests every kingdom is scored against at the end
+    pub(crate) fn quests(&self) -> &[Quest] {&self.quests}
+
+    /// Age of Giants: giants still in the supply
+    pub(crate) fn giants_left(&self) -> u8 {self.giants_left}
+
+    pub(crate) fn is_over(&self) -> bool {
+        self.state.is_over()
+    }
+
+    /// The player whose turn it is. Players are kept sorted by turn order.
+    /// Once the game is over that is the first player, for lack of anyone better.
+    pub(crate) fn active_player(&self) -> &Player {
+        &self.players[self.active_idx()]
+    }
+
+    fn active_idx(&self) -> usize {
+        self.state.king().unwrap_or_default().idx()
+    }
+
+    /// The indices of the pick draft the active player may choose from
+    pub(crate) fn legal_picks(&self) -> Vec<usize> {
+        if self.phase() != Some(Phase::Picking) {
+            return Vec::new();
+        }
+        (0..self.pick_draft.len()).filter(|&idx| self.pick_draft.pickable(idx)).collect()
+    }
+
+    /// Every legal placement of the active player's domino. Empty means it must be discarded.
+    pub(crate) fn legal_placements(&self) -> Vec<GridDomino> {
+        if self.phase() != Some(Phase::Placing) {
+            return Vec::new();
+        }
+        let player = self.active_player();
+        player.grid().legal_placements(&player.placing())
+    }
+
+    /// Current score of every player, ordered by player id
+    pub(crate) fn scores(&self) -> Vec<u32> {
+        let mut scores = vec![0; self.players.len()];
+        for player in &self.players {
+            scores[(player.id() - 1) as usize] = match self.variant {
+                Variant::Kingdomino => player.grid().score(),
+                Variant::Queendomino => self.queendomino_score(player),
+                Variant::AgeOfGiants => self.age_of_giants_score(player),
+            };
+        }
+        scores
+    }
+
+    /// The active player puts a king on the domino at idx of the pick draft
+    pub(crate) fn pick(&mut self, idx: usize) -> Result<Domino, MoveError> {
+        self.expect_phase(Phase::Picking)?;
+        if idx >= self.pick_draft.len() || !self.pick_draft.pickable(idx) {
+            return Err(MoveError::NotPickable);
+        }
+
+        let turn = self.active_idx();
+        let domino = self.pick_draft.pick(idx, self.players[turn].id());
+        self.players[turn].update_last_picked(domino);
+
+        if self.state.is_last_pick(self.players.len()) {
+            // Everyone has picked. The pick draft becomes the place draft and decides the new turn order.
+            self.place_draft = self.pick_draft.clone();
+            self.pick_draft = if self.deck.is_empty() { Draft::null() } else { self.deck.new_draft(self.players.len()) };
+            self.place_draft.apply_new_order(&mut self.players);
+            self.board.refill();
+            self.dragon_called = false;
+            self.advance(self.pick_draft.is_null());
+        } else {
+            self.advance(self.deck.is_empty());
+        }
+        Ok(domino)
+    }
+
+    /// The active player places their domino. None discards it, which is only allowed when it fits nowhere.
+    pub(crate) fn place(&mut self, grid_domino: Option<GridDomino>) -> Result<(), MoveError> {
+        self.expect_phase(Phase::Placing)?;
+
+        let turn = self.active_idx();
+        let domino = self.players[turn].placing();
+        match grid_domino {
+            Some(gd) => {
+                if *gd.domino_id() != domino.id() as usize {
+                    return Err(MoveError::WrongDomino);
+                }
+                let rotation = BuildRotation::from_radians(*gd.rotation());
+                self.players[turn].grid().check_placement(&domino, *gd.x(), *gd.y(), rotation)
+                    .map_err(MoveError::IllegalPlacement)?;
+            }
+            None => {
+                if !self.players[turn].grid().legal_placements(&domino).is_empty() {
+                    return Err(MoveError::MustPlace);
+                }
+            }
+        }
+        self.players[turn].place(grid_domino);
+        match (self.variant, grid_domino) {
+            (Variant::Queendomino, Some(gd)) => self.collect_taxes(turn, gd),
+            (Variant::AgeOfGiants, Some(_)) => self.wake_giants(turn, domino),
+            _ => {}
+        }
+        self.advance(self.deck.is_empty());
+        Ok(())
+    }
+
+    /// Moves on to the next state, giving the next king a fresh turn
+    fn advance(&mut self, deck_empty: bool) {
+        let king = self.state.king();
+        self.state = self.state.next(self.players.len(), deck_empty);
+        if self.state.king() != king {
+            self.built = false;
+        }
+    }
+
+    /// Queendomino: the active king buys the building in the slot and puts it on the town tile at (x, y).
+    /// One building per turn, at any point of it. The queen knocks a coin off the price.
+    pub(crate) fn build(&mut self, slot: usize, x: u8, y: u8) -> Result<(), MoveError> {
+        self.expect_queendomino()?;
+        if self.built {
+            return Err(MoveError::AlreadyBuilt);
+        }
+        let building = self.board.get(slot).ok_or(MoveError::NoSuchBuilding)?;
+
+        let turn = self.active_idx();
+        let player = &self.players[turn];
+        let on_town = player.grid().tile_map().get(x as usize).and_then(|row| row.get(y as usize))
+            .is_some_and(|tile| tile.get_type() == Types::Town);
+        if !on_town || player.has_building_at(x, y) {
+            return Err(MoveError::NotATown);
+        }
+
+        let discount = if self.queen == Some(player.id()) { 1 } else { 0 };
+        if !self.players[turn].spend(building.cost.saturating_sub(discount)) {
+            return Err(MoveError::CannotAfford);
+        }
+        self.board.take(slot);
+        self.players[turn].build(building, x, y);
+        self.built = true;
+
+        // The queen goes to whoever has the most towers. A tie is not enough to take her away.
+        let towers = self.players[turn].towers();
+        let holder_towers = self.queen.and_then(|id| self.players.iter().find(|p| p.id() == id)).map_or(0, Player::towers);
+        if towers > holder_towers {
+            self.queen = Some(self.players[turn].id());
+        }
+        Ok(())
+    }
+
+    /// Queendomino: the active king pays the dragon to burn the building in the slot off the board.
+    /// The dragon burns once per round, and never for whoever holds the queen.
+    pub(crate) fn burn(&mut self, slot: usize) -> Result<(), MoveError> {
+        self.expect_queendomino()?;
+        let turn = self.active_idx();
+        if self.dragon_called || self.queen == Some(self.players[turn].id()) {
+            return Err(MoveError::DragonUnavailable);
+        }
+        if self.board.get(slot).is_none() {
+            return Err(MoveError::NoSuchBuilding);
+        }
+        if !self.players[turn].spend(DRAGON_COST) {
+            return Err(MoveError::CannotAfford);
+        }
+        self.board.take(slot);
+        self.dragon_called = true;
+        Ok(())
+    }
+
+    /// Queendomino: every building the active king could build right now, as (slot, x, y)
+    pub(crate) fn legal_builds(&self) -> Vec<(usize, u8, u8)> {
+        if self.expect_queendomino().is_err() || self.built {
+            return Vec::new();
+        }
+        let player = self.active_player();
+        let discount = if self.queen == Some(player.id()) { 1 } else { 0 };
+        let mut towns = Vec::new();
+        for (x, row) in player.grid().tile_map().iter().enumerate() {
+            for (y, tile) in row.iter().enumerate() {
+                if tile.get_type() == Types::Town && !player.has_building_at(x as u8, y as u8) {
+                    towns.push((x as u8, y as u8));
+                }
+            }
+        }
+
+        let mut builds = Vec::new();
+        for (slot, building) in self.board.slots().iter().enumerate() {
+            if building.is_some_and(|b| b.cost.saturating_sub(discount) <= player.coins()) {
+                builds.extend(towns.iter().map(|&(x, y)| (slot, x, y)));
+            }
+        }
+        builds
+    }
+
+    /// Queendomino: the board slots the active king could have the dragon burn right now
+    pub(crate) fn legal_burns(&self) -> Vec<usize> {
+        if self.expect_queendomino().is_err() || self.dragon_called {
+            return Vec::new();
+        }
+        let player = self.active_player();
+        if self.queen == Some(player.id()) || player.coins() < DRAGON_COST {
+            return Vec::new();
+        }
+        (0..self.board.slots().len()).filter(|&slot| self.board.get(slot).is_some()).collect()
+    }
+
+    /// Age of Giants: a giant domino brings a giant from the supply, a footprint sends one of the placer's giants
+    /// to the opponent in the lead. Then any giant still waiting beside its kingdom looks for a crown.
+    fn wake_giants(&mut self, turn: usize, domino: Domino) {
+        match domino.mark() {
+            Some(Mark::Giant) if self.giants_left > 0 => {
+                self.giants_left -= 1;
+                self.players[turn].take_giant();
+            }
+            Some(Mark::Footprint) => {
+                let scores = self.scores();
+                let leader = (0..self.players.len()).filter(|&idx| idx != turn)
+                    .max_by_key(|&idx| (scores[(self.players[idx].id() - 1) as usize], std::cmp::Reverse(idx)));
+                if let Some(leader) = leader && self.players[turn].lose_giant() {
+                    self.players[leader].take_giant();
+                }
+            }
+            _ => {}
+        }
+        self.players[turn].settle_giants();
+    }
+
+    /// Age of Giants scoring: crowns under a giant don't count, and every kingdom scores the quests
+    fn age_of_giants_score(&self, player: &Player) -> u32 {
+        let quests: u32 = self.quests.iter().map(|quest| quest.bonus(player.grid())).sum();
+        player.grid().score_with_crowns(&player.covered_crowns()) + quests
+    }
+
+    fn expect_queendomino(&self) -> Result<(), MoveError> {
+        if self.variant != Variant::Queendomino {
+            return Err(MoveError::WrongVariant);
+        }
+        if self.is_over() {
+            return Err(MoveError::GameOver);
+        }
+        Ok(())
+    }
+
+    /// Every knight collects a coin per tile of the property they stand in. They go to the tiles of the
+    /// domino that was just placed, biggest property first, one knight per tile.
+    fn collect_taxes(&mut self, turn: usize, grid_domino: GridDomino) {
+        let player = &self.players[turn];
+        let (dx, dy) = BuildRotation::from_radians(*grid_domino.rotation()).second_offset();
+        let anchor = (*grid_domino.x() as usize, *grid_domino.y() as usize);
+        let second = ((anchor.0 as isize + dx) as usize, (anchor.1 as isize + dy) as usize);
+
+        let mut taxes: Vec<u32> = [anchor, second].iter().map(|&(x, y)| player.grid().property(x, y).len() as u32).collect();
+        taxes.sort_unstable_by(|a, b| b.cmp(a));
+        let collected = taxes.iter().take(player.knights() as usize).sum();
+        self.players[turn].earn(collected);
+    }
+
+    /// Queendomino scoring: the properties count the crowns of their buildings, and the queen's crown
+    /// in the holder's biggest property. Buildings are worth their points, and coins a point per three.
+    fn queendomino_score(&self, player: &Player) -> u32 {
+        let grid = player.grid();
+        let mut crowns: Vec<(usize, usize, i32)> = player.buildings().iter()
+            .map(|&(building, x, y)| (x as usize, y as usize, building.crowns as i32))
+            .collect();
+        if self.queen == Some(player.id()) {
+            let biggest = (0..9).flat_map(|x| (0..9).map(move |y| (x, y)))
+                .max_by_key(|&(x, y)| grid.property(x, y).len());
+            if let Some((x, y)) = biggest.filter(|&(x, y)| !grid.property(x, y).is_empty()) {
+                crowns.push((x, y, 1));
+            }
+        }
+
+        let points: u32 = player.buildings().iter().map(|(building, _, _)| building.points).sum();
+        grid.score_with_crowns(&crowns) + points + player.coins() / COINS_PER_POINT
+    }
+
+    fn expect_phase(&self, phase: Phase) -> Result<(), MoveError> {
+        match self.phase() {
+            None => Err(MoveError::GameOver),
+            Some(current) if current != phase => Err(MoveError::WrongPhase),
+            Some(_) => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use rand::seq::IndexedRandom;
+use crate::components::grid::{Grid, KINGDOM_SIZE};
+use crate::components::tile::Types;
+
+// The Age of Giants extras: giants that sit on crowns so they stop counting, the footprints that
+// send a giant to somebody else, and the quests that hand out bonus points at the end of the game.
+// Game decides when any of it happens, this is just the pieces.
+
+
+/// Giants in the box. Once they are all out, giant dominoes are just dominoes.
+pub(crate) const GIANT_SUPPLY: u8 = 6;
+
+/// Quests drawn at the start of every Age of Giants game
+pub(crate) const QUESTS_PER_GAME: usize = 2;
+
+
+/// What an Age of Giants domino shows besides its terrain. Set files put it on a domino with "mark".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Mark {
+    /// Whoever places it takes a giant from the supply and puts it on one of their own crowns
+    Giant,
+    /// Whoever places it sends one of their giants over to the leading opponent
+    Footprint,
+}
+
+impl Mark {
+
+    /// How set files and the wire spell the mark
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Mark::Giant => "giant",
+            Mark::Footprint => "footprint",
+        }
+    }
+
+    /// Reads a mark back from its name
+    pub(crate) fn from_name(name: &str) -> Option<Mark> {
+        [Mark::Giant, Mark::Footprint].into_iter().find(|mark| mark.name() == name)
+    }
+}
+
+
+/// Bonus goals scored at the end of the game. Stand-ins for the printed quest tiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Quest {
+    /// 5 points per water tile right next to the castle
+    Lakeside,
+    /// 10 points for a wheat property of at least six tiles
+    Breadbasket,
+    /// 2 points per mine tile
+    DeepMines,
+    /// 10 points for a kingdom without a single swamp tile
+    DryLand,
+    /// 3 points per crown on the eight tiles around the castle
+    CrownJewels,
+    /// 5 points per corner of a kingdom that spans the full 5x5, as long as the corner has a tile on it
+    FourCorners,
+}
+
+pub(crate) const QUESTS: [Quest; 6] = [
+    Quest::Lakeside,
+    Quest::Breadbasket,
+    Quest::DeepMines,
+    Quest::DryLand,
+    Quest::CrownJewels,
+    Quest::FourCorners,
+];
+
+impl Quest {
+
+    /// Draws the quests for a new game, never the same one twice
+    pub(crate) fn draw() -> Vec<Quest> {
+        QUESTS.choose_multiple(&mut rand::rng(), QUESTS_PER_GAME).copied().collect()
+    }
+
+    /// Reads a quest back from its name
+    pub(crate) fn from_name(name: &str) -> Option<Quest> {
+        QUESTS.iter().find(|quest| quest.name() == name).copied()
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Quest::Lakeside => "Lakeside",
+            Quest::Breadbasket => "Breadbasket",
+            Quest::DeepMines => "Deep Mines",
+            Quest::DryLand => "Dry Land",
+            Quest::CrownJewels => "Crown Jewels",
+            Quest::FourCorners => "Four Corners",
+        }
+    }
+
+    /// What the quest rewards, in a few words
+    pub(crate) fn rule(&self) -> &'static str {
+        match self {
+            Quest::Lakeside => "5 points per water tile next to the castle",
+            Quest::Breadbasket => "10 points for a wheat property of 6 tiles or more",
+            Quest::DeepMines => "2 points per mine tile",
+            Quest::DryLand => "10 points for a kingdom without a swamp tile",
+            Quest::CrownJewels => "3 points per crown on the 8 tiles around the castle",
+            Quest::FourCorners => "5 points per corner tile of a full 5x5 kingdom",
+        }
+    }
+
+    /// The points this kingdom earns from the quest
+    pub(crate) fn bonus(&self, grid: &Grid) -> u32 {
+        let tiles = grid.tile_map();
+        let count = |tile_type: Types| tiles.iter().flatten().filter(|t| t.get_type() == tile_type).count() as u32;
+        let around_castle = |reach: &[(usize, usize)]| reach.iter().map(|&(x, y)| tiles[x][y]).collect::<Vec<_>>();
+
+        match self {
+            Quest::Lakeside => {
+                let next_to = around_castle(&[(3, 4), (5, 4), (4, 3), (4, 5)]);
+                5 * next_to.iter().filter(|t| t.get_type() == Types::Water).count() as u32
+            }
+            Quest::Breadbasket => {
+                let biggest = (0..9).flat_map(|x| (0..9).map(move |y| (x, y)))
+                    .filter(|&(x, y)| tiles[x][y].get_type() == Types::Wheat)
+                    .map(|(x, y)| grid.property(x, y).len())
+                    .max();
+                if biggest.is_some_and(|size| size >= 6) { 10 } else { 0 }
+            }
+            Quest::DeepMines => 2 * count(Types::Mine),
+            Quest::DryLand => if count(Types::Swamp) == 0 { 10 } else { 0 },
+            Quest::CrownJewels => {
+                let ring = around_castle(&[(3, 3), (3, 4), (3, 5), (4, 3), (4, 5), (5, 3), (5, 4), (5, 5)]);
+                3 * ring.iter().map(|t| t.crowns() as u32).sum::<u32>()
+            }
+            Quest::FourCorners => {
+                let (lower_x, upper_x, lower_y, upper_y) = grid.bounds();
+                if upper_x - lower_x + 1 < KINGDOM_SIZE || upper_y - lower_y + 1 < KINGDOM_SIZE {
+                    return 0;
+                }
+                let corners = [(lower_x, lower_y), (lower_x, upper_y), (upper_x, lower_y), (upper_x, upper_y)];
+                5 * corners.iter().filter(|&&(x, y)| tiles[x][y].get_type() != Types::Null).count() as u32
+            }
+        }
+    }
+}
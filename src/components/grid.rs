@@ -2,9 +2,10 @@ use crate::components::domino::Domino;
 use crate::components::tile::Tile;
 use crate::components::tile::Types;
 use crate::components::grid_domino::GridDomino;
-use crate::components::deck::DOMINO_SET;
 use std::f64::consts::PI;
+use std::fmt;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BuildRotation{
     UP,
     DOWN,
@@ -13,42 +14,77 @@ pub enum BuildRotation{
 }
 
 impl BuildRotation{
-    fn next(&self) -> Self {
+    pub(crate) const ALL: [BuildRotation; 4] = [BuildRotation::UP, BuildRotation::DOWN, BuildRotation::LEFT, BuildRotation::RIGHT];
+
+    /// The rotation in radians, the way it is stored on a GridDomino
+    pub(crate) fn radians(&self) -> f64 {
         match *self {
-            BuildRotation::UP => BuildRotation::DOWN,
-            BuildRotation::DOWN => BuildRotation::LEFT,
-            BuildRotation::LEFT => BuildRotation::RIGHT,
-            BuildRotation::RIGHT => BuildRotation::UP, // Cycles back to North
+            BuildRotation::UP => 0.0,
+            BuildRotation::LEFT => PI / 2.0,
+            BuildRotation::DOWN => PI,
+            BuildRotation::RIGHT => PI * (3.0 / 2.0),
         }
     }
+
+    /// Inverse of radians(). Uses the same thresholds as Grid::translate
+    pub(crate) fn from_radians(angle: f64) -> Self {
+        if angle < PI / 4.0 {
+            BuildRotation::UP
+        } else if angle < PI * 3.0 / 4.0 {
+            BuildRotation::LEFT
+        } else if angle < PI * 5.0 / 4.0 {
+            BuildRotation::DOWN
+        } else {
+            BuildRotation::RIGHT
+        }
+    }
+
+    /// The (x, y) step from the anchor tile to the second tile of the domino
+    pub(crate) fn second_offset(&self) -> (isize, isize) {
+        match *self {
+            BuildRotation::UP => (1, 0),
+            BuildRotation::DOWN => (-1, 0),
+            BuildRotation::LEFT => (0, 1),
+            BuildRotation::RIGHT => (0, -1),
+        }
+    }
+}
+
+/// The reasons a domino can't go where someone tried to put it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PlacementError {
+    OutOfBounds,
+    Occupied,
+    NoMatchingNeighbour,
+    TooLarge,
+}
+
+impl fmt::Display for PlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            PlacementError::OutOfBounds => "the domino would hang off the map",
+            PlacementError::Occupied => "that space is already taken",
+            PlacementError::NoMatchingNeighbour => "neither tile touches a matching terrain or the castle",
+            PlacementError::TooLarge => "the kingdom would grow past 5x5",
+        };
+        write!(f, "{}", reason)
+    }
 }
 
+/// Side length of the finished kingdom
+pub(crate) const KINGDOM_SIZE: usize = 5;
+
+/// Side length of the map a kingdom is built on, the castle in the middle, so it can grow 4 tiles any way
+pub(crate) const GRID_SIZE: usize = 9;
+
 #[derive(Default, Clone)]
 pub(crate) struct Grid
 {
     // Does ALL computational heavy lifting
-    tile_map: [[Tile; 9]; 9],
-
-    // Probably want another list of maps strictly for bot use
-    bot_maps: Vec<[[Tile; 9]; 9]>,
-
-    // Idea: maybe the maps only contain sockets? Like 1 if exists 0 if not. sockets are buttons overlayed on domino map
-    // list of possible tile_maps for up
-    up_map: [[bool; 9]; 9],//Vec<Vec<Vec<Tile>>>,
-    // list of possible tile_maps for down
-    down_map: [[bool; 9]; 9],
-    // list of possible tile_maps for left
-    left_map: [[bool; 9]; 9],
-    // list of possible tile_maps for right
-    right_map: [[bool; 9]; 9],
+    tile_map: [[Tile; GRID_SIZE]; GRID_SIZE],
+
     //Does not need to be computationally efficient or useful at ALL. Only purpose is for GUI
     domino_map: Vec<GridDomino>,
-
-    // Bounds used for centering the domino_grid for the gui. dm = Domino Map
-    dm_lower_x: usize,
-    dm_upper_x: usize,
-    dm_lower_y: usize,
-    dm_upper_y: usize,
 }
 
 impl Grid
@@ -57,220 +93,165 @@ impl Grid
         let starting_map = Self::make_starting_map();
         Self {
             tile_map: starting_map, //vec![vec![Tile::new(Types::Castle, 0)]],
-            bot_maps: Default::default(),
-            up_map: [[false; 9]; 9],
-            down_map: [[false; 9]; 9],
-            left_map: [[false; 9]; 9],
-            right_map: [[false; 9]; 9],
             domino_map: vec![GridDomino::new(4,4,49,0.0)],
-
-            dm_lower_x: 4,
-            dm_upper_x: 4,
-            dm_lower_y: 4,
-            dm_upper_y: 4,
         }
     }
 
-    // Tell this function a domino and it will generate maps for up, down, left, and right
-    pub(crate) fn build_maps(&mut self, new_domino: Domino) -> bool {
-        
-
-        let mut rotation = BuildRotation::UP;
-        let anchor_type = new_domino.get_tile_type(1);
-        let second_type = new_domino.get_tile_type(2);
-
-        for _ in 0..4 { // Searching all 4 directions
-            //For each tile in our map...
-            let rows = self.tile_map.len();
-            for i in 0..rows {
-                let cols = self.tile_map[i].len();
-                for j in 0..cols {
-                    let curr_type = self.tile_map[i][j].get_type(); // short-lived borrow
-                    if curr_type == anchor_type || curr_type == Types::Castle {
-                        self.anchor_match(&rotation, i, j); // mutable borrow allowed now
-                    }
-                    if curr_type == second_type || curr_type == Types::Castle {
-                        self.second_match(&rotation, i, j);// handle second-type matches
-                    }
-                }
-            }
-            rotation = rotation.next(); //cycle to next rotation of the domino
-        }
+    pub(crate) fn domino_map(&self) -> &Vec<GridDomino> {&self.domino_map}
 
-        //empty the bot_maps vector
-        self.bot_maps.clear();
-        self.build_bot_maps(rotation, &new_domino);
+    /// Checks whether the domino can go down with its anchor tile on (x, y) in the given rotation.
+    /// x and y index the tile_map the same way GridDomino does.
+    pub(crate) fn check_placement(&self, domino: &Domino, x: u8, y: u8, rotation: BuildRotation) -> Result<(), PlacementError> {
+        let size = self.tile_map.len() as isize;
+        let anchor = (x as isize, y as isize);
+        let (dx, dy) = rotation.second_offset();
+        let second = (anchor.0 + dx, anchor.1 + dy);
 
-        //If there are no bot maps, then the player has no room to place any tiles left!
-        if 0 == self.bot_maps.len() {
-            return false
+        for (i, j) in [anchor, second] {
+            if i < 0 || j < 0 || i >= size || j >= size {
+                return Err(PlacementError::OutOfBounds);
+            }
+            if self.tile_map[i as usize][j as usize].get_type() != Types::Null {
+                return Err(PlacementError::Occupied);
+            }
         }
-        true
-                
-    }
-
-    fn anchor_match(&mut self, rotation: &BuildRotation, i: usize, j: usize) {
-        if j == 0 || i ==0 || j == 8 || i == 8 {
 
+        // The finished kingdom has to fit inside a 5x5 square, so the bounding box can't outgrow it
+        let (lower_x, upper_x, lower_y, upper_y) = self.bounds();
+        let lower_x = (lower_x as isize).min(anchor.0).min(second.0);
+        let upper_x = (upper_x as isize).max(anchor.0).max(second.0);
+        let lower_y = (lower_y as isize).min(anchor.1).min(second.1);
+        let upper_y = (upper_y as isize).max(anchor.1).max(second.1);
+        if upper_x - lower_x >= KINGDOM_SIZE as isize || upper_y - lower_y >= KINGDOM_SIZE as isize {
+            return Err(PlacementError::TooLarge);
         }
-        match *rotation {
-            BuildRotation::UP => { //UP means the tile is "right side up". Rotation = 0
-                if i != 8 && j != 8 && self.tile_map[i][j+1].get_type() == Types::Null && self.tile_map[i+1][j+1].get_type() == Types::Null {
-                    if ((i+1).saturating_sub(self.dm_lower_x)) <= 5 && ((j+1).saturating_sub(self.dm_lower_y)) <= 5 {
-                        self.up_map[i][j+1] = true
-                    }
-                }
-                if i < 7 && self.tile_map[i+1][j].get_type() == Types::Null && self.tile_map[i+2][j].get_type() == Types::Null {
-                    if ((i+2).saturating_sub(self.dm_lower_x)) <= 5 {
-                        self.up_map[i+1][j] = true
-                    }
-                }
-                if i != 8 && j != 0 && self.tile_map[i][j.saturating_sub(1)].get_type() == Types::Null && self.tile_map[i+1][j.saturating_sub(1)].get_type() == Types::Null {
-                    if ((i+1).saturating_sub(self.dm_lower_x)) <= 5 && (self.dm_upper_y.saturating_sub(j.saturating_sub(1))) <= 5 {
-                        self.up_map[i][j.saturating_sub(1)] = true
-                    }
-                }
-            }
-            BuildRotation::DOWN => { //Tile is upside down. Rotation = pi radians
-                if i != 0 && j != 8 && self.tile_map[i][j+1].get_type() == Types::Null && self.tile_map[i.saturating_sub(1)][j+1].get_type() == Types::Null {
-                    if self.dm_upper_x.saturating_sub(i.saturating_sub(1)) <= 5 && ((j+1).saturating_sub(self.dm_lower_y)) <= 5 {self.down_map[i][j+1] = true}
-                }
-                if i > 1 && self.tile_map[i.saturating_sub(1)][j].get_type() == Types::Null && self.tile_map[i.saturating_sub(2)][j].get_type() == Types::Null {
-                    if self.dm_upper_x.saturating_sub(i.saturating_sub(2)) <= 5 {self.down_map[i.saturating_sub(1)][j] = true}
-                }
-                if i != 0 && j != 0 && self.tile_map[i][j.saturating_sub(1)].get_type() == Types::Null && self.tile_map[i.saturating_sub(1)][j.saturating_sub(1)].get_type() == Types::Null {
-                    if self.dm_upper_x.saturating_sub(i.saturating_sub(1)) <= 5 && (self.dm_upper_y.saturating_sub(j.saturating_sub(1))) <= 5 {self.down_map[i][j.saturating_sub(1)] = true}
-                }
-            }
-            BuildRotation::LEFT => { //Rotation is pi/2 radians
-                if i != 8 && j != 8 && self.tile_map[i+1][j].get_type() == Types::Null && self.tile_map[i+1][j+1].get_type() == Types::Null {
-                    if ((i+1).saturating_sub(self.dm_lower_x)) <= 5 && ((j+1).saturating_sub(self.dm_lower_y)) <= 5 {self.left_map[i+1][j] = true}
-                }
-                if j < 7 && self.tile_map[i][j+1].get_type() == Types::Null && self.tile_map[i][j+2].get_type() == Types::Null {
-                    if ((j+2).saturating_sub(self.dm_lower_y)) <= 5 {self.left_map[i][j+1] = true}
-                }
-                if i != 0 && j != 8 && self.tile_map[i.saturating_sub(1)][j].get_type() == Types::Null && self.tile_map[i.saturating_sub(1)][j+1].get_type() == Types::Null {
-                    if self.dm_upper_x.saturating_sub(i.saturating_sub(1)) <= 5 && ((j+1).saturating_sub(self.dm_lower_y)) <= 5 {self.left_map[i.saturating_sub(1)][j] = true}
-                }
-            }
-            BuildRotation::RIGHT => { //Rotation is 3pi/2 radians
-                if i != 8 && j != 8 && self.tile_map[i+1][j].get_type() == Types::Null && self.tile_map[i+1][j.saturating_sub(1)].get_type() == Types::Null {
-                    if ((i+1).saturating_sub(self.dm_lower_x)) <= 5 && (self.dm_upper_y.saturating_sub(j.saturating_sub(1))) <= 5 {self.right_map[i+1][j] = true}
-                }
-                if j > 1 && self.tile_map[i][j.saturating_sub(1)].get_type() == Types::Null && self.tile_map[i][j.saturating_sub(2)].get_type() == Types::Null {
-                    if (self.dm_lower_y.saturating_sub(j.saturating_sub(2))) <= 5 {self.right_map[i][j.saturating_sub(1)] = true}
+
+        // At least one of the two tiles needs a neighbour of the same terrain (or the castle)
+        let tiles = [(anchor, domino.get_tile_type(1)), (second, domino.get_tile_type(2))];
+        for ((i, j), tile_type) in tiles {
+            for (di, dj) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                let (ni, nj) = (i + di, j + dj);
+                if ni < 0 || nj < 0 || ni >= size || nj >= size {
+                    continue;
                 }
-                if i != 0 && j != 0 && self.tile_map[i.saturating_sub(1)][j].get_type() == Types::Null && self.tile_map[i.saturating_sub(1)][j.saturating_sub(1)].get_type() == Types::Null {
-                    if self.dm_upper_x.saturating_sub(i.saturating_sub(1)) <= 5 && (self.dm_upper_y.saturating_sub(j.saturating_sub(1))) <= 5 {self.right_map[i.saturating_sub(1)][j] = true}
+                let neighbour = self.tile_map[ni as usize][nj as usize].get_type();
+                if neighbour == tile_type || neighbour == Types::Castle {
+                    return Ok(());
                 }
             }
         }
+        Err(PlacementError::NoMatchingNeighbour)
     }
-    
-    // This logic is quite complex. Lets see if I can manage not to mess it up
-    // TODO: Add the logic for this. I think can do this quite quickly tomorrow
-    fn second_match(&mut self, rotation: &BuildRotation, i: usize, j: usize){
-        match *rotation {
-            BuildRotation::UP => {
-                if i != 0 && j != 8 && self.tile_map[i][j+1].get_type() == Types::Null && self.tile_map[i.saturating_sub(1)][j+1].get_type() == Types::Null {
-                    if self.dm_upper_x.saturating_sub(i.saturating_sub(1)) <= 5 && ((j+1).saturating_sub(self.dm_lower_y)) <= 5 {self.up_map[i.saturating_sub(1)][j+1] = true}
-                }
-                if i > 1 && self.tile_map[i.saturating_sub(1)][j].get_type() == Types::Null && self.tile_map[i.saturating_sub(2)][j].get_type() == Types::Null {
-                    if self.dm_upper_x.saturating_sub(i.saturating_sub(2)) <= 5 {self.up_map[i.saturating_sub(2)][j] = true}
-                }
-                if i != 0 && j != 0 && self.tile_map[i][j.saturating_sub(1)].get_type() == Types::Null && self.tile_map[i.saturating_sub(1)][j.saturating_sub(1)].get_type() == Types::Null {
-                    if self.dm_upper_x.saturating_sub(i.saturating_sub(1)) <= 5 && (self.dm_upper_y.saturating_sub(j.saturating_sub(1))) <= 5 {self.up_map[i.saturating_sub(1)][j.saturating_sub(1)] = true}
-                }
-            }
-            BuildRotation::DOWN => {
-                if i != 8 && j != 8 && self.tile_map[i][j+1].get_type() == Types::Null && self.tile_map[i+1][j+1].get_type() == Types::Null {
-                    if ((i+1).saturating_sub(self.dm_lower_x)) <= 5 && ((j+1).saturating_sub(self.dm_lower_y)) <= 5 {self.down_map[i+1][j+1] = true}
-                }
-                if i < 7 && self.tile_map[i+1][j].get_type() == Types::Null && self.tile_map[i+2][j].get_type() == Types::Null {
-                    if ((i+2).saturating_sub(self.dm_lower_x)) <= 5 {self.down_map[i+2][j] = true}
-                }
-                if i != 8 && j != 0 && self.tile_map[i][j.saturating_sub(1)].get_type() == Types::Null && self.tile_map[i+1][j.saturating_sub(1)].get_type() == Types::Null {
-                    if ((i+1).saturating_sub(self.dm_lower_x)) <= 5 && (self.dm_upper_y.saturating_sub(j.saturating_sub(1))) <= 5 {self.down_map[i+1][j.saturating_sub(1)] = true}
+
+    /// Every legal way to put the domino into this grid. Empty if the domino has to be discarded.
+    pub(crate) fn legal_placements(&self, domino: &Domino) -> Vec<GridDomino> {
+        let mut placements = Vec::new();
+        for x in 0..self.tile_map.len() as u8 {
+            for y in 0..self.tile_map[0].len() as u8 {
+                for rotation in BuildRotation::ALL {
+                    if self.check_placement(domino, x, y, rotation).is_ok() {
+                        placements.push(GridDomino::new(x, y, domino.id() as usize, rotation.radians()));
+                    }
                 }
             }
-            BuildRotation::LEFT => {
-                if i != 8 && j != 8 && self.tile_map[i+1][j].get_type() == Types::Null && self.tile_map[i+1][j.saturating_sub(1)].get_type() == Types::Null {
-                    if ((i+1).saturating_sub(self.dm_lower_x)) <= 5 && (self.dm_upper_y.saturating_sub(j.saturating_sub(1))) <= 5 {self.left_map[i+1][j.saturating_sub(1)] = true}
-                }
-                if j > 1 && self.tile_map[i][j.saturating_sub(1)].get_type() == Types::Null && self.tile_map[i][j.saturating_sub(2)].get_type() == Types::Null {
-                    if (self.dm_lower_y.saturating_sub(j.saturating_sub(2))) <= 5 {self.left_map[i][j.saturating_sub(2)] = true}
+        }
+        placements
+    }
+
+    /// Every tile connected to (x, y) through tiles of the same terrain, (x, y) included.
+    /// Empty for empty tiles and the castle, which belong to no property.
+    pub(crate) fn property(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let tile_type = self.tile_map[x][y].get_type();
+        if tile_type == Types::Null || tile_type == Types::Castle {
+            return Vec::new();
+        }
+
+        // Flood fill the property starting from this tile
+        let mut visited = [[false; 9]; 9];
+        let mut property = Vec::new();
+        let mut stack = vec![(x, y)];
+        visited[x][y] = true;
+        while let Some((ci, cj)) = stack.pop() {
+            property.push((ci, cj));
+            for (di, dj) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                let (ni, nj) = (ci as isize + di, cj as isize + dj);
+                if ni < 0 || nj < 0 || ni >= 9 || nj >= 9 {
+                    continue;
                 }
-                if i != 0 && j != 0 && self.tile_map[i.saturating_sub(1)][j].get_type() == Types::Null && self.tile_map[i.saturating_sub(1)][j.saturating_sub(1)].get_type() == Types::Null {
-                    if self.dm_upper_x.saturating_sub(i.saturating_sub(1)) <= 5 && (self.dm_upper_y.saturating_sub(j.saturating_sub(1))) <= 5 {self.left_map[i.saturating_sub(1)][j.saturating_sub(1)] = true}
+                let (ni, nj) = (ni as usize, nj as usize);
+                if !visited[ni][nj] && self.tile_map[ni][nj].get_type() == tile_type {
+                    visited[ni][nj] = true;
+                    stack.push((ni, nj));
                 }
             }
-            BuildRotation::RIGHT => {
-                if i != 8 && j != 8 && self.tile_map[i+1][j].get_type() == Types::Null && self.tile_map[i+1][j+1].get_type() == Types::Null {
-                    if ((i+1).saturating_sub(self.dm_lower_x)) <= 5 && ((j+1).saturating_sub(self.dm_lower_y)) <= 5 {self.right_map[i+1][j+1] = true}
+        }
+        property
+    }
+
+    /// Every property of the kingdom, each one as the tiles in it
+    pub(crate) fn properties(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut visited = [[false; 9]; 9];
+        let mut properties = Vec::new();
+        for i in 0..self.tile_map.len() {
+            for j in 0..self.tile_map[i].len() {
+                if visited[i][j] {
+                    continue;
                 }
-                if j < 7 && self.tile_map[i][j+1].get_type() == Types::Null && self.tile_map[i][j+2].get_type() == Types::Null {
-                    if ((j+2).saturating_sub(self.dm_lower_y)) <= 5 {self.right_map[i][j+2] = true}
+                let property = self.property(i, j);
+                for &(x, y) in &property {
+                    visited[x][y] = true;
                 }
-                if i != 0 && j != 8 && self.tile_map[i.saturating_sub(1)][j].get_type() == Types::Null && self.tile_map[i.saturating_sub(1)][j+1].get_type() == Types::Null {
-                    if self.dm_upper_x.saturating_sub(i.saturating_sub(1)) <= 5 && ((j+1).saturating_sub(self.dm_lower_y)) <= 5 {self.right_map[i.saturating_sub(1)][j+1] = true}
+                if !property.is_empty() {
+                    properties.push(property);
                 }
             }
         }
+        properties
     }
 
-    // Builds a new tile map for each socket on each up_map, down_map, left_map, right_map
-    // This function is what builds self.bot_maps: Vec<[[Tile;9];9]>
-    fn build_bot_maps(&mut self, mut rotation: BuildRotation, new_domino: &Domino){
-        //first make a temp 9x9 array with the merged results for each other map
-        let anchor_tile = new_domino.get_tile(1);
-        let second_tile = new_domino.get_tile(2);
-        for _ in 0..4 {
-            for i in 0..self.tile_map.len() {
-                for j in 0..self.tile_map[0].len() {
-                    let mut temp_map: [[Tile;9];9] = self.tile_map.clone();
-                    match rotation {
-                        BuildRotation::UP => { 
-                            if self.up_map[i][j] {
-                                temp_map[i][j] = anchor_tile;
-                                temp_map[i+1][j] = second_tile;
-                            }
-                        }
-                        BuildRotation::DOWN => { 
-                            if self.down_map[i][j] {
-                                temp_map[i][j] = anchor_tile;
-                                temp_map[i.saturating_sub(1)][j] = second_tile;
-                            }
-                        }
-                        BuildRotation::LEFT => { 
-                            if self.left_map[i][j] {
-                                temp_map[i][j] = anchor_tile;
-                                temp_map[i][j+1] = second_tile;
-                            }
-                        }
-                        BuildRotation::RIGHT => {
-                            if self.right_map[i][j] {
-                                temp_map[i][j] = anchor_tile;
-                                temp_map[i][j.saturating_sub(1)] = second_tile;
-                            }
-                        }
-                    }
-                    self.bot_maps.push(temp_map);
+    /// Number of tiles in the biggest property, 0 while the kingdom is only a castle
+    pub(crate) fn largest_property(&self) -> usize {
+        self.properties().iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Every crown printed on the kingdom's tiles
+    pub(crate) fn crowns(&self) -> u32 {
+        self.tile_map.iter().flatten().map(|tile| tile.crowns() as u32).sum()
+    }
+
+    /// Scores the kingdom: every property (connected tiles of one terrain) is worth its size times its crowns
+    pub(crate) fn score(&self) -> u32 {
+        self.score_with_crowns(&[])
+    }
+
+    /// Like score, counting some extra crowns on top of the printed ones (Queendomino buildings and the queen).
+    /// Each entry is (x, y, crowns). Negative crowns take printed ones away, like an Age of Giants giant sitting on them.
+    pub(crate) fn score_with_crowns(&self, extra_crowns: &[(usize, usize, i32)]) -> u32 {
+        let mut visited = [[false; 9]; 9];
+        let mut total = 0;
+        for i in 0..self.tile_map.len() {
+            for j in 0..self.tile_map[i].len() {
+                if visited[i][j] {
+                    continue;
+                }
+                let property = self.property(i, j);
+                let mut crowns = 0;
+                for &(x, y) in &property {
+                    visited[x][y] = true;
+                    crowns += self.tile_map[x][y].crowns() as i32;
+                    crowns += extra_crowns.iter().filter(|e| (e.0, e.1) == (x, y)).map(|e| e.2).sum::<i32>();
                 }
+                total += property.len() as u32 * crowns.max(0) as u32;
             }
-            rotation = rotation.next();
         }
+        total
     }
 
-    //These ought 4 fns to be called upon by the gui
-    pub(crate) fn up_map(&self) -> &[[bool; 9]; 9] {&self.up_map}
-    pub(crate) fn down_map(&self) -> &[[bool; 9]; 9] {&self.down_map}
-    pub(crate) fn left_map(&self) -> &[[bool; 9]; 9] {&self.left_map}
-    pub(crate) fn right_map(&self) -> &[[bool; 9]; 9] {&self.right_map}
-    pub(crate) fn domino_map(&self) -> &Vec<GridDomino> {&self.domino_map}
-
-    //Get the bot maps
-    pub(crate) fn get_bot_maps(&self) -> &Vec<[[Tile; 9]; 9]> {
-        &self.bot_maps
+    /// The score this grid would have with the domino placed as grid_domino says. Does not change the grid.
+    pub(crate) fn score_with(&self, grid_domino: GridDomino, domino: &Domino) -> u32 {
+        let mut temp_grid = self.clone();
+        temp_grid.push_domino_map(grid_domino, domino);
+        temp_grid.score()
     }
 
     //User clicks a socket. This should be called by the gui
@@ -289,20 +270,33 @@ impl Grid
     }
 
     pub(crate) fn tile_map(&self) -> &[[Tile;9];9]{&self.tile_map}
-    pub(crate) fn dm_lower_x(&self) -> &usize{&self.dm_lower_x}
-    pub(crate) fn dm_upper_x(&self) -> &usize{&self.dm_upper_x}
-    pub(crate) fn dm_lower_y(&self) -> &usize{&self.dm_lower_y}
-    pub(crate) fn dm_upper_y(&self) -> &usize{&self.dm_upper_y}
 
-    // adds a grid domino to self.domino_map and updates self.tile_map
-    pub(crate) fn push_domino_map(&mut self, new_gd: GridDomino) {
+    /// The smallest box around every tile placed so far, as (lower x, upper x, lower y, upper y).
+    /// Used to keep the kingdom within 5x5 and to center it on screen.
+    pub(crate) fn bounds(&self) -> (usize, usize, usize, usize) {
+        let (mut lower_x, mut upper_x, mut lower_y, mut upper_y) = (usize::MAX, 0, usize::MAX, 0);
+        for i in 0..self.tile_map.len() {
+            for j in 0..self.tile_map[i].len() {
+                if self.tile_map[i][j].get_type() != Types::Null {
+                    lower_x = lower_x.min(i);
+                    upper_x = upper_x.max(i);
+                    lower_y = lower_y.min(j);
+                    upper_y = upper_y.max(j);
+                }
+            }
+        }
+        (lower_x, upper_x, lower_y, upper_y)
+    }
+
+    // adds a grid domino to self.domino_map and updates self.tile_map. The domino is the one the grid domino places.
+    pub(crate) fn push_domino_map(&mut self, new_gd: GridDomino, domino: &Domino) {
+        debug_assert_eq!(*new_gd.domino_id(), domino.id() as usize);
         self.domino_map.push(new_gd);
-        self.translate(new_gd);
+        self.translate(new_gd, domino);
     }
 
     // puts a grid domino into the tile_map
-    pub(crate) fn translate(&mut self, new_gd: GridDomino) {
-        let new_domino = DOMINO_SET[*new_gd.domino_id()];
+    pub(crate) fn translate(&mut self, new_gd: GridDomino, new_domino: &Domino) {
         self.tile_map[*new_gd.x() as usize][*new_gd.y() as usize] = new_domino.get_tile(1);
         let angle = *new_gd.rotation();
 
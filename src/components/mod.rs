@@ -1,9 +1,14 @@
 pub(crate) mod deck;
 pub(crate) mod domino;
+pub mod domino_set;
 pub(crate) mod draft;
 pub(crate) mod grid;
 pub(crate) mod tile;
-pub(crate) mod turn;
+pub mod turn;
 pub(crate) mod player;
 pub(crate) mod grid_domino;
+pub(crate) mod game;
 
+pub mod variant;
+pub(crate) mod queendomino;
+pub(crate) mod giants;
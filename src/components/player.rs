@@ -1,7 +1,7 @@
 use crate::components::domino::Domino;
-use crate::components::grid::Grid;
+use crate::components::grid::{BuildRotation, Grid};
+use crate::components::queendomino::{Building, STARTING_COINS, STARTING_KNIGHTS};
 use crate::components::grid_domino::{self, GridDomino};
-use crate::gui::{Gui, PlacementDominoRotation};
 
 #[derive(Clone)]
 pub(crate) struct Player {
@@ -10,6 +10,15 @@ pub(crate) struct Player {
     placing: Domino,
     grid: Grid,
     name: String,
+
+    // Queendomino only, they stay at zero in the base game
+    coins: u32,
+    knights: u8,
+    towers: u8,
+    buildings: Vec<(Building, u8, u8)>, // and the town tile each one stands on
+
+    // Age of Giants only. Each giant sits on a crown at (x, y), or beside the kingdom (None) until a crown turns up
+    giants: Vec<Option<(u8, u8)>>,
 }
 
 impl Player {
@@ -20,9 +29,46 @@ impl Player {
             placing: Domino::null(),
             grid: Grid::new(),
             name: name.to_string(),
+            coins: 0,
+            knights: 0,
+            towers: 0,
+            buildings: Vec::new(),
+            giants: Vec::new(),
+        }
+    }
+
+    /// Hands out what every player starts a Queendomino game with
+    pub(crate) fn start_queendomino(&mut self) {
+        self.coins = STARTING_COINS;
+        self.knights = STARTING_KNIGHTS;
+    }
+
+    /// Rebuilds a player from the dominoes they placed (in order, each with where it went) and the one they are holding.
+    /// Every placement is checked again as it is replayed, so None if one of them could never have been made.
+    pub(crate) fn restore(id: u8, name: &str, placed: &[(GridDomino, Domino)], placing: Domino) -> Option<Self> {
+        let mut player = Self::new(id, name);
+        for (grid_domino, domino) in placed {
+            let rotation = BuildRotation::from_radians(*grid_domino.rotation());
+            player.grid.check_placement(domino, *grid_domino.x(), *grid_domino.y(), rotation).ok()?;
+            player.grid.push_domino_map(*grid_domino, domino);
         }
+        player.placing = placing;
+        Some(player)
     }
-    
+
+    /// Queendomino: puts back the coins, knights, towers and buildings of a restored player
+    pub(crate) fn restore_town(&mut self, coins: u32, knights: u8, towers: u8, buildings: Vec<(Building, u8, u8)>) {
+        self.coins = coins;
+        self.knights = knights;
+        self.towers = towers;
+        self.buildings = buildings;
+    }
+
+    /// Age of Giants: puts back the giants of a restored player, where they sit
+    pub(crate) fn restore_giants(&mut self, giants: Vec<Option<(u8, u8)>>) {
+        self.giants = giants;
+    }
+
     // /// Returns the domino that this player picked last
     // pub(crate) fn last_picked(&self) -> Domino {
     //     self.picked
@@ -39,6 +85,93 @@ impl Player {
 
     pub(crate) fn grid(&self) -> &Grid {&self.grid}
 
+    pub(crate) fn coins(&self) -> u32 {self.coins}
+    pub(crate) fn knights(&self) -> u8 {self.knights}
+    pub(crate) fn towers(&self) -> u8 {self.towers}
+    pub(crate) fn buildings(&self) -> &Vec<(Building, u8, u8)> {&self.buildings}
+
+    pub(crate) fn earn(&mut self, coins: u32) {
+        self.coins += coins;
+    }
+
+    /// Pays the coins if there are enough of them. Returns false (and pays nothing) otherwise.
+    pub(crate) fn spend(&mut self, coins: u32) -> bool {
+        if coins > self.coins {
+            return false;
+        }
+        self.coins -= coins;
+        true
+    }
+
+    /// Puts up a building on the town tile at (x, y) and takes whatever it comes with
+    pub(crate) fn build(&mut self, building: Building, x: u8, y: u8) {
+        self.knights += building.knights;
+        self.towers += building.towers;
+        self.buildings.push((building, x, y));
+    }
+
+    pub(crate) fn giants(&self) -> &Vec<Option<(u8, u8)>> {&self.giants}
+
+    /// Every crown a giant sits on, as a crown taken away from its tile. Ready for Grid::score_with_crowns.
+    pub(crate) fn covered_crowns(&self) -> Vec<(usize, usize, i32)> {
+        self.giants.iter().flatten().map(|&(x, y)| (x as usize, y as usize, -1)).collect()
+    }
+
+    /// Takes in a giant and sits it on a crown
+    pub(crate) fn take_giant(&mut self) {
+        self.giants.push(None);
+        self.settle_giants();
+    }
+
+    /// Sits every giant that is waiting beside the kingdom on a crown, if there is one nobody covers yet.
+    /// Giants go where they cost the fewest points.
+    pub(crate) fn settle_giants(&mut self) {
+        for idx in 0..self.giants.len() {
+            if self.giants[idx].is_some() {
+                continue;
+            }
+            let covered = self.covered_crowns();
+            let mut best: Option<((u8, u8), u32)> = None;
+            for (x, row) in self.grid.tile_map().iter().enumerate() {
+                for (y, tile) in row.iter().enumerate() {
+                    let taken = covered.iter().filter(|c| (c.0, c.1) == (x, y)).count();
+                    if tile.crowns() as usize <= taken {
+                        continue;
+                    }
+                    let mut with_giant = covered.clone();
+                    with_giant.push((x, y, -1));
+                    let score = self.grid.score_with_crowns(&with_giant);
+                    if best.is_none_or(|(_, best_score)| score > best_score) {
+                        best = Some(((x as u8, y as u8), score));
+                    }
+                }
+            }
+            self.giants[idx] = best.map(|(spot, _)| spot);
+        }
+    }
+
+    /// Gives up the giant that hurts the most, freeing its crown. False if we have no giant to give.
+    pub(crate) fn lose_giant(&mut self) -> bool {
+        let worst = (0..self.giants.len()).max_by_key(|&idx| {
+            let mut without = self.giants.clone();
+            without.remove(idx);
+            let covered: Vec<(usize, usize, i32)> = without.iter().flatten().map(|&(x, y)| (x as usize, y as usize, -1)).collect();
+            self.grid.score_with_crowns(&covered)
+        });
+        match worst {
+            Some(idx) => {
+                self.giants.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// True if one of our buildings already stands on (x, y)
+    pub(crate) fn has_building_at(&self, x: u8, y: u8) -> bool {
+        self.buildings.iter().any(|&(_, bx, by)| (bx, by) == (x, y))
+    }
+
     pub(crate) fn update_last_picked(&mut self, domino: Domino) {
         assert_ne!(domino.id(), 100, "the domino id is {}", domino.id());
         // We are now placing the domino we stored from last round.
@@ -53,37 +186,13 @@ impl Player {
     // pub(crate) fn picked(&self)->Domino{self.picked}
     pub(crate) fn placing(&self)->Domino{self.placing}
 
-    // TODO: IMPLEMENT/
-    pub(crate) fn domino_placement(&mut self, curr_orientation: &PlacementDominoRotation, curr_socket_vec: &Vec<[f32; 6]>) -> Option<GridDomino> {
-        assert_ne!(self.placing.id(), 100, "For some reason the maps are trying to be built with a null domino");
-        let has_room_left = self.grid.build_maps(self.placing); //builds botmaps and the directional bool maps
-        if !has_room_left {
-            println!("there was no room left for player {}", self.id()); // occurs when the bot_maps vec is of len ==0
-            return None;
-        }
-        let new_domino: Option<GridDomino> = Gui::picked_socket(&self, curr_orientation, curr_socket_vec);
-        match new_domino {
-            Some(domino) => {
-                self.grid.push_domino_map(domino);
-                new_domino
-            }
-            None => {new_domino}
+    /// Puts the domino we are placing into the grid (or throws it away if there is no room for it)
+    pub(crate) fn place(&mut self, grid_domino: Option<GridDomino>) {
+        debug_assert!(!self.is_not_placing(), "player {} has nothing to place", self.id);
+        if let Some(grid_domino) = grid_domino {
+            self.grid.push_domino_map(grid_domino, &self.placing);
         }
-        /*
-TODO:
-
-        --GUI--
-        X) Gui needs to detect a click when each socket is pressed, sends griddomino back here.
-        X) self.grid.position_selected(&mut self, self.picking.id(), x, y, rotation in radians)
-        X) self.placing = Domino::null()
-        X) undraw picked domino
-        X) Undraw hand
-        */
-
-        // use **FACTS AND LOGIC** to find all possible placements for grid. Save as a vector of grids
-        // Also each grid is a vector of vectors... so... Also each grid has Tiles specifically
-        // let grid_vec: Vec<Vec<Vec<Tile>>> = self.grid.some_func(self.picked);
-        // Have gui allow the user to parse the possible options
+        self.placing = Domino::null();
     }
 
 }
\ No newline at end of file
@@ -0,0 +1,112 @@
+use rand::seq::SliceRandom;
+
+// The Queendomino extras: buildings bought from the builders' board with coins, the queen who
+// goes to whoever has the most towers, and the dragon who burns buildings off the board.
+// Game decides when any of it happens, this is just the pieces.
+
+
+/// Coins every player starts a Queendomino game with
+pub(crate) const STARTING_COINS: u32 = 7;
+
+/// Knights every player starts with. Each one collects taxes when a domino is placed.
+pub(crate) const STARTING_KNIGHTS: u8 = 1;
+
+/// What the dragon charges to burn a building
+pub(crate) const DRAGON_COST: u32 = 1;
+
+/// How many coins are worth a point at the end of the game
+pub(crate) const COINS_PER_POINT: u32 = 3;
+
+/// How many buildings are for sale at once
+pub(crate) const BOARD_SIZE: usize = 6;
+
+
+/// A building that can be put on a town tile. Whatever it shows is gained the moment it is built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Building {
+    pub(crate) id: u8,
+    pub(crate) cost: u32,
+    pub(crate) crowns: u8, // count towards the town property the building stands in
+    pub(crate) knights: u8,
+    pub(crate) towers: u8,
+    pub(crate) points: u32, // scored at the end of the game
+}
+
+const fn building(id: u8, cost: u32, crowns: u8, knights: u8, towers: u8, points: u32) -> Building {
+    Building { id, cost, crowns, knights, towers, points }
+}
+
+/// Stand-in for the printed building tiles, with the same kinds of rewards at roughly the same prices
+pub(crate) const BUILDINGS: [Building; 24] = [
+    building(1, 2, 0, 1, 0, 0),
+    building(2, 2, 0, 1, 0, 0),
+    building(3, 3, 0, 1, 0, 1),
+    building(4, 3, 0, 1, 0, 1),
+    building(5, 4, 0, 2, 0, 0),
+    building(6, 5, 0, 2, 0, 2),
+    building(7, 2, 0, 0, 1, 0),
+    building(8, 2, 0, 0, 1, 0),
+    building(9, 3, 0, 0, 1, 1),
+    building(10, 3, 0, 0, 1, 1),
+    building(11, 4, 0, 0, 2, 0),
+    building(12, 5, 0, 0, 2, 2),
+    building(13, 3, 1, 0, 0, 0),
+    building(14, 3, 1, 0, 0, 0),
+    building(15, 4, 1, 0, 0, 2),
+    building(16, 4, 1, 0, 1, 0),
+    building(17, 5, 2, 0, 0, 0),
+    building(18, 6, 2, 0, 0, 3),
+    building(19, 6, 3, 0, 0, 0),
+    building(20, 4, 0, 0, 0, 5),
+    building(21, 5, 0, 0, 0, 7),
+    building(22, 6, 0, 0, 0, 9),
+    building(23, 5, 1, 1, 0, 0),
+    building(24, 6, 1, 0, 1, 2),
+];
+
+
+/// The buildings for sale, and the shuffled stack the board is refilled from
+#[derive(Clone, Default)]
+pub(crate) struct BuildersBoard {
+    slots: [Option<Building>; BOARD_SIZE],
+    stack: Vec<Building>,
+}
+
+impl BuildersBoard {
+
+    /// A full board with the rest of the buildings shuffled underneath
+    pub(crate) fn new() -> Self {
+        let mut stack = BUILDINGS.to_vec();
+        stack.shuffle(&mut rand::rng());
+        let mut board = Self { slots: [None; BOARD_SIZE], stack };
+        board.refill();
+        board
+    }
+
+    /// Fills every empty slot from the stack, for as long as the stack lasts
+    pub(crate) fn refill(&mut self) {
+        for slot in self.slots.iter_mut().filter(|slot| slot.is_none()) {
+            *slot = self.stack.pop();
+        }
+    }
+
+    /// The board as far as anyone at the table can tell: the buildings for sale, and a stack of every one
+    /// that is neither for sale nor among the built ones
+    pub(crate) fn restore(slots: [Option<Building>; BOARD_SIZE], built: &[u8]) -> Self {
+        let for_sale: Vec<u8> = slots.iter().flatten().map(|b| b.id).collect();
+        let mut stack: Vec<Building> = BUILDINGS.iter().filter(|b| !for_sale.contains(&b.id) && !built.contains(&b.id)).copied().collect();
+        stack.shuffle(&mut rand::rng());
+        Self { slots, stack }
+    }
+
+    pub(crate) fn get(&self, slot: usize) -> Option<Building> {
+        self.slots.get(slot).copied().flatten()
+    }
+
+    /// Takes the building off the board, leaving the slot empty until the next refill
+    pub(crate) fn take(&mut self, slot: usize) -> Option<Building> {
+        self.slots.get_mut(slot)?.take()
+    }
+
+    pub(crate) fn slots(&self) -> &[Option<Building>; BOARD_SIZE] {&self.slots}
+}
@@ -7,6 +7,7 @@ pub(crate) enum Types {
     Swamp,
     Water,
     Mine,
+    Town, // Queendomino only. Buildings go here
     Castle,
 
     #[default]
@@ -31,4 +32,8 @@ impl Tile {
     pub(crate) fn get_type(&self) -> Types {
         self.tile_type
     }
+
+    pub(crate) fn crowns(&self) -> u8 {
+        self.crowns
+    }
 }
\ No newline at end of file
@@ -1,26 +1,142 @@
 use num_enum::TryFromPrimitive;
+use crate::controller::Phase;
 
-pub(crate) static NUM_PLAYERS: u8 = 4;
+/// Most kings a game can have. Five needs Age of Giants, every other game has four.
+pub(crate) static MAX_PLAYERS: u8 = 5;
 
 #[repr(u8)]
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
-pub(crate) enum Turn {
+pub enum Turn {
     #[default]Prio1,
     Prio2,
     Prio3,
     Prio4,
+    Prio5,
 }
 
 impl Turn {
 
     /// Advances the turn by one.
     pub(crate) fn advance(&mut self) {
-        let next = ((*self as u8) + 1) % NUM_PLAYERS;
+        let next = ((*self as u8) + 1) % MAX_PLAYERS;
         *self = Turn::try_from(next).unwrap();
     }
     
-    pub(crate) fn idx(&self) -> usize {
+    pub fn idx(&self) -> usize {
         *self as usize
     }
 
-}
\ No newline at end of file
+    /// True for the king who moves last in a round with this many kings
+    pub fn is_last(&self, kings: usize) -> bool {
+        self.idx() + 1 == kings
+    }
+
+}
+
+
+/// Where the game is. Kings always move in the order of the place draft (which is the order of the
+/// players array), the first round being the only one without a place draft.
+///
+/// ```text
+///     Setup
+///     -> FirstRound(Prio1) -> ... -> FirstRound(Prio4)                            everyone picks
+///     -> Round {Prio1, placed: false} -> Round {Prio1, placed: true} -> Round {Prio2, placed: false}
+///        -> ... -> Round {Prio4, placed: true}                                    place, then pick
+///     -> Round {Prio1, placed: false} -> ...                                      once per draft
+///     -> FinalRound(Prio1) -> ... -> FinalRound(Prio4)                            the deck ran out, only place
+///     -> GameOver
+/// ```
+///
+/// That is a four king game. With five kings every round goes on to Prio5.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TurnState {
+    #[default]Setup,
+    FirstRound(Turn),
+    Round { king: Turn, placed: bool },
+    FinalRound(Turn),
+    GameOver,
+}
+
+impl TurnState {
+
+    /// The state after the current king made their move (or the game was dealt, from Setup) in a game of
+    /// this many kings. deck_empty says whether the deck is out of dominoes, which decides if another draft gets dealt.
+    pub fn next(self, kings: usize, deck_empty: bool) -> Self {
+        use TurnState::*;
+
+        // Every king has picked: the next round places from that draft, if there is another one to pick from
+        let new_round = || if deck_empty { FinalRound(Turn::Prio1) } else { Round { king: Turn::Prio1, placed: false } };
+
+        match self {
+            Setup => FirstRound(Turn::Prio1),
+            FirstRound(king) if king.is_last(kings) => new_round(),
+            FirstRound(mut king) => {
+                king.advance();
+                FirstRound(king)
+            }
+            Round { king, placed: false } => Round { king, placed: true },
+            Round { king, placed: true } if king.is_last(kings) => new_round(),
+            Round { mut king, placed: true } => {
+                king.advance();
+                Round { king, placed: false }
+            }
+            FinalRound(king) if king.is_last(kings) => GameOver,
+            FinalRound(mut king) => {
+                king.advance();
+                FinalRound(king)
+            }
+            GameOver => GameOver,
+        }
+    }
+
+    /// Puts the state back together from what everyone can see: the phase, whose turn it is, and
+    /// whether there is a place draft (not in the first round) and a pick draft (not in the final round).
+    pub fn resume(phase: Option<Phase>, king: Turn, has_place_draft: bool, has_pick_draft: bool) -> Self {
+        match phase {
+            None => TurnState::GameOver,
+            Some(Phase::Picking) if !has_place_draft => TurnState::FirstRound(king),
+            Some(Phase::Picking) => TurnState::Round { king, placed: true },
+            Some(Phase::Placing) if has_pick_draft => TurnState::Round { king, placed: false },
+            Some(Phase::Placing) => TurnState::FinalRound(king),
+        }
+    }
+
+    /// The move the current king has to make. None before the deal and once the game is over.
+    pub fn phase(&self) -> Option<Phase> {
+        match self {
+            TurnState::FirstRound(_) | TurnState::Round { placed: true, .. } => Some(Phase::Picking),
+            TurnState::Round { placed: false, .. } | TurnState::FinalRound(_) => Some(Phase::Placing),
+            TurnState::Setup | TurnState::GameOver => None,
+        }
+    }
+
+    /// The king whose turn it is, by position in the place draft
+    pub fn king(&self) -> Option<Turn> {
+        match *self {
+            TurnState::FirstRound(king) | TurnState::Round { king, .. } | TurnState::FinalRound(king) => Some(king),
+            TurnState::Setup | TurnState::GameOver => None,
+        }
+    }
+
+    /// True if this move is the last pick of a round, after which the pick draft becomes the place draft
+    pub fn is_last_pick(&self, kings: usize) -> bool {
+        match self {
+            TurnState::FirstRound(king) | TurnState::Round { king, placed: true } => king.is_last(kings),
+            _ => false,
+        }
+    }
+
+    /// Whether the king on this slot of the place draft has already placed their domino this round
+    pub fn has_placed(&self, slot: usize) -> bool {
+        match *self {
+            TurnState::Round { king, placed } => slot < king.idx() || (slot == king.idx() && placed),
+            TurnState::FinalRound(king) => slot < king.idx(),
+            TurnState::GameOver => true,
+            TurnState::Setup | TurnState::FirstRound(_) => false,
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        *self == TurnState::GameOver
+    }
+}
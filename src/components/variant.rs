@@ -0,0 +1,41 @@
+use crate::components::draft::DRAFT_SIZE;
+use crate::components::turn::MAX_PLAYERS;
+
+/// Which rules a game is played with. Everything outside the base game hangs off this switch,
+/// so a Kingdomino game never sees a town, a coin or a building.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    #[default]
+    Kingdomino,
+    Queendomino,
+    AgeOfGiants, // the base game with giants, quests and room for a fifth king
+}
+
+impl Variant {
+
+    /// Reads the name used on the command line
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "kingdomino" => Some(Variant::Kingdomino),
+            "queendomino" => Some(Variant::Queendomino),
+            "age-of-giants" | "giants" => Some(Variant::AgeOfGiants),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Variant::Kingdomino => "kingdomino",
+            Variant::Queendomino => "queendomino",
+            Variant::AgeOfGiants => "age-of-giants",
+        }
+    }
+
+    /// How many kings can sit at the table. Every variant plays with four, Age of Giants with up to five.
+    pub fn max_players(&self) -> usize {
+        match self {
+            Variant::Kingdomino | Variant::Queendomino => DRAFT_SIZE,
+            Variant::AgeOfGiants => MAX_PLAYERS as usize,
+        }
+    }
+}
@@ -1,13 +1,21 @@
+use std::io;
+use std::path::Path;
 use macroquad::window::next_frame;
-use crate::components::deck::Deck;
+use macroquad::input::{is_key_pressed, KeyCode};
+use crate::animation::AnimationSpeed;
+use crate::assets::AssetSource;
+use crate::client::{RemoteGame, SeatKind};
+use crate::components::domino_set::{DominoSet, SetError};
 use crate::components::draft::Draft;
-use crate::components::grid_domino::GridDomino;
-use crate::components::turn::Turn;
-use crate::gui::Gui;
-use crate::components::player::Player;
-use crate::components::turn::Turn::{Prio1};
+use crate::components::game::Game;
+use crate::components::turn::TurnState;
+use crate::components::variant::Variant;
+use crate::event_log::{events_between, Event, EventLog};
+use crate::gui::{Gui, TownMove};
+use crate::sound::Effect;
 
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Phase {
     Picking,
     Placing
@@ -16,15 +24,11 @@ pub enum Phase {
 
 pub struct Controller {
     gui: Gui,
-    phase: Phase,
-    current_turn: Turn,
-    pick_draft: Draft,
-    place_draft: Draft,
-    deck: Deck,
-    players: [Player; 4],
-    active_player_id: usize,
-    turn_number:u8,
-    subturn_number: u8, // This is from 1-4, helps the gui know how many dominos to remove from the drawn draft
+    game: Game, // the rules and whose turn it is. A copy of the server's when playing remotely
+    remote: Option<RemoteGame>, // Some when the game is hosted by a server and we only play one seat
+    history: Vec<Game>, // the game before each move, for undo. Local games only
+    log: EventLog,
+    source: AssetSource, // where the pictures came from, to load another set's when a server plays another variant
 }
 
 impl Controller {
@@ -33,36 +37,74 @@ impl Controller {
     /// Create a controller object for the starting game state.
     pub async fn new() -> Self {
 
-        let players = [
-            Player::new(1, "Blue"),
-            Player::new(2, "Green"),
-            Player::new(3, "Red"),
-            Player::new(4, "Yellow"),
-        ];
+        Self {
+            gui:            Gui::new(DominoSet::builtin(Variant::Kingdomino), &AssetSource::default()).await,
+            game:           Game::new(),
+            remote:         None,
+            history:        Vec::new(),
+            log:            EventLog::new(),
+            source:         AssetSource::default(),
+        }
+    }
+
 
-        let mut deck = Deck::initial();
-        let draft = deck.new_draft();
+    /// Create a controller for a local game of the variant for this many players (four, or up to the variant's
+    /// max_players), dealt from the given domino set (the variant's own, or one of our own from DominoSet::load)
+    /// and drawn with pictures from the given source.
+    pub async fn with_set(set: &DominoSet, variant: Variant, players: usize, source: &AssetSource) -> Result<Self, SetError> {
+        let game = Game::with_set(variant, players, set)?;
+        Ok(Self {
+            gui:            Gui::new(set, source).await,
+            game,
+            remote:         None,
+            history:        Vec::new(),
+            log:            EventLog::new(),
+            source:         source.clone(),
+        })
+    }
 
 
-        Self {
-            gui:            Gui::new().await,
-            phase:          Phase::Picking, // the game will crash if you make this start as Placing, since it tries to draw the default domino
-            current_turn: Prio1,
-            pick_draft:     draft,
-            place_draft:    Draft::null(),
-            deck,
-            players,
-            active_player_id: 0,
-            turn_number: 0,
-            subturn_number: 0 // technically starts at zero since we dont remove the domino on the first turn. used to trim draft gui
-        }
+    /// Create a controller that plays one seat of a game hosted on a server (like "localhost:7878"),
+    /// asking for the seat of the given color. The table stays empty until the server sends its first state,
+    /// which also says which variant is played.
+    pub async fn connect(address: &str, color: Option<&str>, name: Option<&str>, source: &AssetSource) -> io::Result<Self> {
+        let remote = RemoteGame::connect(address, color, name)?;
+        let mut gui = Gui::new(DominoSet::builtin(Variant::Kingdomino), source).await;
+        gui.disable_hints(); // everyone at the table plays without help
+        Ok(Self {
+            gui,
+            game:           Game::restore(Variant::Kingdomino, Game::new().players().to_vec(), Draft::null(), Draft::null(),
+                                          Vec::new(), TurnState::Setup),
+            remote:         Some(remote),
+            history:        Vec::new(),
+            log:            EventLog::new(),
+            source:         source.clone(),
+        })
+    }
+
+
+
+    /// Writes the game's events to a log file as well as the log panel, replacing whatever the file held
+    pub fn log_to(&mut self, path: &Path) -> io::Result<()> {
+        self.log.write_to(path)
     }
 
 
+    /// How fast drafts, kings and placed dominoes move into place, or whether they just appear
+    pub fn set_animation_speed(&mut self, speed: AnimationSpeed) {
+        self.gui.set_animation_speed(speed);
+    }
+
 
     /// Starts the game.
     pub async fn start(&mut self) {
 
+        // local games are dealt before they start, network ones log their deal when the server sends it
+        if self.game.state() != TurnState::Setup {
+            let event = Event::NewRound { deck_left: self.game.deck_len(), last: false };
+            self.gui.play_event(&event);
+            self.log.record(event);
+        }
         self.run().await;
     }
 
@@ -74,8 +116,13 @@ impl Controller {
 
         while running {
 
+            let variant = self.game.variant();
             self.update(); 
-            self.gui.draw(&self.pick_draft, &self.place_draft, &self.active_player_id, &self.phase, &self.players, &self.subturn_number);
+            if self.game.variant() != variant {
+                // the server plays another variant than the table we drew before its first state
+                self.gui.load_set(DominoSet::builtin(self.game.variant()), &self.source).await;
+            }
+            self.gui.draw(&self.game, &self.log);
             next_frame().await;
 
         }
@@ -85,107 +132,203 @@ impl Controller {
 
     fn update(&mut self) {
 
+        self.gui.toggle_property_view();
+        self.gui.toggle_hints();
+        self.gui.toggle_panels();
+        self.gui.adjust_sound();
+        if self.remote.is_some() {
+            self.update_remote();
+            return;
+        }
 
-        let idx = self.current_turn.idx();
-
-        // Active player is the player at the current priority in the (possibly re-ordered) players array.
-        self.active_player_id = self.players[idx].id() as usize;
-
-        match self.phase {
-
+        if is_key_pressed(KeyCode::Backspace) {
+            self.undo();
+            return;
+        }
 
-            //will cycle 4 times per turn cycle
-            Phase::Placing => {
-                self.gui.check_r_key_pressed();
-                let temp_player = self.players.get_mut((self.subturn_number-1) as usize);
-                let some_grid_domino: Option<GridDomino>;
-                match temp_player {
-                    Some(temp_player) => {
-                        let curr_orientation = self.gui.domino_rotation();
-                        let curr_socket_vec = self.gui.get_socket_vec();
-                        some_grid_domino = temp_player.domino_placement(curr_orientation, curr_socket_vec);
-                        match some_grid_domino {
-                            Some(_) => {
-                                self.advance_turn();
-                                self.phase = Phase::Picking;
-                            }
-                            None => {}
-                        }
-                    }
-                    None => {
-                        eprintln!("Out of bounds error when accessing the players array before domino_placement")
-                    }
+        // Queendomino: a building can be bought or burned at any point of the turn
+        if self.game.variant() == Variant::Queendomino && self.game.phase().is_some() && let Some(town_move) = self.gui.town_move(&self.game) {
+            let before = self.game.clone();
+            let done = match town_move {
+                TownMove::Build(slot, x, y) => self.game.build(slot, x, y),
+                TownMove::Burn(slot) => self.game.burn(slot),
+            };
+            match done {
+                Ok(()) => self.moved(before),
+                Err(e) => {
+                    eprintln!("could not {:?}: {}", town_move, e);
+                    self.gui.play(Effect::Illegal);
                 }
             }
+            return;
+        }
 
-            Phase::Picking => {
-                
-                let picked = {
-                    // pass a mutable reference so GUI/pick logic can update player state if needed
-                    let player_ref = &mut self.players[idx];
-                    Gui::picked_draft_domino(&mut self.pick_draft, player_ref)
+        match self.game.phase() {
+
+            // The active king places the domino they picked last round
+            Some(Phase::Placing) => {
+                let player = self.game.active_player();
+                let grid_domino = if player.grid().legal_placements(&player.placing()).is_empty() {
+                    None // the domino fits nowhere
+                } else {
+                    match self.gui.clicked_placement(player).or_else(|| self.gui.keyboard_placement(player)) {
+                        Some(grid_domino) => Some(grid_domino),
+                        None => return,
+                    }
                 };
-
-                if let Some(domino) = picked {
-                    self.subturn_number = self.subturn_number.wrapping_add(1);
-                    self.players[idx].update_last_picked(domino);
-                    println!("Player {} has picked domino of id {}", self.players[idx].name(), self.players[idx].placing().id());
-
-                    
-                    if self.turn_number == 0 && self.subturn_number != 4{ //First turn the placing phase is skipped
-                        self.advance_turn();
-                        return;
+                let before = self.game.clone();
+                match self.game.place(grid_domino) {
+                    Ok(()) => self.moved(before),
+                    Err(e) => {
+                        eprintln!("could not place the domino: {}", e);
+                        self.gui.play(Effect::Illegal);
                     }
+                }
+            }
 
-                    if self.pick_draft.is_empty() {
-                        self.advance_turn();
+            Some(Phase::Picking) => {
+                let draft = self.game.pick_draft();
+                if let Some(slot) = self.gui.clicked_draft_slot().or_else(|| self.gui.keyboard_pick(draft)) {
+                    let before = self.game.clone();
+                    match self.game.pick(slot) {
+                        Ok(_) => self.moved(before),
+                        Err(e) => {
+                            eprintln!("could not pick the domino: {}", e);
+                            self.gui.play(Effect::Illegal);
+                        }
                     }
-
-                    if self.subturn_number%4==0{self.subturn_number=1; self.turn_number+=1;}
-                    self.phase = Phase::Placing;
                 }
             }
 
+            None => {
+                self.gui.set_status(format!("Game over! Scores by seat: {:?}", self.game.scores()));
+            }
         }
     }
 
 
-    /// Advanced the turn, performing any necessary actions
-    /// to do so (or ending the game, if over)
-    fn advance_turn(&mut self) {
-
-        if self.game_over() {
-            return;
+    /// Logs what a local move did and plays its sounds, and keeps the game from before it for undo
+    fn moved(&mut self, before: Game) {
+        for event in events_between(&before, &self.game) {
+            self.gui.play_event(&event);
+            self.log.record(event);
         }
+        self.history.push(before);
+    }
 
-        if self.pick_draft.is_empty() {
-            // All players have picked from the current pick_draft. Turn it into the place_draft
-            // and start the placing phase. Reset the current turn to the first placement priority.
-            self.place_draft = self.pick_draft.clone();
-            self.pick_draft = self.deck.new_draft();
-            self.place_draft.apply_new_order(&mut self.players);
 
-            // Restart Draft with first Priority
-            self.current_turn = Prio1;
-            return;
+    /// Takes back the last pick or placement, if there is one to take back
+    fn undo(&mut self) {
+        match self.history.pop() {
+            Some(game) => {
+                self.game = game;
+                self.gui.clear_focus();
+                self.gui.clear_building();
+                self.log.record(Event::Undo { player: self.game.active_player().name().clone() });
+            }
+            None => self.gui.play(Effect::Illegal), // nothing to take back
         }
-
-        // Otherwise simply advance to the next player for picking
-        self.current_turn.advance();
     }
 
 
+    /// Copies the server's table into the controller, and sends our own moves when it is our turn.
+    fn update_remote(&mut self) {
+        let Some(remote) = self.remote.as_mut() else { return };
 
-    // /// Ends the game
-    // fn end_game(&mut self) {
-    //     return
-    // }
+        if remote.poll() && let Some(table) = remote.table() {
+            let before = std::mem::replace(&mut self.game, table.game.clone());
+            for event in events_between(&before, &self.game) {
+                self.gui.play_event(&event);
+                self.log.record(event);
+            }
+        }
+        self.gui.set_local_seat(remote.seat());
+        self.gui.set_status(Self::remote_status(remote));
 
-    /// Returns true iff all players have placed their last tile
-    fn game_over(&self) -> bool {
-        false
+        if !remote.has_started() && remote.seat().is_some() {
+            if let Err(e) = Self::update_lobby(remote) {
+                eprintln!("could not reach the server: {}", e);
+            }
+            return;
+        }
+        if !remote.is_my_turn() {
+            return;
+        }
+        if self.game.variant() == Variant::Queendomino && let Some(town_move) = self.gui.town_move(&self.game) {
+            let sent = match town_move {
+                TownMove::Build(slot, x, y) => remote.build(slot, x, y),
+                TownMove::Burn(slot) => remote.burn(slot),
+            };
+            if let Err(e) = sent {
+                eprintln!("could not send the move: {}", e);
+            }
+            return;
+        }
+        let sent = match self.game.phase() {
+            Some(Phase::Picking) => match self.gui.clicked_draft_slot().or_else(|| self.gui.keyboard_pick(self.game.pick_draft())) {
+                Some(slot) => remote.pick(slot),
+                None => Ok(()),
+            },
+            Some(Phase::Placing) => {
+                let player = self.game.active_player();
+                if player.grid().legal_placements(&player.placing()).is_empty() {
+                    remote.place_domino(None) // the server's table will show the discard
+                } else {
+                    match self.gui.clicked_placement(player).or_else(|| self.gui.keyboard_placement(player)) {
+                        Some(grid_domino) => remote.place_domino(Some(grid_domino)),
+                        None => Ok(()),
+                    }
+                }
+            }
+            None => Ok(()),
+        };
+        if let Err(e) = sent {
+            eprintln!("could not send the move: {}", e);
+        }
     }
 
+    /// Before the game: Enter toggles ready, B (host only) fills the open seats with bots
+    fn update_lobby(remote: &mut RemoteGame) -> Result<(), String> {
+        if is_key_pressed(KeyCode::Enter) {
+            remote.set_ready(!remote.is_ready())?;
+        }
+        if is_key_pressed(KeyCode::B) && remote.is_host() {
+            remote.fill_with_bots("greedy")?;
+        }
+        Ok(())
+    }
 
+    /// Where the networked game stands, for the top of the screen. Lists the seats while in the lobby.
+    fn remote_status(remote: &RemoteGame) -> String {
+        let seat = match remote.seat() {
+            Some(seat) => format!("Seat {}", seat),
+            None => "Joining".to_string(),
+        };
+        let state = if let Some(error) = remote.last_error() {
+            error.to_string()
+        } else if remote.is_over() {
+            format!("game over, scores by seat: {:?}", remote.scores().unwrap_or_default())
+        } else if !remote.has_started() {
+            let mut lobby = String::from("lobby, press Enter to toggle ready");
+            if remote.is_host() {
+                lobby.push_str(", B to fill open seats with bots");
+            }
+            for seat in remote.lobby() {
+                let who = match seat.kind {
+                    SeatKind::Open => "open".to_string(),
+                    SeatKind::Player | SeatKind::Bot => seat.name.clone().unwrap_or_default(),
+                };
+                let ready = if seat.ready { " (ready)" } else { "" };
+                lobby.push_str(&format!("\n{}: {}{}", seat.color, who, ready));
+            }
+            lobby
+        } else if remote.lobby().iter().any(|seat| seat.away) {
+            "a bot is standing in for a player who left".to_string()
+        } else if remote.is_my_turn() {
+            "your turn".to_string()
+        } else {
+            "waiting for the other players".to_string()
+        };
+        format!("{} - {}", seat, state)
+    }
 }
-
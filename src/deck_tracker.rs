@@ -0,0 +1,92 @@
+use crate::components::domino::Domino;
+use crate::components::tile::Types;
+
+// What is left in the deck. Every domino of the set is known and every draft is dealt face up, so anyone
+// at the table could count this out by hand; the deck tracker does the counting for them.
+
+
+/// The chance that at least one of `matching` dominoes out of `remaining` shows up when `draft` of them are dealt
+pub fn chance_in_draft(remaining: usize, matching: usize, draft: usize) -> f64 {
+    if matching == 0 || draft == 0 || remaining == 0 {
+        return 0.0;
+    }
+    if draft > remaining - matching.min(remaining) {
+        return 1.0; // too few of the others left to fill the draft
+    }
+    // 1 - C(remaining - matching, draft) / C(remaining, draft), multiplied out one dealt domino at a time
+    let mut none = 1.0;
+    for dealt in 0..draft {
+        none *= (remaining - matching - dealt) as f64 / (remaining - dealt) as f64;
+    }
+    1.0 - none
+}
+
+
+/// One terrain's share of what is left
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TerrainCount {
+    pub(crate) terrain: Types,
+    /// Dominoes with at least one tile of this terrain
+    pub(crate) dominoes: usize,
+    /// Crowns on tiles of this terrain
+    pub(crate) crowns: usize,
+    /// Of those dominoes, the ones with a crown on this terrain
+    pub(crate) crowned: usize,
+}
+
+
+/// The deck counted out: by terrain, by pair of terrains and by crowns
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DeckSummary {
+    pub(crate) remaining: usize,
+    /// Most common first
+    pub(crate) terrains: Vec<TerrainCount>,
+    /// Each pair of terrains (in the order the set lists them) with how many dominoes have it, most common first
+    pub(crate) pairs: Vec<((Types, Types), usize)>,
+    /// How many dominoes have 0, 1, 2 and 3 crowns
+    pub(crate) by_crowns: [usize; 4],
+}
+
+impl DeckSummary {
+
+    pub(crate) fn new(dominoes: &[Domino]) -> Self {
+        let mut terrains: Vec<TerrainCount> = Vec::new();
+        let mut pairs: Vec<((Types, Types), usize)> = Vec::new();
+        let mut by_crowns = [0; 4];
+
+        for domino in dominoes {
+            let (first, second) = (domino.get_tile(1), domino.get_tile(2));
+            let crowns = first.crowns() as usize + second.crowns() as usize;
+            by_crowns[crowns.min(3)] += 1;
+
+            let pair = (first.get_type(), second.get_type());
+            match pairs.iter_mut().find(|(p, _)| *p == pair) {
+                Some((_, count)) => *count += 1,
+                None => pairs.push((pair, 1)),
+            }
+
+            for terrain in [first.get_type(), second.get_type()] {
+                if terrains.iter().any(|t| t.terrain == terrain) {
+                    continue;
+                }
+                terrains.push(TerrainCount { terrain, dominoes: 0, crowns: 0, crowned: 0 });
+            }
+            for count in terrains.iter_mut() {
+                let tiles: Vec<_> = [first, second].into_iter().filter(|tile| tile.get_type() == count.terrain).collect();
+                if tiles.is_empty() {
+                    continue;
+                }
+                count.dominoes += 1;
+                let on_terrain: usize = tiles.iter().map(|tile| tile.crowns() as usize).sum();
+                count.crowns += on_terrain;
+                if on_terrain > 0 {
+                    count.crowned += 1;
+                }
+            }
+        }
+
+        terrains.sort_by_key(|t| std::cmp::Reverse(t.dominoes));
+        pairs.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        Self { remaining: dominoes.len(), terrains, pairs, by_crowns }
+    }
+}
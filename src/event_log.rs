@@ -0,0 +1,185 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+use std::time::Instant;
+use crate::components::game::Game;
+use crate::components::grid::BuildRotation;
+use crate::components::turn::TurnState;
+use crate::controller::Phase;
+
+// What happened during the game, in words: picks, placements, discards, buildings bought and burned, score
+// changes and new rounds.
+// Each event goes to the log panel and (if one was given) a log file, one line per event.
+// Events are worked out by comparing the game before and after a move, so moves made by other seats of a
+// network game are logged the same way as our own.
+
+
+/// Something that happened in the game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Picked { player: String, domino: u8 },
+    /// `second` is where the second tile went, relative to the first (x, y)
+    Placed { player: String, domino: u8, x: u8, y: u8, second: (isize, isize) },
+    /// The domino fit nowhere in the kingdom, so it was thrown away
+    Discarded { player: String, domino: u8 },
+    Score { player: String, from: u32, to: u32 },
+    /// A new draft was dealt, or the last round started when there was none left to deal
+    NewRound { deck_left: usize, last: bool },
+    GameOver { scores: Vec<(String, u32)> },
+    /// The last move was taken back
+    Undo { player: String },
+    /// Queendomino: a building was bought off the board and put up on a town tile
+    Built { player: String, building: u8, x: u8, y: u8 },
+    /// Queendomino: the dragon burned a building off the board for this player
+    Burned { player: String, building: u8 },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::Picked { player, domino } => write!(f, "{} picked domino {}", player, domino),
+            Event::Placed { player, domino, x, y, second } => {
+                let facing = match second {
+                    (1, 0) => "right",
+                    (-1, 0) => "left",
+                    (0, 1) => "below",
+                    _ => "above",
+                };
+                write!(f, "{} placed domino {} at ({}, {}), second tile {}", player, domino, x, y, facing)
+            }
+            Event::Discarded { player, domino } => write!(f, "{} had no room for domino {} and discarded it", player, domino),
+            Event::Score { player, from, to } => write!(f, "{} scores {} (was {})", player, to, from),
+            Event::NewRound { last: true, .. } => write!(f, "Last round: every king places their final domino"),
+            Event::NewRound { deck_left, .. } => write!(f, "New round, {} dominoes left in the deck", deck_left),
+            Event::GameOver { scores } => {
+                let scores: Vec<String> = scores.iter().map(|(player, score)| format!("{} {}", player, score)).collect();
+                write!(f, "Game over: {}", scores.join(", "))
+            }
+            Event::Undo { player } => write!(f, "{} took their move back", player),
+            Event::Built { player, building, x, y } => write!(f, "{} built building {} at ({}, {})", player, building, x, y),
+            Event::Burned { player, building } => write!(f, "{} had the dragon burn building {}", player, building),
+        }
+    }
+}
+
+
+/// Everything that happened between two states of a game, in the order it would have happened
+pub(crate) fn events_between(before: &Game, after: &Game) -> Vec<Event> {
+    let mut events = Vec::new();
+    let name = |id: u8| after.players().iter().find(|p| p.id() == id).map(|p| p.name().clone()).unwrap_or_default();
+
+    if before.state() == TurnState::Setup {
+        // nothing to compare against yet, like when sitting down at a network game
+        if after.state() != TurnState::Setup {
+            events.push(Event::NewRound { deck_left: after.deck_len(), last: false });
+        }
+        return events;
+    }
+
+    // picks: dominoes that had no king before and have one now, wherever their draft went since
+    let picking = before.pick_draft();
+    for slot in (0..picking.len()).filter(|&slot| picking.pickable(slot)) {
+        let domino = picking.domino(slot).id();
+        for draft in [after.pick_draft(), after.place_draft()] {
+            for (other, _) in draft.iter().enumerate().filter(|(_, d)| d.id() == domino) {
+                if let Some(id) = draft.player_on(other) {
+                    events.push(Event::Picked { player: name(id), domino });
+                }
+            }
+        }
+    }
+
+    // placements: dominoes that are new to a kingdom
+    for player in after.players() {
+        let Some(old) = before.players().iter().find(|p| p.id() == player.id()) else { continue };
+        for grid_domino in player.grid().domino_map().iter().skip(old.grid().domino_map().len()) {
+            let (dx, dy) = BuildRotation::from_radians(*grid_domino.rotation()).second_offset();
+            events.push(Event::Placed { player: player.name().clone(), domino: *grid_domino.domino_id() as u8,
+                                        x: *grid_domino.x(), y: *grid_domino.y(), second: (dx, dy) });
+        }
+    }
+
+    // buildings that are new to a kingdom, and one that left the board without going to any kingdom
+    let mut built = Vec::new();
+    for player in after.players() {
+        let Some(old) = before.players().iter().find(|p| p.id() == player.id()) else { continue };
+        for &(building, x, y) in player.buildings().iter().skip(old.buildings().len()) {
+            built.push(building.id);
+            events.push(Event::Built { player: player.name().clone(), building: building.id, x, y });
+        }
+    }
+    if after.dragon_called() && !before.dragon_called() {
+        let gone = before.board().slots().iter().flatten()
+            .find(|b| !after.board().slots().contains(&Some(**b)) && !built.contains(&b.id));
+        if let Some(building) = gone {
+            events.push(Event::Burned { player: before.active_player().name().clone(), building: building.id });
+        }
+    }
+
+    // a discard: the placing king moved on without their kingdom growing
+    let mover = before.active_player();
+    let grew = after.players().iter().find(|p| p.id() == mover.id())
+        .is_some_and(|p| p.grid().domino_map().len() > mover.grid().domino_map().len());
+    if before.phase() == Some(Phase::Placing) && before.state() != after.state() && !grew {
+        events.push(Event::Discarded { player: mover.name().clone(), domino: mover.placing().id() });
+    }
+
+    for (id, (&from, &to)) in before.scores().iter().zip(after.scores().iter()).enumerate() {
+        if from != to {
+            events.push(Event::Score { player: name(id as u8 + 1), from, to });
+        }
+    }
+
+    let ids = |game: &Game| game.place_draft().iter().map(|d| d.id()).collect::<Vec<_>>();
+    if !after.place_draft().is_null() && ids(before) != ids(after) {
+        events.push(Event::NewRound { deck_left: after.deck_len(), last: after.pick_draft().is_null() });
+    }
+
+    if after.is_over() && !before.is_over() {
+        let scores = after.scores();
+        events.push(Event::GameOver { scores: after.players().iter().map(|p| (p.name().clone(), scores[p.id() as usize - 1])).collect() });
+    }
+    events
+}
+
+
+/// The game's events as lines of text, kept for the log panel and written to a file as they come
+pub struct EventLog {
+    lines: Vec<String>,
+    file: Option<LineWriter<File>>,
+    started: Instant,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self { lines: Vec::new(), file: None, started: Instant::now() }
+    }
+}
+
+impl EventLog {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes every event from now on to the file as well, replacing whatever it held
+    pub fn write_to(&mut self, path: &Path) -> io::Result<()> {
+        self.file = Some(LineWriter::new(File::create(path)?));
+        Ok(())
+    }
+
+    /// Adds an event, stamped with the minutes and seconds since the game started
+    pub fn record(&mut self, event: Event) {
+        let seconds = self.started.elapsed().as_secs();
+        let line = format!("[{:02}:{:02}] {}", seconds / 60, seconds % 60, event);
+        if let Some(file) = self.file.as_mut() && let Err(e) = writeln!(file, "{}", line) {
+            eprintln!("could not write to the log file, it stops here: {}", e);
+            self.file = None;
+        }
+        self.lines.push(line);
+    }
+
+    /// Every line so far, oldest first
+    pub fn lines(&self) -> &[String] {&self.lines}
+}
@@ -0,0 +1,263 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::agent::{Agent, RandomAgent};
+use crate::components::game::Game;
+use crate::components::grid_domino::GridDomino;
+use crate::json::Json;
+use crate::protocol::{decode_build, decode_placement, encode_builds, encode_burns, encode_observation};
+
+// A bot living in another process, written in whatever language, talking one JSON object per line.
+//
+// Every time the bot has to move, it gets the observation from protocol::encode_observation on its stdin:
+//   {"type": "pick" | "place", "id": 7, "seat": 2, "you": player, "opponents": [player, ...],
+//    "pick_draft": draft, "place_draft": draft, "deck_remaining": 32, "placing": domino or null,
+//    "variant": "kingdomino", "quests": ["Lakeside", ...], "giants_left": 6,
+//    "board": [building or null, ...], "queen": seat or null, "dragon_called": false, "legal": [...]}
+// Players list their "giants" too, each one the {"x", "y"} of the crown it sits on, or null while it waits,
+// and their coins, knights, towers and buildings. The quests and giants only matter in Age of Giants games,
+// the board, the queen and the dragon only in Queendomino.
+// "legal" lists the slots it may pick, or the placements it may make (empty means it has to discard).
+// It answers on stdout with {"pick": 2} or {"place": placement}, {"place": null} to discard.
+// In Queendomino every turn starts with two more questions, each only asked when there is something to do:
+//   {"type": "build", ...} lists the builds it can afford in "legal", answered with {"build": build} or {"build": null}
+//   {"type": "burn", ...} lists the slots the dragon would burn in "legal", answered with {"burn": 3} or {"burn": null}
+// Echoing "id" back is optional, but lets us throw away answers that arrive after we stopped waiting.
+// When a game ends it gets {"type": "game_over", ...} with the final grids, and no answer is expected.
+//
+// A bot that answers too late, with garbage, or with an illegal move gets a random legal move played for it,
+// or saves its coins when asked to build or burn, and that gets logged to stderr. Anything the bot writes to its own stderr shows up in ours.
+
+
+/// How long a bot gets to answer before a random move is played for it
+pub const DEFAULT_MOVE_TIMEOUT: Duration = Duration::from_secs(2);
+
+
+pub(crate) struct ExternalAgent {
+    name: String,
+    child: Child,
+    stdin: Option<ChildStdin>,
+    lines: Receiver<String>,
+    timeout: Duration,
+    request_id: usize,
+    fallback: RandomAgent,
+}
+
+impl ExternalAgent {
+
+    /// Starts the bot. The command goes through the shell, so arguments and pipes work as usual.
+    pub(crate) fn spawn(command: &str, timeout: Duration) -> io::Result<Self> {
+        let mut child = shell(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+
+        let stdin = child.stdin.take();
+        let stdout = child.stdout.take().expect("stdout was piped");
+
+        // Reading blocks, so a thread forwards every line and we can wait on the channel with a timeout
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            name: command.to_string(),
+            child,
+            stdin,
+            lines,
+            timeout,
+            request_id: 0,
+            fallback: RandomAgent::new(),
+        })
+    }
+
+    /// Writes one line to the bot. Returns false (and stops talking to it) if the pipe is gone.
+    fn send(&mut self, message: &Json) -> bool {
+        let Some(stdin) = self.stdin.as_mut() else { return false };
+        if writeln!(stdin, "{}", message).and_then(|_| stdin.flush()).is_err() {
+            eprintln!("bot '{}' closed its input, playing randomly for it from now on", self.name);
+            self.stdin = None;
+            return false;
+        }
+        true
+    }
+
+    /// Sends the observation and waits for the answer. None means we have to move for the bot.
+    fn ask(&mut self, mut request: Json) -> Option<Json> {
+        self.request_id += 1;
+        if let Json::Object(pairs) = &mut request {
+            pairs.insert(1, ("id".to_string(), Json::from(self.request_id)));
+        }
+
+        // Anything still in the pipe answers an earlier question we already gave up on
+        while self.lines.try_recv().is_ok() {}
+
+        if !self.send(&request) {
+            return None;
+        }
+
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            match self.lines.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(line) => match Json::parse(&line) {
+                    Ok(reply) => {
+                        if reply.get("id").and_then(Json::as_u64).is_some_and(|id| id as usize != self.request_id) {
+                            continue;
+                        }
+                        return Some(reply);
+                    }
+                    Err(e) => {
+                        eprintln!("bot '{}' sent something that isn't JSON ({}): {}", self.name, e, line);
+                        return None;
+                    }
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    eprintln!("bot '{}' did not answer within {:?}", self.name, self.timeout);
+                    return None;
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    eprintln!("bot '{}' exited, playing randomly for it from now on", self.name);
+                    self.stdin = None;
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+impl Agent for ExternalAgent {
+    fn name(&self) -> &str {&self.name}
+
+    fn pick(&mut self, game: &Game) -> usize {
+        let seat = game.active_player().id();
+        let reply = self.ask(encode_observation(game, seat));
+        match reply.as_ref().and_then(|r| r.get("pick")).and_then(Json::as_u64) {
+            Some(idx) => idx as usize,
+            None => {
+                if let Some(reply) = reply {
+                    eprintln!("bot '{}' answered a pick without a slot: {}", self.name, reply);
+                }
+                eprintln!("picking at random for bot '{}'", self.name);
+                self.fallback.pick(game)
+            }
+        }
+    }
+
+    fn place(&mut self, game: &Game) -> Option<GridDomino> {
+        let player = game.active_player();
+        let domino_id = player.placing().id();
+        let Some(reply) = self.ask(encode_observation(game, player.id())) else {
+            eprintln!("placing at random for bot '{}'", self.name);
+            return self.fallback.place(game);
+        };
+
+        match reply.get("place") {
+            Some(Json::Null) => None,
+            Some(placement) => match decode_placement(placement, domino_id) {
+                Some(grid_domino) => Some(grid_domino),
+                None => {
+                    eprintln!("bot '{}' sent an unreadable placement {}, placing at random", self.name, placement);
+                    self.fallback.place(game)
+                }
+            },
+            None => {
+                eprintln!("bot '{}' answered a placement without \"place\": {}, placing at random", self.name, reply);
+                self.fallback.place(game)
+            }
+        }
+    }
+
+    fn build(&mut self, game: &Game) -> Option<(usize, u8, u8)> {
+        if game.legal_builds().is_empty() {
+            return None;
+        }
+        let reply = self.ask(question(game, "build", encode_builds(game)))?;
+        match reply.get("build") {
+            Some(Json::Null) => None,
+            Some(build) => {
+                let build = decode_build(build);
+                if build.is_none() {
+                    eprintln!("bot '{}' sent an unreadable build {}, building nothing", self.name, reply);
+                }
+                build
+            }
+            None => {
+                eprintln!("bot '{}' answered a build without \"build\": {}, building nothing", self.name, reply);
+                None
+            }
+        }
+    }
+
+    fn burn(&mut self, game: &Game) -> Option<usize> {
+        if game.legal_burns().is_empty() {
+            return None;
+        }
+        let reply = self.ask(question(game, "burn", encode_burns(game)))?;
+        match reply.get("burn") {
+            Some(Json::Null) => None,
+            Some(slot) => {
+                let slot = slot.as_u64().map(|slot| slot as usize);
+                if slot.is_none() {
+                    eprintln!("bot '{}' sent an unreadable burn {}, burning nothing", self.name, reply);
+                }
+                slot
+            }
+            None => {
+                eprintln!("bot '{}' answered a burn without \"burn\": {}, burning nothing", self.name, reply);
+                None
+            }
+        }
+    }
+
+    fn game_over(&mut self, game: &Game, seat: u8) {
+        self.send(&encode_observation(game, seat));
+    }
+}
+
+impl Drop for ExternalAgent {
+    fn drop(&mut self) {
+        // Closing stdin tells a well behaved bot to exit. The rest get killed.
+        self.stdin = None;
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// The active player's observation, asking a Queendomino question instead of the usual move
+fn question(game: &Game, kind: &str, legal: Json) -> Json {
+    let mut observation = encode_observation(game, game.active_player().id());
+    if let Json::Object(pairs) = &mut observation {
+        for (key, value) in pairs.iter_mut() {
+            match key.as_str() {
+                "type" => *value = Json::from(kind),
+                "legal" => *value = legal.clone(),
+                _ => {}
+            }
+        }
+    }
+    observation
+}
+
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
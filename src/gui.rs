@@ -2,14 +2,38 @@ use std::f64::consts::PI;
 use macroquad::color::WHITE;
 use macroquad::input::{is_mouse_button_pressed, MouseButton};
 use macroquad::prelude::*;
-use crate::assets::Assets;
-use crate::components::{draft, grid, grid_domino};
+use crate::assets::{AssetSource, Assets};
+use crate::hit::{Button, HitMap, Target};
+use crate::layout::{self, DraftColumn, Layout, Rect};
+use crate::animation::AnimationSpeed;
+use crate::components::grid::{BuildRotation, PlacementError};
 use crate::components::grid_domino::GridDomino;
 use crate::controller::Phase;
-use crate::components::domino::Domino;
-use crate::components::draft::{Draft, DRAFT_SIZE};
+use crate::components::domino::{Domino, CASTLE_ID};
+use crate::components::domino_set::DominoSet;
+use crate::components::draft::{Draft, DRAFT_SIZE, MAX_DRAFT_SIZE};
+use crate::components::game::Game;
+use crate::components::turn::TurnState;
 use crate::components::player::Player;
-use crate::gui::text_bank::{PICKING_ADVICE, PLACING_ADVICE};
+use crate::components::variant::Variant;
+use crate::event_log::EventLog;
+use crate::scoreboard::{self, ScoreTicker};
+use crate::sound::Effect;
+use crate::tile_art;
+use crate::gui::text_bank::{GAME_OVER_ADVICE, PICKING_ADVICE, PLACING_ADVICE, SETUP_ADVICE, VIEW_ADVICE, HINT_ADVICE, SOUND_ADVICE, BUILD_ADVICE};
+
+mod animate;
+mod audio;
+mod drag;
+mod giants;
+mod hints;
+mod keyboard;
+mod loading;
+mod panels;
+mod properties;
+mod town;
+
+pub(crate) use town::TownMove;
 
 mod board_gui {
     use macroquad::prelude::Color;
@@ -19,32 +43,52 @@ mod board_gui {
     pub(crate) const GREEN: Color = Color::from_rgba(50, 176, 92, 255);
     pub(crate) const RED: Color = Color::from_rgba(238, 131, 138, 255);
     pub(crate) const YELLOW: Color = Color::from_rgba(232, 189, 2, 255);
-    pub(crate) const SCROLL_SIZE: f32 = 75.0;
-    pub(crate) const SCORE_KING_SIZE: f32 = 75.0;
-}
-
-mod grid_multipliers_gui {
-    use crate::gui::draft_gui;
-
-    pub(crate) const X_MULTIPLIER: f32 = draft_gui::DOMINO_TILE_SIZE;
-    pub(crate) const Y_MULTIPLIER: f32 = draft_gui::DOMINO_TILE_SIZE;
+    pub(crate) const PURPLE: Color = Color::from_rgba(150, 96, 200, 255);
+    // Stand-in kings when the king pictures are missing, by player id - 1
+    pub(crate) const KING_COLORS: [Color; 5] = [BLUE, GREEN, RED, YELLOW, PURPLE];
+    pub(crate) const STATUS_FONT_SIZE: f32 = 24.0;
+    // The held domino snapped to the kingdom: how see through it is, and the tint saying if it fits there
+    pub(crate) const GHOST_ALPHA: f32 = 0.75;
+    pub(crate) const LEGAL_TINT: Color = Color::new(0.2, 0.9, 0.3, 0.35);
+    pub(crate) const ILLEGAL_TINT: Color = Color::new(0.95, 0.2, 0.2, 0.4);
+    // Width of the outline around whatever the keyboard is pointing at
+    pub(crate) const FOCUS_THICKNESS: f32 = 4.0;
+    // The property overlays: the property under the mouse, and what goes behind the score labels
+    pub(crate) const PROPERTY_HOVER: Color = Color::new(1.0, 1.0, 1.0, 0.35);
+    pub(crate) const LABEL_BACKGROUND: Color = Color::new(0.0, 0.0, 0.0, 0.6);
+    // How strongly the placement hint colors the sockets
+    pub(crate) const HINT_ALPHA: f32 = 0.45;
 }
 
 mod text_bank {
     pub(crate) const PICKING_ADVICE: &str = "
     Phase: Picking\n
-    Click on a domino to place a king there.\n
+    Click on a domino to place a king there,\n
+    or pick one with the arrow keys and Enter.\n
+    Backspace takes the last move back.\n
     Active Player:
     ";
     pub(crate) const PLACING_ADVICE: &str = "
     Phase: Placing\n
-    press 'r' to rotate, click to place\n
-    domino into a socket. Available sockets\n
-    are only shown for the current orientation.\n
-    A socket represents where the TILE WITH\n
-    THE HAND ON IT will be placed\n
+    Drag your domino from the draft onto\n
+    your kingdom and let go where it fits.\n
+    The mouse wheel or a right click turns\n
+    it while you hold it. The TILE WITH THE\n
+    HAND ON IT goes where you let go\n
+    Or: arrows move, Q/E rotate, Enter places\n
     Active Player:
     ";
+    pub(crate) const SETUP_ADVICE: &str = "
+    Waiting for the game to be dealt\n
+    ";
+    pub(crate) const GAME_OVER_ADVICE: &str = "
+    Game over!\n
+    The kingdom with the most points wins\n
+    ";
+    pub(crate) const VIEW_ADVICE: &str = "P: properties  H: hover  A: draft  D: deck  L: log";
+    pub(crate) const SOUND_ADVICE: &str = "S: sound  M: mute";
+    pub(crate) const HINT_ADVICE: &str = "V: hint";
+    pub(crate) const BUILD_ADVICE: &str = "B: builders' board";
 }
 
 pub enum PlacementDominoRotation {
@@ -54,6 +98,16 @@ pub enum PlacementDominoRotation {
     RIGHT
 }
 impl PlacementDominoRotation{
+    /// The same rotation, the way the grid names it
+    pub(crate) fn build_rotation(&self) -> BuildRotation {
+        match *self {
+            PlacementDominoRotation::UP => BuildRotation::UP,
+            PlacementDominoRotation::LEFT => BuildRotation::LEFT,
+            PlacementDominoRotation::DOWN => BuildRotation::DOWN,
+            PlacementDominoRotation::RIGHT => BuildRotation::RIGHT,
+        }
+    }
+
     fn next(&self) -> Self {
         match *self {
             PlacementDominoRotation::UP => PlacementDominoRotation::LEFT,
@@ -62,227 +116,270 @@ impl PlacementDominoRotation{
             PlacementDominoRotation::RIGHT => PlacementDominoRotation::UP,
         }
     }
-}
-
-/// Holds the constants related to the display of the draft
-mod draft_gui {
 
-    pub(crate) const PICK_DOMINO_X: f32 = 100.0;
-    pub(crate) const PLACE_DOMINO_X: f32 = 220.0;
-    pub(crate) const DOMINO_TILE_SIZE: f32 = 50.0;
-
-    // The vertical spacing between the draft dominoes
-    pub(crate) const VERT_OFFSET: f32 = 60.0;
+    fn prev(&self) -> Self {
+        match *self {
+            PlacementDominoRotation::UP => PlacementDominoRotation::RIGHT,
+            PlacementDominoRotation::LEFT => PlacementDominoRotation::UP,
+            PlacementDominoRotation::DOWN => PlacementDominoRotation::LEFT,
+            PlacementDominoRotation::RIGHT => PlacementDominoRotation::DOWN,
+        }
+    }
 }
 
 pub(crate) struct Gui {
     assets: Assets,
     domino_rotation: PlacementDominoRotation,
-    blue_offset: [f32;2], //map offsets
-    green_offset: [f32;2], //map offsets
-    red_offset: [f32;2], //map offsets
-    yellow_offset: [f32;2], //map offsets
-    curr_socket_locations: Vec<[f32; 6]> // [socket_row, socket_col, x_lower, x_upper, y_lower, y_upper]
+    layout: Layout, // worked out again every frame, so the window can be resized
+    map_offsets: Vec<[f32;2]>, // where cell (0, 0) of each player's kingdom is drawn, by player id - 1
+    hits: HitMap, // what can be clicked, as drawn last frame
+    focus: Option<keyboard::Focus>, // where the keyboard is pointing, None while the mouse is in charge
+    dragging: Option<usize>, // the place draft slot whose domino is being dragged onto the kingdom
+    building: Option<usize>, // Queendomino: the board slot of the building about to be put on a town tile
+    score_tickers: Vec<ScoreTicker>, // the scores as shown, counting up after each placement, by player id - 1
+    property_view: properties::PropertyView,
+    show_hints: bool,
+    hints_allowed: bool, // false in network games
+    panel: Option<panels::Panel>, // the pop-up panel that is open
+    log_scroll: usize, // how many lines the log panel is scrolled back from the newest
+    last_mouse: (f32, f32),
+    local_seat: Option<u8>, // only this seat gets sockets and a hand. None means everyone plays on this screen
+    status: Option<String>,
+    animations: Vec<(animate::Animation, f64)>, // what is moving and when it started
+    seen: Option<animate::Seen>, // the table last frame, to tell what moved
+    animation_speed: AnimationSpeed,
+    audio: audio::Audio,
 }
 
 impl Gui {
 
-    pub(crate) async fn new() -> Self {
+    /// A gui that can draw every domino of the set, with pictures from the given source.
+    /// Shows the loading scene until they are loaded.
+    pub(crate) async fn new(set: &DominoSet, source: &AssetSource) -> Self {
         Self {
-            assets: Assets::load().await,
+            assets: loading::load_assets(set, source).await,
+            audio: loading::load_sounds(source).await,
             domino_rotation: PlacementDominoRotation::UP,
-            blue_offset: [0.0;2],
-            green_offset: [0.0;2],
-            red_offset: [0.0;2],
-            yellow_offset: [0.0;2],
-            curr_socket_locations: Vec::new(),
-
+            layout: Layout::new(screen_width(), screen_height(), DRAFT_SIZE, DRAFT_SIZE),
+            map_offsets: Vec::new(),
+            hits: HitMap::new(),
+            focus: None,
+            dragging: None,
+            building: None,
+            score_tickers: Vec::new(),
+            property_view: properties::PropertyView::default(),
+            show_hints: false,
+            hints_allowed: true,
+            panel: None,
+            log_scroll: 0,
+            last_mouse: mouse_position(),
+            local_seat: None,
+            status: None,
+            animations: Vec::new(),
+            seen: None,
+            animation_speed: AnimationSpeed::default(),
         }
     }
 
+    /// Loads the pictures of another set, like when a server turns out to play another variant than expected
+    pub(crate) async fn load_set(&mut self, set: &DominoSet, source: &AssetSource) {
+        self.assets = loading::load_assets(set, source).await;
+    }
+
+    pub(crate) fn set_local_seat(&mut self, seat: Option<u8>) {self.local_seat = seat;}
+
+    pub(crate) fn set_status(&mut self, status: String) {self.status = Some(status);}
+
+    /// How fast things move, or not at all
+    pub(crate) fn set_animation_speed(&mut self, speed: AnimationSpeed) {self.animation_speed = speed;}
+
+    /// True if the player sits at this screen, so their moves are made here
+    fn is_local(&self, player: &Player) -> bool {
+        self.local_seat.is_none_or(|seat| seat == player.id())
+    }
+
     /// Creates the container lines
     pub(crate) fn make_containers(&self){
         let color = board_gui::ACCENT_COLOR;
+        let layout = &self.layout;
         // Draw container lines
-        draw_line(0.0, screen_height()/2.0-100.0, screen_width()/3.0, screen_height()/2.0-100.0, 10.0, color);  //hori
-        draw_line(0.0, screen_height()/2.0+50.0, screen_width()/3.0, screen_height()/2.0+50.0, 10.0, color);  //hori
+        draw_line(0.0, layout.advice.y, layout.sidebar.w, layout.advice.y, 10.0, color);  //hori
+        draw_line(0.0, layout.scores.y, layout.sidebar.w, layout.scores.y, 10.0, color);  //hori
 
-        draw_line(screen_width()/3.0, 0.0, screen_width()/3.0, screen_height(), 10.0, color);       //virt
-        // Draw player-pane subdivision lines
-        draw_line(screen_width()/3.0, screen_height()/2.0, screen_width(), screen_height()/2.0, 5.0, color);//hori
-        draw_line(screen_width()*(2.0/3.0), 0.0, screen_width()*(2.0/3.0), screen_height(), 5.0, color);    //virt
+        draw_line(layout.sidebar.w, 0.0, layout.sidebar.w, layout.sidebar.h, 10.0, color);       //virt
 
         // Draw scrolls
-        self.draw_obj(self.assets.fetch_draft_scroll(), 0.0,(screen_height()/4.0)-75.0, board_gui::SCROLL_SIZE);
-        self.draw_obj(self.assets.fetch_score_scroll(), 0.0, (screen_height()*(3.0/4.0))-50.0, board_gui::SCROLL_SIZE);
-
-        // Draw king icons on score box
-        for idx in 1..5 {
-            let i: f32 = idx as f32;
-            self.draw_obj(self.assets.fetch_king_texture_by_turn(idx), 100.0, screen_height()*(3.0/4.0)-200.0+i*75.0, board_gui::SCORE_KING_SIZE);
+        let (draft_scroll, score_scroll) = (layout.draft_scroll(), layout.score_scroll());
+        self.draw_obj(self.assets.fetch_draft_scroll(), draft_scroll.x, draft_scroll.y, draft_scroll.w);
+        self.draw_obj(self.assets.fetch_score_scroll(), score_scroll.x, score_scroll.y, score_scroll.w);
+
+        // Draw the player panes, each with a border of its player's color
+        for (seat, pane) in layout.panes.iter().enumerate() {
+            draw_rectangle_lines(pane.x, pane.y, pane.w, pane.h, 5.0, color);
+            self.draw_color_border(board_gui::KING_COLORS[seat], pane.shrink(layout::PANE_MARGIN));
         }
 
-        // Draw colored borders within grid panes
-        self.draw_color_border(board_gui::BLUE,   -1.0, -1.0);
-        self.draw_color_border(board_gui::GREEN,   1.0, -1.0);
-        self.draw_color_border(board_gui::RED,    -1.0,  1.0);
-        self.draw_color_border(board_gui::YELLOW,  1.0,  1.0);
-
     }
 
-    // Detects if R key is pressed and rotates the domino if so (only called during placement phase)
-    pub(crate) fn check_r_key_pressed(&mut self) {
-        if is_key_pressed(KeyCode::R) {
-            self.domino_rotation = self.domino_rotation.next();
-            return;
+    /// What was clicked this frame, if anything. Clicks on the sound panel are for its sliders, and those on the
+    /// builders' board for its buttons.
+    fn clicked(&self) -> Option<Target> {
+        if !is_mouse_button_pressed(MouseButton::Left) { return None; }
+        let (mx, my) = mouse_position();
+        if matches!(self.panel, Some(panels::Panel::Sound | panels::Panel::Builders)) && self.layout.panel().contains(mx, my) {
+            return None;
         }
+        self.hits.target_at(mx, my)
     }
 
-    /// Returns the domino if we clicked a domino in the draft
-    pub(crate) fn picked_draft_domino(draft: &mut Draft, cur_player: &Player) -> Option<Domino> {
-        if is_mouse_button_pressed(MouseButton::Left) {
-            let (mx, my) = mouse_position();
-
-            // X bounds of the draft area
-            let x_min = draft_gui::PICK_DOMINO_X;
-            let x_max = draft_gui::PICK_DOMINO_X + 2.0 * draft_gui::DOMINO_TILE_SIZE;
-
-            if mx >= x_min && mx <= x_max {
-                let top = Self::top_draft_domino_y();
-
-                for i in 0..DRAFT_SIZE {
-                    let y_min = top + i as f32 * draft_gui::VERT_OFFSET;
-                    let y_max = y_min + draft_gui::DOMINO_TILE_SIZE;
-
-                    if my >= y_min && my <= y_max && draft.pickable(i) {
-                        return Some(draft.pick(i, cur_player.id()));
-                    }
-                }
-            }
+    /// Returns the slot of the pick draft that was clicked, if it can still be picked
+    pub(crate) fn clicked_draft_slot(&self) -> Option<usize> {
+        match self.clicked() {
+            Some(Target::DraftSlot(slot)) => Some(slot),
+            _ => None,
         }
-        None
     }
 
-    pub(crate) fn picked_socket(cur_player: &Player, curr_orientation: &PlacementDominoRotation, curr_socket_vec: &Vec<[f32; 6]>) -> Option<GridDomino> {
-        if !is_mouse_button_pressed(MouseButton::Left) { return None; }
-        let (mx, my) = mouse_position();
-
-        let rotation: f64;
-        match curr_orientation {
-            PlacementDominoRotation::UP => {rotation = 0.0;}
-            PlacementDominoRotation::LEFT => {rotation = PI/2.0;}
-            PlacementDominoRotation::DOWN => {rotation = PI;}
-            PlacementDominoRotation::RIGHT => {rotation = PI*(3.0/2.0);}
+    /// Returns where the player clicked, or dropped the dragged domino, to place it. The rotate button turns the
+    /// domino instead, and pressing on the domino in the place draft picks it up.
+    pub(crate) fn clicked_placement(&mut self, cur_player: &Player) -> Option<GridDomino> {
+        if self.dragged_slot().is_some() {
+            return self.dropped_placement(cur_player);
         }
-
-        for entry in curr_socket_vec {
-            if mx > entry[2] && mx < entry[3] && my > entry[4] && my < entry[5] {
-                println!("row: {}, col: {}", entry[0], entry[1]);
-                //reflect the index accross the center
-                
-                let x_low:  &usize = cur_player.grid().dm_lower_x();
-                let x_high: &usize = cur_player.grid().dm_upper_x();
-                let y_low:  &usize = cur_player.grid().dm_lower_y();
-                let y_high: &usize = cur_player.grid().dm_upper_y();
-                let mut x_center: f32 = ((*x_high as f32) + (*x_low as f32))/2.0;
-                let mut y_center: f32 = ((*y_high as f32) + (*y_low as f32))/2.0;
-                let x = 2.0*x_center - entry[0];
-                let y = 2.0*y_center - entry[1];
-                
-                return Some(GridDomino::new(x as u8, y as u8, cur_player.placing().id() as usize, rotation));
+        match self.clicked()? {
+            // sockets are registered with the grid coords of the anchor tile they stand for
+            Target::Socket { x, y } => {
+                let rotation = self.domino_rotation.build_rotation().radians();
+                Some(GridDomino::new(x, y, cur_player.placing().id() as usize, rotation))
+            }
+            Target::Button(Button::Rotate) => {
+                self.domino_rotation = self.domino_rotation.next();
+                self.play(Effect::Rotate);
+                None
+            }
+            Target::PlaceSlot(slot) => {
+                self.start_drag(slot);
+                None
             }
+            Target::DraftSlot(_) | Target::Town { .. } => None,
         }
-        None
     }
 
 
     /// The overarching draw function. Called each frame of the game.
-    pub(crate) fn draw(&mut self, pick_draft: &Draft, place_draft: &Draft, active_player_id: &usize, phase: &Phase, player_list: &[Player; 4], subturn_number: &u8) {
-        let active_player = Gui::get_active_player(subturn_number, player_list);
-        // assert!(active_player.picked().id() < 49, "You managed to click on a domino with id greater than 48. The id is {}", active_player.picked().id());
-        let mut valid_draft_doms: [bool;4] = [true;4];
+    pub(crate) fn draw(&mut self, game: &Game, log: &EventLog) {
+        let active_player = game.active_player();
+        self.layout = Layout::new(screen_width(), screen_height(), game.players().len(), game.players().len());
+        self.start_animations(game);
         clear_background(board_gui::BACKGROUND_COLOR);
         self.make_containers();
-        self.add_advice_box(*active_player_id, phase); 
-        self.draw_draft(pick_draft, draft_gui::PICK_DOMINO_X, valid_draft_doms); // unsure if valid_draft_doms should always be true for this line. If you get a weird error where the unpicked doms are not showing up, this line is the issue
-        for temp_player in player_list {
+        self.draw_scores(game);
+        self.add_advice_box(active_player.id(), game.state(), game.variant());
+        self.hits.clear();
+        self.draw_draft(game.pick_draft(), DraftColumn::Pick, &[true; MAX_DRAFT_SIZE]);
+        for slot in (0..game.pick_draft().len()).filter(|&slot| game.pick_draft().pickable(slot)) {
+            self.hits.register(self.layout.draft_slot(DraftColumn::Pick, slot), Target::DraftSlot(slot));
+        }
+        for temp_player in game.players() {
             self.update_offset(temp_player);
             self.draw_domino_map(temp_player); // Draws the domino maps for each player regardless of if they are active
+            self.draw_properties(temp_player);
+            self.draw_town(game, temp_player);
+            self.draw_giants(game, temp_player);
         }
-        
-        match phase {
-            Phase::Placing => {
-                self.draw_sockets(active_player);
-                valid_draft_doms = self.undraw_old_doms(subturn_number);
-                self.draw_placing_textures_if_placing(active_player);
+
+        let placing = game.phase() == Some(Phase::Placing) && self.is_local(active_player);
+        if placing {
+            self.draw_sockets(active_player);
+            self.draw_placement_hints(active_player);
+            self.draw_rotate_button(active_player);
+        }
+        if game.phase().is_some() && self.is_local(active_player) {
+            self.draw_building_sites(game);
+        }
+
+        if !game.place_draft().is_null() {
+            let mut shown = self.undraw_old_doms(game.state());
+            if let Some(slot) = self.dragged_slot() {
+                shown[slot] = false; // it is in the player's hand
+            }
+            self.draw_draft(game.place_draft(), DraftColumn::Place, &shown); //Written by Peter
+        }
+        self.draw_quests(game);
+
+        // the domino being placed waits in the place draft to be dragged out, drawn last so it is on top of everything
+        if placing && let Some(slot) = game.state().king().map(|king| king.idx()) {
+            let rect = self.layout.draft_slot(DraftColumn::Place, slot);
+            if self.dragged_slot().is_none() {
+                let ring = rect.shrink(-board_gui::FOCUS_THICKNESS);
+                draw_rectangle_lines(ring.x, ring.y, ring.w, ring.h, board_gui::FOCUS_THICKNESS, board_gui::ACCENT_COLOR);
             }
-            Phase::Picking => {}
+            self.hits.register(rect, Target::PlaceSlot(slot));
+            self.draw_placing_textures_if_placing(active_player);
         }
 
-        if !place_draft.is_null() {
-            self.draw_draft(place_draft, draft_gui::PLACE_DOMINO_X, valid_draft_doms); //Written by Peter
+        self.draw_panel(game, log);
 
+        if let Some(status) = &self.status {
+            draw_multiline_text(status, 10.0, board_gui::STATUS_FONT_SIZE, board_gui::STATUS_FONT_SIZE, None, WHITE);
         }
     }
 
-    /// Returns the y coordinate of the top domino of the draft. Calculated based on screen height and draft size.
-    fn top_draft_domino_y() -> f32 {
-        // Half screen plus the two dominoes above the halfway point
-        screen_height()/4.0 - 50.0 - (DRAFT_SIZE as f32/2.0) * draft_gui::VERT_OFFSET
-    }
-
-    fn draw_draft(&self, draft: &Draft, domino_x: f32, valid_doms: [bool;4]) { //Written by Peter
+    fn draw_draft(&self, draft: &Draft, column: DraftColumn, valid_doms: &[bool]) { //Written by Peter
 
         if draft.is_null() {
             return;
         }
 
-        let top_domino_y = Self::top_draft_domino_y();
-
         for (i, domino) in draft.iter().enumerate() {
             if valid_doms[i]{
-                self.draw_domino(domino, draft_gui::DOMINO_TILE_SIZE,
-                                domino_x,
-                                top_domino_y + (i as f32 * draft_gui::VERT_OFFSET));
+                let slot = self.layout.draft_slot(column, i);
+                // new dominoes slide into the pick draft
+                let (x, y) = if column == DraftColumn::Pick { self.dealt_position(domino.id(), slot) } else { (slot.x, slot.y) };
+                self.draw_domino(domino, slot.h, x, y);
 
                 if let Some(id) = draft.player_on(i) {
-                    self.draw_king_on_domino(id as usize,
-                                            domino_x,
-                                            top_domino_y + (i as f32 * draft_gui::VERT_OFFSET));
+                    self.draw_king_on_domino(id as usize, domino.id(), slot);
+                }
+                if column == DraftColumn::Pick && self.focused_slot() == Some(i) {
+                    let ring = slot.shrink(-board_gui::FOCUS_THICKNESS);
+                    draw_rectangle_lines(ring.x, ring.y, ring.w, ring.h, board_gui::FOCUS_THICKNESS, WHITE);
                 }
             }   
         }
     }
 
 
-    /// Takes the coordinates of a domino and draws the king meeple on top of it. The player idx determines color.
-    fn draw_king_on_domino(&self, player_idx: usize, domino_x: f32, domino_y: f32) {
-
-        let texture = self.assets.fetch_king_texture_by_turn(player_idx as u8);
-
-        debug_assert_ne!(texture, None);
+    /// Draws the king meeple on top of the domino in the given draft slot. The player idx determines color.
+    fn draw_king_on_domino(&self, player_idx: usize, domino_id: u8, slot: Rect) {
 
-        let texture = texture.unwrap(); // extract from Some(texture) -> texture
+        // Size of the king drawn is scaled off of the domino
+        let size: f32 = slot.h/2.5;
 
-        // Size of the king drawn is scaled off of the
-        const SIZE: f32 = draft_gui::DOMINO_TILE_SIZE/2.5;
+        // Draw the king on the middle of the domino, or on the way there if they just picked it
+        let (x, y) = self.king_position(player_idx as u8, domino_id, slot.center());
+        self.draw_king(player_idx as u8, x - size/2.0, y - size/2.0, size);
+    }
 
-        // Draw the texture on the board. x is twice as large as y, by nature of what a domino is and our orientation.
-        draw_texture_ex(texture, domino_x + draft_gui::DOMINO_TILE_SIZE - SIZE/2.0,
-                        domino_y + draft_gui::DOMINO_TILE_SIZE/2.0 - SIZE/2.0, WHITE, DrawTextureParams {
-            dest_size: Some(Vec2::new(SIZE, SIZE)),
-            ..Default::default()
-        }, );
+    /// Draws the king of the given player in a size x size square. A plain disc of their color if the picture is missing.
+    fn draw_king(&self, player_id: u8, x: f32, y: f32, size: f32) {
+        match self.assets.fetch_king_texture_by_turn(player_id) {
+            Some(texture) => self.draw_obj(Some(texture), x, y, size),
+            None => {
+                let color = board_gui::KING_COLORS[player_id as usize - 1];
+                draw_circle(x + size/2.0, y + size/2.0, size/2.5, color);
+                draw_circle_lines(x + size/2.0, y + size/2.0, size/2.5, 2.0, WHITE);
+            }
+        }
     }
 
     fn draw_domino(&self, domino: &Domino, tile_size: f32, x: f32, y: f32) {
 
-        let texture = self.assets.fetch_domino_texture_by_id(domino.id());
-
-        // Make sure we actually got the texture back!
-        debug_assert_ne!(texture, None);
-
-        let texture = texture.unwrap(); // extract from Some(texture) -> texture
+        // Dominoes without a picture are painted from their tiles, second tile to the right
+        let Some(texture) = self.assets.fetch_domino_texture_by_id(domino.id()) else {
+            tile_art::draw_domino(domino, x, y, tile_size, (1, 0), 1.0);
+            return;
+        };
 
 
         // Draw the texture on the board. x is twice as large as y, by nature of what a domino is and our orientation.
@@ -292,27 +389,18 @@ impl Gui {
         }, );
     }
 
-    // Draws a scroll on the screen
+    // Draws a scroll on the screen. Nothing at all if its picture is missing
     fn draw_obj(&self, texture: Option<&Texture2D>, x: f32, y: f32, size: f32){
-        debug_assert_ne!(texture, None);
-        let texture = texture.unwrap(); // extract from Some(texture) -> texture
+        let Some(texture) = texture else { return };
         draw_texture_ex(texture, x, y, WHITE, DrawTextureParams {
             dest_size: Some(Vec2::new(size, size)),
             ..Default::default()
         }, );
     }
 
-    // Draws a border around each pane
-    fn draw_color_border(&self, color: Color, x: f32, y: f32){
-        //math stuff
-        let mut x_offset: f32 = screen_width()*(2.0/3.0);
-        let mut y_offset: f32 = screen_height()/2.0;
-        x_offset = x_offset + x*screen_width()/6.0;
-        y_offset = y_offset + y*screen_height()/4.0;
-        let x_lef_wall = x_offset - screen_width()/6.0 + 5.0;
-        let x_rig_wall = x_offset + screen_width()/6.0 - 5.0;
-        let y_top_wall = y_offset - screen_height()/4.0 + 5.0;
-        let y_bot_wall = y_offset + screen_height()/4.0 - 5.0;
+    // Draws a border around a pane
+    fn draw_color_border(&self, color: Color, pane: Rect){
+        let (x_lef_wall, x_rig_wall, y_top_wall, y_bot_wall) = (pane.x, pane.right(), pane.y, pane.bottom());
 
         //macroquad fn call
         draw_line(x_lef_wall, y_top_wall, x_rig_wall, y_top_wall, 5.0, color);//top
@@ -321,177 +409,254 @@ impl Gui {
         draw_line(x_rig_wall, y_top_wall, x_rig_wall, y_bot_wall, 5.0, color);//right
     }
 
-    // Adds the advice text and active king sprite to the box on the left hand side and halfway down screen
-    fn add_advice_box(&self, active_player_id: usize, phase: &Phase){
-        //Gets the right text based on game phase
-        let curr_advice: String;
-        match phase {
-            Phase::Placing => {
-                curr_advice = String::from(PLACING_ADVICE);
-            }
-            &Phase::Picking => { // I have no idea why this a reference and the other is not but I dont want to touch it
-                curr_advice = String::from(PICKING_ADVICE);
+    /// One row per player in the score box: their king, their score counting up to its new value, their rank and the
+    /// tie breakers. The active player's row is lit up.
+    fn draw_scores(&mut self, game: &Game) {
+        let now = get_time();
+        let lines = scoreboard::score_lines(game);
+        self.score_tickers.resize(lines.len(), ScoreTicker::with_duration(self.animation_speed.scale(scoreboard::TICK_SECONDS)));
+        let active = game.phase().map(|_| game.active_player().id());
+
+        for (seat, line) in lines.iter().enumerate() {
+            let ticker = &mut self.score_tickers[seat];
+            ticker.set(line.score, now);
+            let (shown, progress) = (ticker.shown(now), ticker.progress(now));
+
+            let row = self.layout.score_row(seat, lines.len());
+            if active == Some(line.player) {
+                let color = board_gui::KING_COLORS[seat];
+                draw_rectangle(row.x, row.y, row.w, row.h, Color::new(color.r, color.g, color.b, 0.25));
+                draw_rectangle_lines(row.x, row.y, row.w, row.h, 2.0, color);
             }
+            self.draw_king(line.player, row.x, row.y, row.h);
+
+            // the score grows and glows while it counts up
+            let size = row.h * 0.55 * (1.0 + 0.3 * (1.0 - progress));
+            let color = if progress < 1.0 { board_gui::YELLOW } else { WHITE };
+            let text_x = row.x + row.h * 1.2;
+            draw_text(&format!("{}", shown.round() as u32), text_x, row.y + row.h * 0.6, size, color);
+
+            let details = format!("#{}  {} crowns  largest {}", line.rank, line.crowns, line.largest_property);
+            draw_text(&details, text_x, row.y + row.h * 0.9, row.h * 0.3, board_gui::ACCENT_COLOR);
         }
+    }
+
+    // Adds the advice text and active king sprite to the box on the left hand side and halfway down screen
+    fn add_advice_box(&self, active_player_id: u8, state: TurnState, variant: Variant){
+        //Gets the right text based on game phase
+        let curr_advice = match state {
+            TurnState::Setup => String::from(SETUP_ADVICE),
+            TurnState::GameOver => String::from(GAME_OVER_ADVICE),
+            _ if state.phase() == Some(Phase::Placing) => String::from(PLACING_ADVICE),
+            _ => String::from(PICKING_ADVICE),
+        };
         //Draw text
-        draw_multiline_text(&curr_advice, -10.0, screen_height()/2.0 - 75.0, 20.0, Some(0.3), WHITE);
+        let advice = self.layout.advice;
+        draw_multiline_text(&curr_advice, advice.x - 10.0, advice.y + self.layout.text_size, self.layout.text_size, Some(0.3), WHITE);
+        let keys = if self.hints_allowed { format!("{}  {}", VIEW_ADVICE, HINT_ADVICE) } else { String::from(VIEW_ADVICE) };
+        draw_text(&keys, advice.x + 10.0, advice.bottom() - 10.0, self.layout.text_size * 0.8, board_gui::ACCENT_COLOR);
+        let keys = if variant == Variant::Queendomino { format!("{}  {}", SOUND_ADVICE, BUILD_ADVICE) } else { String::from(SOUND_ADVICE) };
+        draw_text(&keys, advice.x + 10.0, advice.bottom() - 10.0 - self.layout.text_size, self.layout.text_size * 0.8, board_gui::ACCENT_COLOR);
         //Draw king of active player
-        self.draw_obj(self.assets.fetch_king_texture_by_turn(active_player_id as u8), screen_width()/3.0-50.0, screen_height()/2.0, 30.0);
+        let king = self.layout.advice_king();
+        self.draw_king(active_player_id, king.x, king.y, king.w);
     }
 
-    fn undraw_old_doms(&self, subturn_number: &u8) -> [bool;4]  {
-        let id = *subturn_number;
-        let mut valid_dominos: [bool;4] = [true;4];
-        for i in 0..id {
-            valid_dominos[(i.saturating_sub(1)) as usize] = false;
-        }
-        valid_dominos
-
+    /// The place draft only shows the dominoes that have not been placed yet
+    fn undraw_old_doms(&self, state: TurnState) -> [bool; MAX_DRAFT_SIZE]  {
+        std::array::from_fn(|slot| !state.has_placed(slot))
     }
 
     fn draw_placing_textures_if_placing(&self, active_player: &Player){
-        
+        // the domino in hand is the size it will be in the kingdom
+        let tile = self.layout.kingdom_tile;
+
         // get cursor coords
         let (mouse_x, mouse_y) = mouse_position();
-        // offsets
-        let mut x_offset: f32 = -draft_gui::DOMINO_TILE_SIZE/2.0; //TODO: once picking logic is done, fine tune offsets
-        let mut y_offset: f32 = -draft_gui::DOMINO_TILE_SIZE/2.0; //TODO: once picking logic is done, fine tune offsets
+        // offsets that keep the first tile centered under the hand. Textures turn around their center, so a
+        // turned domino is shifted back by however far that swings the first tile away.
+        let mut x_offset: f32 = -tile/2.0;
+        let mut y_offset: f32 = -tile/2.0;
         // get rotation in radians
         let rotation: f64;
         match self.domino_rotation {
             PlacementDominoRotation::UP => {rotation = 0.0;}
-            PlacementDominoRotation::LEFT => {rotation = PI/2.0; x_offset = x_offset - draft_gui::DOMINO_TILE_SIZE/2.0; y_offset = y_offset + draft_gui::DOMINO_TILE_SIZE/2.0}
-            PlacementDominoRotation::DOWN => {rotation = PI; x_offset = x_offset - draft_gui::DOMINO_TILE_SIZE}
-            PlacementDominoRotation::RIGHT => {rotation = PI*(3.0/2.0); x_offset = x_offset - draft_gui::DOMINO_TILE_SIZE/2.0; y_offset = y_offset - draft_gui::DOMINO_TILE_SIZE/2.0}
+            PlacementDominoRotation::LEFT => {rotation = PI/2.0; x_offset = x_offset - tile/2.0; y_offset = y_offset + tile/2.0}
+            PlacementDominoRotation::DOWN => {rotation = PI; x_offset = x_offset - tile}
+            PlacementDominoRotation::RIGHT => {rotation = PI*(3.0/2.0); x_offset = x_offset - tile/2.0; y_offset = y_offset - tile/2.0}
+        }
+        // then draw domino, based on rotation enum. (R, Q/E, the mouse wheel or a right click turn it)
+        let placing = active_player.placing();
+        let hand_x = mouse_x - tile/2.0;
+        let hand_y = mouse_y - tile/2.0;
+        let pane = self.layout.panes[active_player.id() as usize - 1];
+        let origin = *self.fetch_offset(active_player.id());
+        if let Some((x, y)) = self.focused_cell() {
+            // the keyboard cursor, without the hand as the mouse is not in use
+            self.draw_ghost(active_player, (x, y));
+            let cell = self.layout.kingdom_cell((origin[0], origin[1]), 0, 0);
+            draw_rectangle_lines(cell.x + x as f32 * tile, cell.y + y as f32 * tile, tile, tile,
+                                 board_gui::FOCUS_THICKNESS, WHITE);
+            return;
+        } else if self.dragged_slot().is_none() {
+            return; // still in the draft
+        } else if pane.contains(mouse_x, mouse_y) {
+            // over the kingdom, the domino snaps to the grid
+            self.draw_ghost(active_player, self.layout.kingdom_cell_at((origin[0], origin[1]), mouse_x, mouse_y));
+        } else {
+            match self.assets.fetch_domino_texture_by_id(placing.id()) {
+                Some(texture) => draw_texture_ex(
+                    texture,
+                    mouse_x + x_offset,
+                    mouse_y + y_offset,
+                    WHITE, DrawTextureParams {
+                    dest_size: Some(Vec2::new(tile*2.0, tile)),
+                    rotation: rotation as f32,
+                    ..Default::default()
+                }, ),
+                // painted tile by tile, the first one under the hand
+                None => tile_art::draw_domino(&placing, hand_x, hand_y, tile,
+                                              self.domino_rotation.build_rotation().second_offset(), 1.0),
+            }
+        }
+        // then draw hand, or a ring where it would be
+        match self.assets.fetch_hand() {
+            Some(hand) => self.draw_obj(Some(hand), hand_x, hand_y, tile),
+            None => draw_circle_lines(mouse_x, mouse_y, tile/4.0, 3.0, WHITE),
         }
-        // then draw domino, based on rotation enum. (Pressing 'r' cycles through the enum)
-        draw_texture_ex(
-            self.assets.fetch_domino_texture_by_id(active_player.placing().id()).unwrap(),
-            mouse_x + x_offset,
-            mouse_y + y_offset,
-            WHITE, DrawTextureParams {
-            dest_size: Some(Vec2::new(draft_gui::DOMINO_TILE_SIZE*2.0, draft_gui::DOMINO_TILE_SIZE)),
-            rotation: rotation as f32,
-            ..Default::default()
-        }, );
-        // then draw hand
-        x_offset = -draft_gui::DOMINO_TILE_SIZE/2.0; //TODO: once picking logic is done, fine tune offsets
-        y_offset = -draft_gui::DOMINO_TILE_SIZE/2.0; //TODO: once picking logic is done, fine tune offsets
-        self.draw_obj(self.assets.fetch_hand(), mouse_x + x_offset, mouse_y + y_offset, draft_gui::DOMINO_TILE_SIZE);
 
         return;
     }
 
-    fn draw_sockets(&mut self, active_player: &Player){
-        // get the correct socket map based on the orientation enum
-        let socket_map: &[[bool; 9]; 9];
-        match self.domino_rotation {
-            PlacementDominoRotation::UP => {
-                socket_map = active_player.grid().up_map();
-            }
-            PlacementDominoRotation::LEFT => {
-                socket_map = active_player.grid().left_map();
-            }
-            PlacementDominoRotation::DOWN => {
-                socket_map = active_player.grid().down_map();
-            }
-            PlacementDominoRotation::RIGHT => {
-                socket_map = active_player.grid().right_map();
-            }
+    /// Draws the held domino with its first tile on a kingdom cell, tinted green if it can go there and red if it
+    /// can't, with a line at the top of the pane saying why
+    fn draw_ghost(&self, active_player: &Player, (x, y): (i32, i32)) {
+        let tile = self.layout.kingdom_tile;
+        let placing = active_player.placing();
+        let rotation = self.domino_rotation.build_rotation();
+        let origin = *self.fetch_offset(active_player.id());
+
+        let legality = match (u8::try_from(x), u8::try_from(y)) {
+            (Ok(x), Ok(y)) => active_player.grid().check_placement(&placing, x, y, rotation),
+            _ => Err(PlacementError::OutOfBounds),
+        };
+        let (dx, dy) = rotation.second_offset();
+        let cell_x = origin[0] + x as f32 * tile;
+        let cell_y = origin[1] + y as f32 * tile;
+
+        // the domino itself, see through so the kingdom under it still shows
+        match self.assets.fetch_domino_texture_by_id(placing.id()) {
+            Some(texture) => draw_texture_ex(
+                texture,
+                cell_x + (dx as f32 - 1.0) * tile / 2.0,
+                cell_y + dy as f32 * tile / 2.0,
+                Color::new(1.0, 1.0, 1.0, board_gui::GHOST_ALPHA), DrawTextureParams {
+                dest_size: Some(Vec2::new(tile*2.0, tile)),
+                rotation: rotation.radians() as f32,
+                ..Default::default()
+            }, ),
+            None => tile_art::draw_domino(&placing, cell_x, cell_y, tile, (dx, dy), board_gui::GHOST_ALPHA),
         }
 
-        // calculate offset (copy the offset out so we don't hold an immutable borrow of self)
-        let map_offset = *self.fetch_offset(active_player.id());
-
-        // reset stored sockets for this frame
-        self.curr_socket_locations.clear();
-
-        for row in 0..socket_map[0].len(){
-            for col in 0..socket_map.len(){
-                // print!("{}\t", socket_map[row][col]);
-                if socket_map[row][col] {   
-                    let x_lower = map_offset[0] - (8.0 - row as f32)*grid_multipliers_gui::X_MULTIPLIER;
-                    let y_lower = map_offset[1] - (8.0 - col as f32)*grid_multipliers_gui::Y_MULTIPLIER;
-                    let x_upper = x_lower + draft_gui::DOMINO_TILE_SIZE;
-                    let y_upper = y_lower + draft_gui::DOMINO_TILE_SIZE;
-                    self.curr_socket_locations.push([row as f32, col as f32, x_lower, x_upper, y_lower, y_upper]);
-                    self.draw_obj(self.assets.fetch_socket(),
-                    map_offset[0] - (8.0 - row as f32)*grid_multipliers_gui::X_MULTIPLIER, 
-                    map_offset[1] - (8.0 - col as f32)*grid_multipliers_gui::Y_MULTIPLIER, 
-                    draft_gui::DOMINO_TILE_SIZE);
-                }
-            }
-            // print!("\n")
+        // then the tint over both of its cells
+        let tint = if legality.is_ok() { board_gui::LEGAL_TINT } else { board_gui::ILLEGAL_TINT };
+        for (cx, cy) in [(cell_x, cell_y), (cell_x + dx as f32 * tile, cell_y + dy as f32 * tile)] {
+            draw_rectangle(cx, cy, tile, tile, tint);
         }
-        //panic!("End of testing");
-        
 
-        return;
+        let pane = self.layout.panes[active_player.id() as usize - 1].shrink(3.0 * layout::PANE_MARGIN);
+        let reason = match legality {
+            Ok(()) if self.focus.is_some() => "Press Enter to place".to_string(),
+            Ok(()) => "Let go to place".to_string(),
+            Err(e) => format!("Can't place here: {}", e),
+        };
+        draw_text(&reason, pane.x, pane.y + self.layout.text_size, self.layout.text_size, WHITE);
     }
 
-    //get active player obj here so i dont have to do this a gazillion times
-    fn get_active_player<'a>(active_player_id: &u8, player_list: &'a [Player; 4]) -> &'a Player {
-        let id = (*active_player_id).saturating_sub(1);
-        player_list.get(id as usize).expect(&format!("There was an error. active_player_id is {}", *active_player_id)) 
+    /// The rotate button in the corner of the active player's pane, for those who would rather click than press R
+    fn draw_rotate_button(&mut self, active_player: &Player) {
+        let button = self.layout.rotate_button(self.layout.panes[active_player.id() as usize - 1]);
+        let hovered = button.contains(mouse_position().0, mouse_position().1);
+        draw_rectangle(button.x, button.y, button.w, button.h, if hovered { board_gui::ACCENT_COLOR } else { board_gui::BACKGROUND_COLOR });
+        draw_rectangle_lines(button.x, button.y, button.w, button.h, 2.0, WHITE);
+        let size = self.layout.text_size;
+        let label = measure_text("Rotate (R)", None, size as u16, 1.0);
+        draw_text("Rotate (R)", button.center().0 - label.width/2.0, button.center().1 + label.offset_y/2.0, size, WHITE);
+        self.hits.register(button, Target::Button(Button::Rotate));
     }
 
-    // gives the coords of the active player's box
-    fn get_active_player_box_offset(active_player: &Player) -> (f32, f32) {
-        let id = active_player.id();
-        let mut x_offset: f32 = screen_width()*(2.0/3.0);
-        let mut y_offset: f32 = screen_height()/2.0;
-        match id {
-            1 => {
-                x_offset = x_offset - screen_width()/6.0;
-                y_offset = y_offset - screen_height()/4.0;
-                (x_offset, y_offset)
-            }
-            2 => {
-                x_offset = x_offset + screen_width()/6.0;
-                y_offset = y_offset - screen_height()/4.0;
-                (x_offset, y_offset)
-            }
-            3 => {
-                x_offset = x_offset - screen_width()/6.0;
-                y_offset = y_offset + screen_height()/4.0;
-                (x_offset, y_offset)
+    fn draw_sockets(&mut self, active_player: &Player){
+        let tile = self.layout.kingdom_tile;
+        // only the sockets for the current orientation are shown
+        let rotation = self.domino_rotation.build_rotation();
+
+        // calculate offset (copy the offset out so we don't hold an immutable borrow of self)
+        let map_offset = *self.fetch_offset(active_player.id());
+
+        for placement in active_player.grid().legal_placements(&active_player.placing()) {
+            if BuildRotation::from_radians(*placement.rotation()) != rotation {
+                continue;
             }
-            4 => {
-                x_offset = x_offset + screen_width()/6.0;
-                y_offset = y_offset + screen_height()/4.0;
-                (x_offset, y_offset)
+            let row = *placement.x();
+            let col = *placement.y();
+            let x_lower = map_offset[0] + row as f32 * tile;
+            let y_lower = map_offset[1] + col as f32 * tile;
+            self.hits.register(Rect::new(x_lower, y_lower, tile, tile), Target::Socket { x: row, y: col });
+            match self.assets.fetch_socket() {
+                Some(socket) => self.draw_obj(Some(socket), x_lower, y_lower, tile),
+                None => draw_rectangle_lines(x_lower + 4.0, y_lower + 4.0, tile - 8.0,
+                                             tile - 8.0, 3.0, board_gui::ACCENT_COLOR),
             }
-            _ => {panic!("got a player id that was not 1-4 in get_active_player_box_offset()");}
         }
     }
 
     fn draw_domino_map(&self, active_player: &Player) {
+        let tile = self.layout.kingdom_tile;
         let domino_map: &Vec<GridDomino> = active_player.grid().domino_map();
         let map_offset: &[f32; 2] = self.fetch_offset(active_player.id());
         assert_ne!(0, domino_map.len(), "The length of the domino map is 0, it ought to start at 1. len is: {}", domino_map.len());
         
         for grid_domino in domino_map {
-            let x = map_offset[0] - grid_multipliers_gui::X_MULTIPLIER * (*grid_domino.x() as f32);
-            let y = map_offset[1] - grid_multipliers_gui::Y_MULTIPLIER * (*grid_domino.y() as f32);
+            // top left corner of the anchor tile's cell, or where it is on its way there from the draft
+            let (cell_x, cell_y, tile) = self.placed_position(active_player.id(), *grid_domino.domino_id() as u8,
+                (map_offset[0] + tile * (*grid_domino.x() as f32), map_offset[1] + tile * (*grid_domino.y() as f32)), tile);
             let rotation: f64 = *grid_domino.rotation();
-            let texture_option: Option<&Texture2D> = self.assets.fetch_domino_texture_by_id(*grid_domino.domino_id() as u8);
+            let Some(texture) = self.assets.fetch_domino_texture_by_id(*grid_domino.domino_id() as u8) else {
+                // No picture, so paint the tiles that are on the map
+                let tiles = active_player.grid().tile_map();
+                let (gx, gy) = (*grid_domino.x() as usize, *grid_domino.y() as usize);
+                tile_art::draw_tile(tiles[gx][gy], cell_x, cell_y, tile, 1.0);
+                if *grid_domino.domino_id() != CASTLE_ID as usize {
+                    let (dx, dy) = BuildRotation::from_radians(rotation).second_offset();
+                    tile_art::draw_tile(tiles[(gx as isize + dx) as usize][(gy as isize + dy) as usize],
+                                        cell_x + dx as f32 * tile,
+                                        cell_y + dy as f32 * tile,
+                                        tile, 1.0);
+                }
+                continue;
+            };
             if *grid_domino.domino_id() == 49 {
                 draw_texture_ex(
-                texture_option.unwrap(),
-                x,
-                y,
+                texture,
+                cell_x,
+                cell_y,
                 WHITE, DrawTextureParams {
-                dest_size: Some(Vec2::new(draft_gui::DOMINO_TILE_SIZE, draft_gui::DOMINO_TILE_SIZE)),
+                dest_size: Some(Vec2::new(tile, tile)),
                 rotation: rotation as f32,
                 ..Default::default()
             }, );
             } else {
+                // The texture rotates around its center, which is halfway between the anchor and the second tile.
+                // Shift the unrotated texture so that center lands there and tile 1 ends up on the anchor cell.
+                let (dx, dy) = BuildRotation::from_radians(rotation).second_offset();
+                let x = cell_x + (dx as f32 - 1.0) * tile / 2.0;
+                let y = cell_y + dy as f32 * tile / 2.0;
                 draw_texture_ex(
-                texture_option.unwrap(),
+                texture,
                 x,
                 y,
                 WHITE, DrawTextureParams {
-                dest_size: Some(Vec2::new(draft_gui::DOMINO_TILE_SIZE*2.0, draft_gui::DOMINO_TILE_SIZE)),
+                dest_size: Some(Vec2::new(tile*2.0, tile)),
                 rotation: rotation as f32,
                 ..Default::default()
             }, );
@@ -511,37 +676,19 @@ impl Gui {
     }
 
     fn fetch_offset(&self, id: u8) -> &[f32; 2] {
-        match id {
-            1 => {&self.blue_offset}
-            2 => {&self.green_offset}
-            3 => {&self.red_offset}
-            4 => {&self.yellow_offset}
-            _ => {panic!("you called fetch_offset with an id not corresponding to any colors")}
-        }
+        &self.map_offsets[id as usize - 1]
     }
 
     fn update_offset(&mut self, active_player: &Player) {
-        let offset: (f32, f32) = Gui::get_active_player_box_offset(active_player); // box offset... still needs the map offset
-
-        let x_low:  &usize = active_player.grid().dm_lower_x();
-        let x_high: &usize = active_player.grid().dm_upper_x();
-        let y_low:  &usize = active_player.grid().dm_lower_y();
-        let y_high: &usize = active_player.grid().dm_upper_y();
-        let mut x_center: f32 = ((*x_high as f32) + (*x_low as f32) - 1.0)/2.0;
-        let mut y_center: f32 = ((*y_high as f32) + (*y_low as f32) - 1.0)/2.0;
-        x_center = offset.0 + x_center * grid_multipliers_gui::X_MULTIPLIER;
-        y_center = offset.1 + y_center * grid_multipliers_gui::Y_MULTIPLIER;
-
-        match active_player.id() {
-            1 => {self.blue_offset[0] = x_center; self.blue_offset[1] = y_center; }
-            2 => {self.green_offset[0] = x_center; self.green_offset[1] = y_center; }
-            3 => {self.red_offset[0] = x_center; self.red_offset[1] = y_center; }
-            4 => {self.yellow_offset[0] = x_center; self.yellow_offset[1] = y_center; }
-            _ => {panic!("you called fetch_offset with an id not corresponding to any colors")}
-        } 
-        return;
+        // The offset is where cell (0, 0) would be drawn, chosen so the placed tiles sit in the middle of the pane
+        let pane = self.layout.panes[active_player.id() as usize - 1];
+        let (x, y) = self.layout.kingdom_origin(pane, active_player.grid().bounds());
+
+        let idx = active_player.id() as usize - 1;
+        if self.map_offsets.len() <= idx {
+            self.map_offsets.resize(idx + 1, [0.0; 2]);
+        }
+        self.map_offsets[idx] = [x, y];
     }
 
-    pub(crate) fn domino_rotation(&self) -> &PlacementDominoRotation {&self.domino_rotation}
-    pub(crate) fn get_socket_vec(&self) -> &Vec<[f32; 6]> {&self.curr_socket_locations}
 }
\ No newline at end of file
@@ -0,0 +1,137 @@
+use macroquad::prelude::*;
+use crate::animation::{self, ease_out, lerp, AnimationSpeed, DEAL_SECONDS, DEAL_STAGGER, KING_SECONDS, PLACE_SECONDS};
+use crate::components::game::Game;
+use crate::gui::Gui;
+use crate::layout::{DraftColumn, Rect};
+
+// Works out what moved since last frame, by whoever (this screen, a bot or another seat of a network game),
+// and starts an animation for it: new drafts slide in, kings walk onto their pick and placed dominoes fly
+// from the draft to their kingdom.
+
+
+/// Something moving on screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Animation {
+    /// A domino dealt into the pick draft, sliding in from the deck by the draft scroll
+    Dealt { domino_id: u8 },
+    /// A king going onto the domino they picked, from where `from` is on screen
+    King { player: u8, domino_id: u8, from: (f32, f32) },
+    /// A domino going into a kingdom, from its place draft slot
+    Placed { player: u8, domino_id: u8, from: Rect },
+}
+
+
+/// What the table looked like last frame, as far as the animations care
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Seen {
+    pick: Vec<(u8, Option<u8>)>, // domino id and king on it, by slot
+    place: Vec<u8>,
+    placed: Vec<usize>, // dominoes in each kingdom, by player id - 1
+}
+
+impl Seen {
+    fn new(game: &Game) -> Self {
+        let pick = game.pick_draft();
+        Self {
+            pick: (0..pick.len()).map(|slot| (pick.domino(slot).id(), pick.player_on(slot))).collect(),
+            place: game.place_draft().iter().map(|domino| domino.id()).collect(),
+            placed: game.players().iter().map(|player| player.grid().domino_map().len()).collect(),
+        }
+    }
+}
+
+
+impl Gui {
+
+    /// Starts animating whatever changed since last frame. Needs this frame's layout.
+    pub(crate) fn start_animations(&mut self, game: &Game) {
+        let now = get_time();
+        let seen = Seen::new(game);
+        let last = self.seen.take().unwrap_or_default();
+        let speed = self.animation_speed;
+        self.animations.retain(|&(animation, started)| now - started < length(speed, animation));
+
+        // a new draft was dealt
+        if seen.pick.iter().map(|d| d.0).ne(last.pick.iter().map(|d| d.0)) {
+            for (slot, &(domino_id, _)) in seen.pick.iter().enumerate() {
+                self.animations.push((Animation::Dealt { domino_id }, now + slot as f64 * DEAL_STAGGER));
+            }
+        }
+
+        // kings that just got onto a domino walk there from the advice box, where the active king stands
+        let from = self.layout.advice_king().center();
+        let kings = seen.pick.iter().copied()
+            .chain(game.place_draft().iter().enumerate().map(|(slot, d)| (d.id(), game.place_draft().player_on(slot))));
+        for (domino_id, king) in kings {
+            let was_free = last.pick.iter().any(|&(id, on)| id == domino_id && on.is_none());
+            if let (Some(player), true) = (king, was_free) {
+                self.animations.push((Animation::King { player, domino_id, from }, now));
+            }
+        }
+
+        // dominoes new to a kingdom fly there from where they were in the place draft
+        for player in game.players() {
+            let map = player.grid().domino_map();
+            let before = last.placed.get(player.id() as usize - 1).copied().unwrap_or(map.len());
+            for grid_domino in map.iter().skip(before) {
+                let domino_id = *grid_domino.domino_id() as u8;
+                if let Some(slot) = last.place.iter().position(|&id| id == domino_id) {
+                    let from = self.layout.draft_slot(DraftColumn::Place, slot);
+                    self.animations.push((Animation::Placed { player: player.id(), domino_id, from }, now));
+                }
+            }
+        }
+        self.seen = Some(seen);
+    }
+
+    /// Where a dealt domino in this pick draft slot is drawn, as the top left corner of the slot's rect
+    pub(crate) fn dealt_position(&self, domino_id: u8, slot: Rect) -> (f32, f32) {
+        let scroll = self.layout.draft_scroll();
+        match self.newest_animation(|animation| *animation == Animation::Dealt { domino_id }) {
+            Some((_, t)) => lerp((scroll.x, scroll.center().1 - slot.h/2.0), (slot.x, slot.y), ease_out(t)),
+            None => (slot.x, slot.y),
+        }
+    }
+
+    /// Where the center of a king on this domino is drawn, given where it ends up
+    pub(crate) fn king_position(&self, player: u8, domino_id: u8, to: (f32, f32)) -> (f32, f32) {
+        let animation = self.newest_animation(|animation| {
+            matches!(*animation, Animation::King { player: p, domino_id: id, .. } if p == player && id == domino_id)
+        });
+        match animation {
+            Some((Animation::King { from, .. }, t)) => lerp(from, to, ease_out(t)),
+            _ => to,
+        }
+    }
+
+    /// Where the anchor cell of a placed domino is drawn and how big its tiles are, given where it lands
+    pub(crate) fn placed_position(&self, player: u8, domino_id: u8, cell: (f32, f32), tile: f32) -> (f32, f32, f32) {
+        let animation = self.newest_animation(|animation| {
+            matches!(*animation, Animation::Placed { player: p, domino_id: id, .. } if p == player && id == domino_id)
+        });
+        match animation {
+            Some((Animation::Placed { from, .. }, t)) => {
+                let t = ease_out(t);
+                let (x, y) = lerp((from.x, from.y), cell, t);
+                (x, y, from.h + (tile - from.h) * t)
+            }
+            _ => (cell.0, cell.1, tile),
+        }
+    }
+
+    /// The newest animation that matches and how far along (0 to 1) it is, None if there is none
+    fn newest_animation(&self, matching: impl Fn(&Animation) -> bool) -> Option<(Animation, f32)> {
+        let &(animation, started) = self.animations.iter().rev().find(|(animation, _)| matching(animation))?;
+        Some((animation, animation::progress(started, get_time(), length(self.animation_speed, animation))))
+    }
+}
+
+
+/// How long an animation takes at this speed
+fn length(speed: AnimationSpeed, animation: Animation) -> f64 {
+    speed.scale(match animation {
+        Animation::Dealt { .. } => DEAL_SECONDS,
+        Animation::King { .. } => KING_SECONDS,
+        Animation::Placed { .. } => PLACE_SECONDS,
+    })
+}
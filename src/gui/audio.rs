@@ -0,0 +1,172 @@
+use macroquad::audio::{load_sound_from_bytes, play_sound, set_sound_volume, PlaySoundParams, Sound};
+use macroquad::prelude::*;
+use crate::assets::{AssetError, AssetSource};
+use crate::event_log::Event;
+use crate::gui::panels::Panel;
+use crate::gui::{board_gui, Gui};
+use crate::layout::Rect;
+use crate::sound::{self, Effect, Slider, Volume};
+
+// Playing the sound effects and the music. M mutes everything, S opens the sound panel where the volume
+// sliders are dragged with the mouse. Without an audio device (like on a server with no sound card) nothing
+// is loaded and nothing plays, and the game goes on without sound.
+
+
+/// The loaded sounds and how loud to play them
+pub(crate) struct Audio {
+    effects: Vec<(Effect, Sound)>,
+    music: Option<Sound>,
+    volume: Volume,
+    /// Why there is no sound at all, if there is none
+    off: Option<&'static str>,
+    dragging: Option<Slider>,
+}
+
+impl Audio {
+
+    /// Loads every effect, and the music if the theme pack has some. Whatever fails to load stays silent.
+    pub(crate) async fn load(source: &AssetSource) -> Self {
+        let mut audio = Self { effects: Vec::new(), music: None, volume: Volume::default(), off: None, dragging: None };
+        if !device_available() {
+            eprintln!("no audio device, playing without sound");
+            audio.off = Some("No audio device, so there is no sound");
+            return audio;
+        }
+
+        for effect in Effect::ALL {
+            if let Some(sound) = load(effect.name(), source.sound(effect.name())).await {
+                audio.effects.push((effect, sound));
+            }
+        }
+        audio.music = match source.sound(sound::MUSIC) {
+            Err(AssetError::Missing(_)) => None, // music is optional
+            bytes => load(sound::MUSIC, bytes).await,
+        };
+        if let Some(music) = &audio.music {
+            play_sound(music, PlaySoundParams { looped: true, volume: audio.volume.music_level() });
+        }
+        audio
+    }
+}
+
+
+impl Gui {
+
+    /// Plays a sound effect, unless the effects are turned all the way down
+    pub(crate) fn play(&self, effect: Effect) {
+        let level = self.audio.volume.effects_level();
+        if level <= 0.0 {
+            return;
+        }
+        if let Some((_, sound)) = self.audio.effects.iter().find(|(e, _)| *e == effect) {
+            play_sound(sound, PlaySoundParams { looped: false, volume: level });
+        }
+    }
+
+    /// Plays whatever the event sounds like, if anything
+    pub(crate) fn play_event(&self, event: &Event) {
+        if let Some(effect) = Effect::for_event(event) {
+            self.play(effect);
+        }
+    }
+
+    /// M mutes and unmutes. While the sound panel is open, the sliders follow the mouse when pressed on.
+    pub(crate) fn adjust_sound(&mut self) {
+        let before = self.audio.volume;
+        if is_key_pressed(KeyCode::M) {
+            self.audio.volume.muted = !self.audio.volume.muted;
+        }
+
+        if self.panel == Some(Panel::Sound) {
+            let (mouse_x, mouse_y) = mouse_position();
+            if is_mouse_button_pressed(MouseButton::Left) {
+                self.audio.dragging = Slider::ALL.into_iter().find(|&slider| self.slider_bar(slider).shrink(-8.0).contains(mouse_x, mouse_y));
+            }
+            if let Some(slider) = self.audio.dragging {
+                let bar = self.slider_bar(slider);
+                self.audio.volume.set(slider, (mouse_x - bar.x) / bar.w);
+            }
+        }
+        if !is_mouse_button_down(MouseButton::Left) || self.panel != Some(Panel::Sound) {
+            self.audio.dragging = None;
+        }
+
+        if let Some(music) = &self.audio.music && self.audio.volume.music_level() != before.music_level() {
+            set_sound_volume(music, self.audio.volume.music_level());
+        }
+    }
+
+    /// The sound panel: a slider for each volume and whether it is muted
+    pub(crate) fn draw_sound_panel(&self) {
+        let rect = self.layout.panel().shrink(20.0);
+        let size = self.layout.text_size * 1.2;
+        draw_text("Sound (S to close, M to mute)", rect.x, rect.y + size, size * 1.3, WHITE);
+        if let Some(off) = self.audio.off {
+            draw_text(off, rect.x, rect.y + size * 3.0, size, board_gui::ACCENT_COLOR);
+            return;
+        }
+
+        let volume = self.audio.volume;
+        for slider in Slider::ALL {
+            let bar = self.slider_bar(slider);
+            let level = volume.get(slider);
+            draw_text(slider.label(), rect.x, bar.y + bar.h, size, WHITE);
+            draw_rectangle(bar.x, bar.y, bar.w * level, bar.h, if volume.muted { board_gui::ACCENT_COLOR } else { board_gui::GREEN });
+            draw_rectangle_lines(bar.x, bar.y, bar.w, bar.h, 2.0, WHITE);
+            draw_text(&format!("{}%", (level * 100.0).round()), bar.right() + size * 0.5, bar.y + bar.h, size, WHITE);
+        }
+
+        let mut y = self.slider_bar(Slider::Music).bottom() + size * 2.0;
+        if volume.muted {
+            draw_text("Muted, press M to hear it again", rect.x, y, size, board_gui::RED);
+            y += size * 1.5;
+        }
+        if self.audio.music.is_none() {
+            draw_text("No music: a theme pack can add sounds/music.ogg or sounds/music.wav", rect.x, y, size, board_gui::ACCENT_COLOR);
+        }
+    }
+
+    /// Where a volume slider's bar is drawn in the sound panel
+    fn slider_bar(&self, slider: Slider) -> Rect {
+        let rect = self.layout.panel().shrink(20.0);
+        let size = self.layout.text_size * 1.2;
+        let row = Slider::ALL.iter().position(|&s| s == slider).unwrap_or(0) as f32;
+        let x = rect.x + size * 6.0;
+        Rect::new(x, rect.y + size * (3.0 + row * 2.0), (rect.w - size * 10.0).max(size), size)
+    }
+}
+
+
+/// Whether there is something to play sound on. macroquad's audio thread stops when it finds none,
+/// and every sound sent to it after that would only print an error.
+fn device_available() -> bool {
+    if cfg!(target_os = "linux") {
+        std::path::Path::new("/dev/snd").is_dir()
+    } else {
+        true
+    }
+}
+
+
+/// Loads a sound read by AssetSource::sound, or None (after saying why) if it cannot be played.
+/// The backend gives up on sounds it cannot decode, so the format is checked before it gets them.
+async fn load(name: &str, bytes: Result<Vec<u8>, AssetError>) -> Option<Sound> {
+    let checked = bytes.and_then(|bytes| match sound::check_format(&bytes) {
+        Ok(()) => Ok(bytes),
+        Err(e) => Err(AssetError::Malformed(name.into(), e)),
+    });
+    let bytes = match checked {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("sound problem: {}", e);
+            return None;
+        }
+    };
+    match load_sound_from_bytes(&bytes).await {
+        Ok(sound) => Some(sound),
+        Err(e) => {
+            eprintln!("sound problem: {} could not be loaded: {}", name, e);
+            None
+        }
+    }
+}
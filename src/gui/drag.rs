@@ -0,0 +1,59 @@
+use macroquad::prelude::*;
+use crate::components::grid_domino::GridDomino;
+use crate::components::player::Player;
+use crate::gui::Gui;
+use crate::sound::Effect;
+
+// Dragging the domino being placed from the place draft onto the kingdom. Pressing on it picks it up,
+// the mouse wheel or a right click turns it while it is held, and letting go over a spot it fits places it.
+// Letting go anywhere else (or pressing Escape) puts it back in the draft.
+
+
+impl Gui {
+
+    /// Starts dragging the domino in this slot of the place draft
+    pub(crate) fn start_drag(&mut self, slot: usize) {
+        self.dragging = Some(slot);
+        self.focus = None;
+    }
+
+    /// The place draft slot whose domino is being dragged, if any
+    pub(crate) fn dragged_slot(&self) -> Option<usize> {self.dragging}
+
+    /// The placement made by dropping the dragged domino this frame, if any. Also turns it with the wheel and right click.
+    pub(crate) fn dropped_placement(&mut self, cur_player: &Player) -> Option<GridDomino> {
+        self.dragging?;
+        let (_, wheel) = mouse_wheel();
+        if wheel > 0.0 || is_mouse_button_pressed(MouseButton::Right) {
+            self.domino_rotation = self.domino_rotation.next();
+            self.play(Effect::Rotate);
+        } else if wheel < 0.0 {
+            self.domino_rotation = self.domino_rotation.prev();
+            self.play(Effect::Rotate);
+        }
+
+        if is_key_pressed(KeyCode::Escape) {
+            self.dragging = None;
+            return None;
+        }
+        if !is_mouse_button_released(MouseButton::Left) {
+            return None;
+        }
+        self.dragging = None;
+
+        let (mouse_x, mouse_y) = mouse_position();
+        let origin = *self.fetch_offset(cur_player.id());
+        let (x, y) = self.layout.kingdom_cell_at((origin[0], origin[1]), mouse_x, mouse_y);
+        let (Ok(x), Ok(y)) = (u8::try_from(x), u8::try_from(y)) else { return None };
+        let pane = self.layout.panes[cur_player.id() as usize - 1];
+        let rotation = self.domino_rotation.build_rotation();
+        if !pane.contains(mouse_x, mouse_y) {
+            return None; // back to the draft it goes
+        }
+        if cur_player.grid().check_placement(&cur_player.placing(), x, y, rotation).is_err() {
+            self.play(Effect::Illegal);
+            return None;
+        }
+        Some(GridDomino::new(x, y, cur_player.placing().id() as usize, rotation.radians()))
+    }
+}
@@ -0,0 +1,86 @@
+use macroquad::prelude::*;
+use crate::components::game::Game;
+use crate::components::player::Player;
+use crate::components::variant::Variant;
+use crate::gui::{board_gui, Gui};
+use crate::layout;
+use crate::tile_art;
+
+// Age of Giants at the table. Giants stand on the crowns they cover, and the ones still looking for a crown are
+// counted along the bottom of their pane. The quests lie above and below the draft scroll, with their rule shown
+// while the mouse is over one, and the giants left in the supply are counted under them.
+
+
+const QUEST_COLOR: Color = Color::from_rgba(222, 204, 160, 255);
+const QUEST_INK: Color = Color::from_rgba(60, 40, 20, 255);
+
+
+impl Gui {
+
+    /// The giants on a player's kingdom, and how many of theirs are waiting for a crown. Age of Giants only.
+    pub(crate) fn draw_giants(&self, game: &Game, player: &Player) {
+        if game.variant() != Variant::AgeOfGiants {
+            return;
+        }
+        let origin = *self.fetch_offset(player.id());
+        for &(x, y) in player.giants().iter().flatten() {
+            let cell = self.layout.kingdom_cell((origin[0], origin[1]), x as usize, y as usize);
+            tile_art::draw_giant(cell.x, cell.y, cell.w);
+        }
+
+        let waiting = player.giants().iter().filter(|giant| giant.is_none()).count();
+        if waiting > 0 {
+            let pane = self.layout.panes[player.id() as usize - 1].shrink(3.0 * layout::PANE_MARGIN);
+            let line = format!("{} {} waiting for a crown", waiting, if waiting == 1 { "giant" } else { "giants" });
+            draw_text(&line, pane.x, pane.bottom(), self.layout.text_size, board_gui::YELLOW);
+        }
+    }
+
+    /// The quest tiles next to the draft scroll and the giants left in the supply. Age of Giants only.
+    pub(crate) fn draw_quests(&self, game: &Game) {
+        if game.variant() != Variant::AgeOfGiants {
+            return;
+        }
+        let (mouse_x, mouse_y) = mouse_position();
+        let mut hovered = None;
+        for (idx, quest) in game.quests().iter().enumerate() {
+            let tile = self.layout.quest_tile(idx);
+            draw_rectangle(tile.x, tile.y, tile.w, tile.h, QUEST_COLOR);
+            draw_rectangle_lines(tile.x, tile.y, tile.w, tile.h, 2.0, board_gui::ACCENT_COLOR);
+            // a word per line, as large as the longest one allows
+            let words: Vec<&str> = quest.name().split(' ').collect();
+            let size = words.iter().map(|word| fitting_size(word, tile.w * 0.9, self.layout.text_size)).fold(f32::INFINITY, f32::min);
+            let top = tile.center().1 - size * (words.len() as f32 / 2.0 - 0.8);
+            for (line, word) in words.iter().enumerate() {
+                let width = measure_text(word, None, size as u16, 1.0).width;
+                draw_text(word, tile.center().0 - width/2.0, top + line as f32 * size, size, QUEST_INK);
+            }
+            if tile.contains(mouse_x, mouse_y) {
+                hovered = Some((quest, tile));
+            }
+        }
+
+        let supply = self.layout.giant_supply();
+        let left = game.giants_left();
+        let line = format!("{} {} left", left, if left == 1 { "giant" } else { "giants" });
+        let size = fitting_size(&line, supply.w, self.layout.text_size * 0.8);
+        draw_text(&line, supply.x, supply.bottom(), size, WHITE);
+
+        // the rule of the quest under the mouse, over the draft
+        if let Some((quest, tile)) = hovered {
+            let size = self.layout.text_size;
+            let text = format!("{}: {}", quest.name(), quest.rule());
+            let width = measure_text(&text, None, size as u16, 1.0).width;
+            let (x, y) = (tile.right() + 5.0, tile.center().1 - size);
+            draw_rectangle(x, y, width + size, size * 2.0, board_gui::LABEL_BACKGROUND);
+            draw_text(&text, x + size/2.0, y + size * 1.3, size, WHITE);
+        }
+    }
+}
+
+
+/// The largest font size up to `max` that fits the text in `width`
+fn fitting_size(text: &str, width: f32, max: f32) -> f32 {
+    let at_max = measure_text(text, None, max as u16, 1.0).width;
+    if at_max <= width { max } else { (max * width / at_max).floor().max(6.0) }
+}
@@ -0,0 +1,72 @@
+use macroquad::prelude::*;
+use crate::components::grid::BuildRotation;
+use crate::components::grid_domino::GridDomino;
+use crate::components::player::Player;
+use crate::gui::{board_gui, Gui};
+
+// The placement hint: V colors every socket by how many points putting the domino there would add,
+// from red for the least to green for the most, and marks the best placement in any rotation.
+// Network games have no hints, so nobody gets help the others don't.
+
+
+impl Gui {
+
+    /// Turns the placement hint on and off with V, when hints are allowed
+    pub(crate) fn toggle_hints(&mut self) {
+        if self.hints_allowed && is_key_pressed(KeyCode::V) {
+            self.show_hints = !self.show_hints;
+        }
+    }
+
+    /// No hints for the rest of this game, for games where they would be unfair
+    pub(crate) fn disable_hints(&mut self) {
+        self.hints_allowed = false;
+        self.show_hints = false;
+    }
+
+    /// Colors the sockets of the current rotation by the points they add, and outlines the best placement
+    pub(crate) fn draw_placement_hints(&self, active_player: &Player) {
+        if !self.show_hints {
+            return;
+        }
+        let values = Self::placement_values(active_player);
+        let Some(&(best, best_value)) = values.iter().max_by_key(|(_, value)| *value) else { return };
+        let worst_value = values.iter().map(|(_, value)| *value).min().unwrap_or(best_value);
+
+        let origin = *self.fetch_offset(active_player.id());
+        let origin = (origin[0], origin[1]);
+        let tile = self.layout.kingdom_tile;
+        let rotation = self.domino_rotation.build_rotation();
+        let size = (tile * 0.35).max(10.0);
+
+        for (placement, value) in &values {
+            if BuildRotation::from_radians(*placement.rotation()) != rotation {
+                continue;
+            }
+            let share = if best_value == worst_value { 1.0 } else { (value - worst_value) as f32 / (best_value - worst_value) as f32 };
+            let cell = self.layout.kingdom_cell(origin, *placement.x() as usize, *placement.y() as usize);
+            draw_rectangle(cell.x, cell.y, cell.w, cell.h, Color::new(1.0 - share, share, 0.2, board_gui::HINT_ALPHA));
+            draw_text(&format!("+{}", value), cell.x + 3.0, cell.bottom() - 3.0, size, WHITE);
+        }
+
+        // the best placement gets both its cells outlined, even when it needs another rotation
+        let (dx, dy) = BuildRotation::from_radians(*best.rotation()).second_offset();
+        let (x, y) = (*best.x() as usize, *best.y() as usize);
+        let first = self.layout.kingdom_cell(origin, x, y);
+        let second = self.layout.kingdom_cell(origin, (x as isize + dx) as usize, (y as isize + dy) as usize);
+        let (left, top) = (first.x.min(second.x), first.y.min(second.y));
+        let (right, bottom) = (first.right().max(second.right()), first.bottom().max(second.bottom()));
+        draw_rectangle_lines(left, top, right - left, bottom - top, board_gui::FOCUS_THICKNESS, board_gui::YELLOW);
+        draw_text(&format!("best +{}", best_value), left, top - 4.0, size, board_gui::YELLOW);
+    }
+
+    /// Every legal placement of the player's domino with the points it would add to their kingdom
+    fn placement_values(player: &Player) -> Vec<(GridDomino, i64)> {
+        let grid = player.grid();
+        let domino = player.placing();
+        let score = grid.score() as i64;
+        grid.legal_placements(&domino).into_iter()
+            .map(|placement| (placement, grid.score_with(placement, &domino) as i64 - score))
+            .collect()
+    }
+}
@@ -0,0 +1,160 @@
+use macroquad::prelude::*;
+use crate::components::draft::Draft;
+use crate::components::grid::BuildRotation;
+use crate::components::grid_domino::GridDomino;
+use crate::components::player::Player;
+use crate::gui::Gui;
+use crate::sound::Effect;
+
+// Playing without the mouse. Arrow keys move the focus (a draft slot while picking, a cell of the
+// kingdom while placing), Q and E turn the domino either way, Enter commits and Escape lets go of
+// the focus. Moving the mouse hands control back to it.
+
+
+/// Where the keyboard is pointing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Focus {
+    /// A slot of the pick draft
+    DraftSlot(usize),
+    /// The kingdom cell the first tile of the held domino would go on
+    Cell(i32, i32),
+}
+
+/// Side length of the kingdom grid the cursor moves over
+const GRID_SIZE: i32 = 9;
+
+
+impl Gui {
+
+    /// The draft slot picked with the keyboard this frame, if any
+    pub(crate) fn keyboard_pick(&mut self, draft: &Draft) -> Option<usize> {
+        self.drop_focus_on_mouse_move();
+        let pickable: Vec<usize> = (0..draft.len()).filter(|&slot| draft.pickable(slot)).collect();
+        if pickable.is_empty() {
+            return None;
+        }
+
+        let current = match self.focus {
+            Some(Focus::DraftSlot(slot)) => pickable.iter().position(|&s| s == slot),
+            _ => None,
+        };
+        let step: isize = if is_key_pressed(KeyCode::Down) || is_key_pressed(KeyCode::Right) {
+            1
+        } else if is_key_pressed(KeyCode::Up) || is_key_pressed(KeyCode::Left) {
+            -1
+        } else {
+            0
+        };
+        if step != 0 {
+            // the first arrow press only shows where the focus is
+            let next = match current {
+                Some(idx) => (idx as isize + step).rem_euclid(pickable.len() as isize) as usize,
+                None => 0,
+            };
+            self.focus = Some(Focus::DraftSlot(pickable[next]));
+        }
+
+        if is_key_pressed(KeyCode::Escape) {
+            self.focus = None;
+        }
+        match self.focus {
+            Some(Focus::DraftSlot(slot)) if is_key_pressed(KeyCode::Enter) && draft.pickable(slot) => {
+                self.focus = None;
+                Some(slot)
+            }
+            _ => None,
+        }
+    }
+
+    /// The placement committed with the keyboard this frame, if any. Also turns the held domino with R, Q and E.
+    pub(crate) fn keyboard_placement(&mut self, cur_player: &Player) -> Option<GridDomino> {
+        self.drop_focus_on_mouse_move();
+        if is_key_pressed(KeyCode::R) || is_key_pressed(KeyCode::E) {
+            self.domino_rotation = self.domino_rotation.next();
+            self.play(Effect::Rotate);
+        }
+        if is_key_pressed(KeyCode::Q) {
+            self.domino_rotation = self.domino_rotation.prev();
+            self.play(Effect::Rotate);
+        }
+
+        let step = if is_key_pressed(KeyCode::Left) {
+            Some((-1, 0))
+        } else if is_key_pressed(KeyCode::Right) {
+            Some((1, 0))
+        } else if is_key_pressed(KeyCode::Up) {
+            Some((0, -1))
+        } else if is_key_pressed(KeyCode::Down) {
+            Some((0, 1))
+        } else {
+            None
+        };
+        if let Some((dx, dy)) = step {
+            self.focus = Some(match self.focus {
+                Some(Focus::Cell(x, y)) => Focus::Cell((x + dx).clamp(0, GRID_SIZE - 1), (y + dy).clamp(0, GRID_SIZE - 1)),
+                // the first arrow press puts the cursor somewhere the domino fits, if there is such a place
+                _ => self.starting_cell(cur_player),
+            });
+        }
+
+        if is_key_pressed(KeyCode::Escape) {
+            self.focus = None;
+        }
+        let Some(Focus::Cell(x, y)) = self.focus else { return None };
+        if !is_key_pressed(KeyCode::Enter) {
+            return None;
+        }
+        let rotation = self.domino_rotation.build_rotation();
+        let (x, y) = (x as u8, y as u8);
+        match cur_player.grid().check_placement(&cur_player.placing(), x, y, rotation) {
+            Ok(()) => {
+                self.focus = None;
+                Some(GridDomino::new(x, y, cur_player.placing().id() as usize, rotation.radians()))
+            }
+            Err(_) => {
+                self.play(Effect::Illegal); // the ghost already says why
+                None
+            }
+        }
+    }
+
+    /// The kingdom cell under the keyboard cursor, if it is being used
+    pub(crate) fn focused_cell(&self) -> Option<(i32, i32)> {
+        match self.focus {
+            Some(Focus::Cell(x, y)) => Some((x, y)),
+            _ => None,
+        }
+    }
+
+    /// The draft slot with the keyboard focus, if any
+    pub(crate) fn focused_slot(&self) -> Option<usize> {
+        match self.focus {
+            Some(Focus::DraftSlot(slot)) => Some(slot),
+            _ => None,
+        }
+    }
+
+    /// Forgets the focus and drops whatever is being dragged, like after an undo when they may point at something
+    /// that is not there anymore
+    pub(crate) fn clear_focus(&mut self) {
+        self.focus = None;
+        self.dragging = None;
+    }
+
+    /// The first legal spot for the current rotation, or next to the castle
+    fn starting_cell(&self, cur_player: &Player) -> Focus {
+        let rotation = self.domino_rotation.build_rotation();
+        cur_player.grid().legal_placements(&cur_player.placing()).iter()
+            .find(|placement| BuildRotation::from_radians(*placement.rotation()) == rotation)
+            .map_or(Focus::Cell(4, 4), |placement| Focus::Cell(*placement.x() as i32, *placement.y() as i32))
+    }
+
+    /// Once the mouse moves, it is in charge again
+    fn drop_focus_on_mouse_move(&mut self) {
+        let mouse = mouse_position();
+        if mouse != self.last_mouse {
+            self.focus = None;
+            self.last_mouse = mouse;
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use macroquad::prelude::*;
+use crate::assets::{AssetLoader, AssetSource, Assets};
+use crate::components::domino_set::DominoSet;
+use crate::gui::audio::Audio;
+use crate::gui::board_gui;
+
+// The loading scene: a progress bar while the pictures load, then a list of whatever could not be loaded,
+// so the player knows why some of the game is drawn plainly before it starts. The sounds load after that.
+
+
+/// How long a frame may spend loading before the progress bar is drawn again, in seconds
+const FRAME_BUDGET: f64 = 1.0 / 60.0;
+
+const BAR_WIDTH: f32 = 400.0;
+const BAR_HEIGHT: f32 = 24.0;
+const FONT_SIZE: f32 = 24.0;
+
+
+/// Loads every picture of the set, showing how far along it is, then lists the failures (if any)
+/// until the player moves on. Whatever failed is drawn without its picture.
+pub(crate) async fn load_assets(set: &DominoSet, source: &AssetSource) -> Assets {
+    let mut loader = AssetLoader::new(set, source);
+
+    let mut loading = true;
+    while loading {
+        let started = get_time();
+        while loading && get_time() - started < FRAME_BUDGET {
+            loading = loader.load_next();
+        }
+        draw_progress(&loader);
+        next_frame().await;
+    }
+
+    if !loader.problems().is_empty() {
+        while !(is_key_pressed(KeyCode::Enter) || is_mouse_button_pressed(MouseButton::Left)) {
+            draw_problems(&loader);
+            next_frame().await;
+        }
+    }
+    loader.finish()
+}
+
+
+/// Loads the sound effects and the music. Those that fail are only reported in the terminal, the game
+/// just plays without them.
+pub(crate) async fn load_sounds(source: &AssetSource) -> Audio {
+    clear_background(board_gui::BACKGROUND_COLOR);
+    draw_text("Loading sounds", screen_width()/2.0 - BAR_WIDTH/2.0, screen_height()/2.0, FONT_SIZE * 1.5, WHITE);
+    next_frame().await;
+    Audio::load(source).await
+}
+
+
+/// The bar, and the name of the picture being loaded under it
+fn draw_progress(loader: &AssetLoader) {
+    clear_background(board_gui::BACKGROUND_COLOR);
+    let (done, total) = loader.progress();
+    let x = screen_width()/2.0 - BAR_WIDTH/2.0;
+    let y = screen_height()/2.0 - BAR_HEIGHT/2.0;
+
+    draw_text("Loading", x, y - FONT_SIZE, FONT_SIZE * 1.5, WHITE);
+    draw_rectangle(x, y, BAR_WIDTH * done as f32 / total.max(1) as f32, BAR_HEIGHT, board_gui::ACCENT_COLOR);
+    draw_rectangle_lines(x, y, BAR_WIDTH, BAR_HEIGHT, 2.0, WHITE);
+
+    let line = match loader.current() {
+        Some(name) => format!("{}/{}  {}", done, total, name),
+        None => format!("{}/{}", done, total),
+    };
+    draw_text(&line, x, y + BAR_HEIGHT + FONT_SIZE * 1.5, FONT_SIZE, WHITE);
+    if !loader.problems().is_empty() {
+        let failed = format!("{} could not be loaded", loader.problems().len());
+        draw_text(&failed, x, y + BAR_HEIGHT + FONT_SIZE * 3.0, FONT_SIZE, board_gui::RED);
+    }
+}
+
+
+/// Every asset that failed and why
+fn draw_problems(loader: &AssetLoader) {
+    clear_background(board_gui::BACKGROUND_COLOR);
+    let mut y = FONT_SIZE * 2.0;
+    let heading = format!("{} picture(s) could not be loaded, they will be drawn plainly:", loader.problems().len());
+    draw_text(&heading, FONT_SIZE, y, FONT_SIZE, WHITE);
+
+    for problem in loader.problems() {
+        y += FONT_SIZE * 1.2;
+        if y > screen_height() - FONT_SIZE * 3.0 {
+            draw_text("... and more, see the terminal", FONT_SIZE * 2.0, y, FONT_SIZE, board_gui::ACCENT_COLOR);
+            break;
+        }
+        draw_text(&problem.to_string(), FONT_SIZE * 2.0, y, FONT_SIZE, board_gui::RED);
+    }
+    draw_text("Press Enter or click to play", FONT_SIZE, screen_height() - FONT_SIZE, FONT_SIZE, WHITE);
+}
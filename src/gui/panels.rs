@@ -0,0 +1,212 @@
+use macroquad::prelude::*;
+use crate::components::domino::Domino;
+use crate::components::game::Game;
+use crate::components::player::Player;
+use crate::controller::Phase;
+use crate::deck_tracker::{chance_in_draft, DeckSummary};
+use crate::event_log::EventLog;
+use crate::gui::{board_gui, Gui};
+use crate::tile_art;
+
+// Pop-up panels drawn over the kingdoms, for learning the game. A opens the draft analysis: for every domino
+// in the pick draft, what it is worth to the player picking, where it puts them in next round's pick order
+// (lower dominoes pick first, see Draft::apply_new_order) and which opponent would like it most.
+// D opens the deck tracker: what is left to be dealt and the odds of seeing it in the next draft.
+// L opens the event log, which scrolls with the mouse wheel and Page Up/Down. S opens the sound settings,
+// B the Queendomino builders' board (see town.rs).
+
+
+/// The panel that is open, if any
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Panel {
+    DraftAnalysis,
+    Deck,
+    Log,
+    Sound,
+    Builders,
+}
+
+
+impl Gui {
+
+    /// Opens and closes the panels: A for the draft analysis, D for the deck tracker, L for the event log, S for sound,
+    /// B for the builders' board. Opening one closes the other. Also scrolls the log while it is open.
+    pub(crate) fn toggle_panels(&mut self) {
+        for (key, panel) in [(KeyCode::A, Panel::DraftAnalysis), (KeyCode::D, Panel::Deck), (KeyCode::L, Panel::Log),
+                              (KeyCode::S, Panel::Sound), (KeyCode::B, Panel::Builders)] {
+            if is_key_pressed(key) {
+                self.panel = if self.panel == Some(panel) { None } else { Some(panel) };
+                self.log_scroll = 0;
+            }
+        }
+
+        // the wheel turns a dragged domino, so it only scrolls when nothing is being dragged
+        if self.panel == Some(Panel::Log) {
+            let (_, wheel) = mouse_wheel();
+            if is_key_pressed(KeyCode::PageUp) || (wheel > 0.0 && self.dragged_slot().is_none()) {
+                self.log_scroll += if wheel > 0.0 { 3 } else { 10 };
+            }
+            if is_key_pressed(KeyCode::PageDown) || (wheel < 0.0 && self.dragged_slot().is_none()) {
+                self.log_scroll = self.log_scroll.saturating_sub(if wheel < 0.0 { 3 } else { 10 });
+            }
+        }
+    }
+
+    /// Draws the open panel, if any, on top of everything else
+    pub(crate) fn draw_panel(&self, game: &Game, log: &EventLog) {
+        let Some(panel) = self.panel else { return };
+        let rect = self.layout.panel();
+        let background = board_gui::BACKGROUND_COLOR;
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, Color::new(background.r, background.g, background.b, 0.93));
+        draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 3.0, board_gui::ACCENT_COLOR);
+
+        match panel {
+            Panel::DraftAnalysis => self.draw_draft_analysis(game),
+            Panel::Deck => self.draw_deck_tracker(game),
+            Panel::Log => self.draw_log(log),
+            Panel::Sound => self.draw_sound_panel(),
+            Panel::Builders => self.draw_builders_board(game),
+        }
+    }
+
+    /// One row per domino of the pick draft
+    fn draw_draft_analysis(&self, game: &Game) {
+        let rect = self.layout.panel().shrink(20.0);
+        let size = self.layout.text_size * 1.2;
+        draw_text("Draft analysis (A to close)", rect.x, rect.y + size, size * 1.3, WHITE);
+        if game.phase() != Some(Phase::Picking) {
+            draw_text("Opens up while a king is picking", rect.x, rect.y + size * 3.0, size, board_gui::ACCENT_COLOR);
+            return;
+        }
+
+        let draft = game.pick_draft();
+        let active = game.active_player();
+        let last_draft = game.deck_len() == 0;
+        let row_h = ((rect.h - size * 3.0) / draft.len().max(1) as f32).min(size * 4.0);
+        let tile = row_h * 0.6;
+
+        for (slot, domino) in draft.iter().enumerate() {
+            let y = rect.y + size * 2.5 + slot as f32 * row_h;
+            self.draw_domino(domino, tile, rect.x, y);
+            let text_x = rect.x + tile * 2.5;
+
+            if let Some(id) = draft.player_on(slot) {
+                self.draw_king(id, text_x, y, tile);
+                draw_text("already taken", text_x + tile * 1.2, y + tile * 0.7, size, board_gui::ACCENT_COLOR);
+                continue;
+            }
+
+            let gain = match best_gain(active, domino) {
+                Some(gain) => format!("+{} points for you", gain),
+                None => String::from("no room for it in your kingdom"),
+            };
+            let order = if last_draft {
+                String::from("last draft, there is no next pick")
+            } else {
+                format!("you pick {} next round", ordinal(slot + 1))
+            };
+            let rival = game.players().iter()
+                .filter(|player| player.id() != active.id())
+                .filter_map(|player| best_gain(player, domino).map(|gain| (player, gain)))
+                .max_by_key(|&(_, gain)| gain);
+            let helps = match rival {
+                Some((player, gain)) if gain > 0 => format!("worth +{} to {}", gain, player.name()),
+                _ => String::from("worth nothing to the others"),
+            };
+
+            draw_text(&gain, text_x, y + size, size, WHITE);
+            draw_text(&order, text_x, y + size * 2.0, size, board_gui::ACCENT_COLOR);
+            draw_text(&helps, text_x, y + size * 3.0, size, board_gui::ACCENT_COLOR);
+        }
+    }
+
+    /// What is left in the deck by terrain, by crowns and by pair, with the odds for the next draft
+    fn draw_deck_tracker(&self, game: &Game) {
+        let rect = self.layout.panel().shrink(20.0);
+        let size = self.layout.text_size * 1.1;
+        let summary = DeckSummary::new(game.deck().dominoes());
+        let draft = game.players().len();
+        draw_text(&format!("Deck: {} dominoes left (D to close)", summary.remaining), rect.x, rect.y + size, size * 1.3, WHITE);
+        if summary.remaining == 0 {
+            draw_text("Every domino has been dealt", rect.x, rect.y + size * 3.0, size, board_gui::ACCENT_COLOR);
+            return;
+        }
+        let chance = |matching| format!("{:.0}%", 100.0 * chance_in_draft(summary.remaining, matching, draft));
+
+        // by terrain, with a swatch of its color
+        let mut y = rect.y + size * 3.0;
+        draw_text("Terrain: dominoes, crowns, chance in the next draft (any / with a crown)", rect.x, y, size, board_gui::ACCENT_COLOR);
+        for count in &summary.terrains {
+            y += size * 1.3;
+            draw_rectangle(rect.x, y - size * 0.8, size, size, tile_art::terrain_color(count.terrain));
+            let line = format!("{:?}: {}, {} crowns, {} / {}", count.terrain, count.dominoes, count.crowns,
+                               chance(count.dominoes), chance(count.crowned));
+            draw_text(&line, rect.x + size * 1.5, y, size, WHITE);
+        }
+
+        // by crowns on the whole domino
+        y += size * 2.0;
+        draw_text("Crowns per domino: dominoes, chance in the next draft", rect.x, y, size, board_gui::ACCENT_COLOR);
+        for (crowns, &dominoes) in summary.by_crowns.iter().enumerate() {
+            y += size * 1.3;
+            let line = format!("{} crown{}: {}, {}", crowns, if crowns == 1 { "" } else { "s" }, dominoes, chance(dominoes));
+            draw_text(&line, rect.x + size * 1.5, y, size, WHITE);
+        }
+
+        // pairs in a second column, as many as fit
+        let x = rect.x + rect.w / 2.0 + size;
+        let mut y = rect.y + size * 3.0;
+        draw_text("Terrain pairs", x, y, size, board_gui::ACCENT_COLOR);
+        for ((first, second), count) in &summary.pairs {
+            y += size * 1.3;
+            if y > rect.bottom() {
+                break;
+            }
+            let pair = if first == second { format!("{:?} x2", first) } else { format!("{:?} / {:?}", first, second) };
+            draw_text(&format!("{}: {}", pair, count), x, y, size, WHITE);
+        }
+    }
+
+    /// The newest lines of the log that fit, or older ones when scrolled back
+    fn draw_log(&self, log: &EventLog) {
+        let rect = self.layout.panel().shrink(20.0);
+        let size = self.layout.text_size;
+        draw_text("Event log (L to close, wheel or Page Up/Down to scroll)", rect.x, rect.y + size, size * 1.3, WHITE);
+        if log.lines().is_empty() {
+            draw_text("Nothing has happened yet", rect.x, rect.y + size * 3.0, size, board_gui::ACCENT_COLOR);
+            return;
+        }
+
+        let fits = (((rect.h - size * 2.5) / (size * 1.25)) as usize).max(1);
+        let newest = log.lines().len() - self.log_scroll.min(log.lines().len() - 1);
+        let oldest = newest.saturating_sub(fits);
+        for (row, line) in log.lines()[oldest..newest].iter().enumerate() {
+            draw_text(line, rect.x, rect.y + size * 2.5 + (row + 1) as f32 * size * 1.25, size, WHITE);
+        }
+        if newest < log.lines().len() {
+            let more = format!("{} newer lines below", log.lines().len() - newest);
+            draw_text(&more, rect.x, rect.bottom(), size, board_gui::ACCENT_COLOR);
+        }
+    }
+}
+
+
+/// The most points the domino could add to the player's kingdom right now. None if it fits nowhere.
+fn best_gain(player: &Player, domino: &Domino) -> Option<u32> {
+    let grid = player.grid();
+    let score = grid.score();
+    grid.legal_placements(domino).into_iter()
+        .map(|placement| grid.score_with(placement, domino).saturating_sub(score))
+        .max()
+}
+
+/// 1st, 2nd, 3rd...
+fn ordinal(n: usize) -> String {
+    let suffix = match n {
+        1 => "st",
+        2 => "nd",
+        3 => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
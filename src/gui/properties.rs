@@ -0,0 +1,77 @@
+use macroquad::prelude::*;
+use crate::components::player::Player;
+use crate::gui::{board_gui, Gui};
+
+// Overlays showing the properties (connected tiles of one terrain) of the kingdoms. P outlines every property
+// with what it scores, H lights up the property under the mouse.
+
+
+/// Which of the property overlays are on
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct PropertyView {
+    pub(crate) outlines: bool,
+    pub(crate) hover: bool,
+}
+
+
+impl Gui {
+
+    /// Turns the property overlays on and off: P for the outlines, H for the hover highlight
+    pub(crate) fn toggle_property_view(&mut self) {
+        if is_key_pressed(KeyCode::P) {
+            self.property_view.outlines = !self.property_view.outlines;
+        }
+        if is_key_pressed(KeyCode::H) {
+            self.property_view.hover = !self.property_view.hover;
+        }
+    }
+
+    /// Draws whichever property overlays are on over a player's kingdom
+    pub(crate) fn draw_properties(&self, player: &Player) {
+        let grid = player.grid();
+        let origin = *self.fetch_offset(player.id());
+        let origin = (origin[0], origin[1]);
+        let tile = self.layout.kingdom_tile;
+
+        if self.property_view.hover {
+            let (mouse_x, mouse_y) = mouse_position();
+            let (x, y) = self.layout.kingdom_cell_at(origin, mouse_x, mouse_y);
+            if (0..9).contains(&x) && (0..9).contains(&y) && self.layout.panes[player.id() as usize - 1].contains(mouse_x, mouse_y) {
+                for (px, py) in grid.property(x as usize, y as usize) {
+                    let cell = self.layout.kingdom_cell(origin, px, py);
+                    draw_rectangle(cell.x, cell.y, cell.w, cell.h, board_gui::PROPERTY_HOVER);
+                }
+            }
+        }
+
+        if !self.property_view.outlines {
+            return;
+        }
+        for property in grid.properties() {
+            // an edge of a tile is on the outline when the tile across it is not in the property
+            for &(x, y) in &property {
+                let cell = self.layout.kingdom_cell(origin, x, y);
+                let inside = |dx: isize, dy: isize| property.contains(&((x as isize + dx) as usize, (y as isize + dy) as usize));
+                let edges = [
+                    (!inside(-1, 0), (cell.x, cell.y, cell.x, cell.bottom())),
+                    (!inside(1, 0), (cell.right(), cell.y, cell.right(), cell.bottom())),
+                    (!inside(0, -1), (cell.x, cell.y, cell.right(), cell.y)),
+                    (!inside(0, 1), (cell.x, cell.bottom(), cell.right(), cell.bottom())),
+                ];
+                for (_, (x1, y1, x2, y2)) in edges.into_iter().filter(|(on_outline, _)| *on_outline) {
+                    draw_line(x1, y1, x2, y2, 3.0, WHITE);
+                }
+            }
+
+            // size x crowns, on the top left tile of the property
+            let crowns: u32 = property.iter().map(|&(x, y)| grid.tile_map()[x][y].crowns() as u32).sum();
+            let &(x, y) = property.iter().min_by_key(|&&(x, y)| (y, x)).unwrap();
+            let cell = self.layout.kingdom_cell(origin, x, y);
+            let label = format!("{}x{}={}", property.len(), crowns, property.len() as u32 * crowns);
+            let size = (tile * 0.3).max(10.0);
+            let dims = measure_text(&label, None, size as u16, 1.0);
+            draw_rectangle(cell.x + 2.0, cell.y + 2.0, dims.width + 4.0, size, board_gui::LABEL_BACKGROUND);
+            draw_text(&label, cell.x + 4.0, cell.y + 2.0 + dims.offset_y, size, WHITE);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use macroquad::prelude::*;
+use crate::components::game::Game;
+use crate::components::player::Player;
+use crate::components::queendomino::{Building, DRAGON_COST};
+use crate::components::tile::{Tile, Types};
+use crate::components::variant::Variant;
+use crate::gui::panels::Panel;
+use crate::gui::{board_gui, Gui};
+use crate::hit::Target;
+use crate::layout::{self, Rect};
+use crate::tile_art;
+
+// Queendomino at the table. Every kingdom shows its coins, knights and towers along the bottom of its pane,
+// its buildings on the town tiles they stand on, and the queen if she is there. B opens the builders' board:
+// Build on a building lights up the town tiles it can go on, and clicking one of them buys it. Burn pays the
+// dragon to take it off the board instead. Escape puts a chosen building back.
+
+
+/// A Queendomino move made on the builders' board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TownMove {
+    /// Buy the building in the board slot and put it on the town tile at (x, y)
+    Build(usize, u8, u8),
+    /// Have the dragon burn the building in the board slot
+    Burn(usize),
+}
+
+
+impl Gui {
+
+    /// The Queendomino move the active player made this frame, if any. Only asked when they play at this screen.
+    pub(crate) fn town_move(&mut self, game: &Game) -> Option<TownMove> {
+        if is_key_pressed(KeyCode::Escape) {
+            self.building = None;
+        }
+        if !is_mouse_button_pressed(MouseButton::Left) {
+            return None;
+        }
+        let (mouse_x, mouse_y) = mouse_position();
+
+        if self.panel == Some(Panel::Builders) {
+            for slot in 0..game.board().slots().len() {
+                if self.build_button(slot).contains(mouse_x, mouse_y) && game.legal_builds().iter().any(|b| b.0 == slot) {
+                    self.building = Some(slot);
+                    self.panel = None;
+                    return None;
+                }
+                if self.burn_button(slot).contains(mouse_x, mouse_y) && game.legal_burns().contains(&slot) {
+                    return Some(TownMove::Burn(slot));
+                }
+            }
+            return None;
+        }
+
+        let slot = self.building?;
+        match self.clicked() {
+            Some(Target::Town { x, y }) => {
+                self.building = None;
+                Some(TownMove::Build(slot, x, y))
+            }
+            _ => None,
+        }
+    }
+
+    /// Forgets the building chosen on the board, like once the turn is over
+    pub(crate) fn clear_building(&mut self) {
+        self.building = None;
+    }
+
+    /// The coins, knights and towers of a player along the bottom of their pane, and their buildings on the
+    /// town tiles. Queendomino only.
+    pub(crate) fn draw_town(&self, game: &Game, player: &Player) {
+        if game.variant() != Variant::Queendomino {
+            return;
+        }
+        let origin = *self.fetch_offset(player.id());
+        for (building, x, y) in player.buildings() {
+            let cell = self.layout.kingdom_cell((origin[0], origin[1]), *x as usize, *y as usize);
+            tile_art::draw_building(building, cell.x, cell.y, cell.w);
+        }
+
+        let pane = self.layout.panes[player.id() as usize - 1].shrink(3.0 * layout::PANE_MARGIN);
+        let mut line = format!("{} coins  {} knights  {} towers", player.coins(), player.knights(), player.towers());
+        if game.queen() == Some(player.id()) {
+            line.push_str("  - has the queen");
+        }
+        draw_text(&line, pane.x, pane.bottom(), self.layout.text_size, board_gui::YELLOW);
+    }
+
+    /// Lights up the town tiles the building chosen on the board can go on, and makes them clickable
+    pub(crate) fn draw_building_sites(&mut self, game: &Game) {
+        let Some(slot) = self.building else { return };
+        let sites: Vec<(u8, u8)> = game.legal_builds().into_iter().filter(|b| b.0 == slot).map(|(_, x, y)| (x, y)).collect();
+        if sites.is_empty() {
+            self.building = None; // it was bought or burned by someone else, or we can't afford it anymore
+            return;
+        }
+
+        let player = game.active_player();
+        let origin = *self.fetch_offset(player.id());
+        for (x, y) in sites {
+            let cell = self.layout.kingdom_cell((origin[0], origin[1]), x as usize, y as usize);
+            draw_rectangle(cell.x, cell.y, cell.w, cell.h, board_gui::LEGAL_TINT);
+            draw_rectangle_lines(cell.x, cell.y, cell.w, cell.h, board_gui::FOCUS_THICKNESS, board_gui::YELLOW);
+            self.hits.register(cell, Target::Town { x, y });
+        }
+        let pane = self.layout.panes[player.id() as usize - 1].shrink(3.0 * layout::PANE_MARGIN);
+        draw_text("Click a lit up town tile to build there, Escape to put it back", pane.x, pane.y + self.layout.text_size,
+                  self.layout.text_size, WHITE);
+    }
+
+    /// The builders' board panel: every building for sale with its price and what it brings, and who can do what
+    pub(crate) fn draw_builders_board(&self, game: &Game) {
+        let rect = self.layout.panel().shrink(20.0);
+        let size = self.layout.text_size * 1.2;
+        draw_text("Builders' board (B to close)", rect.x, rect.y + size, size * 1.3, WHITE);
+        if game.variant() != Variant::Queendomino {
+            draw_text("Only Queendomino has buildings", rect.x, rect.y + size * 3.0, size, board_gui::ACCENT_COLOR);
+            return;
+        }
+
+        let queen = match game.queen().and_then(|id| game.players().iter().find(|p| p.id() == id)) {
+            Some(player) => format!("The queen is with {}", player.name()),
+            None => "Nobody has the queen yet".to_string(),
+        };
+        let dragon = if game.dragon_called() { "the dragon already burned this round" } else { "the dragon is waiting" };
+        draw_text(&format!("{}, {}", queen, dragon), rect.x, rect.y + size * 2.5, size, board_gui::ACCENT_COLOR);
+
+        let (builds, burns) = (game.legal_builds(), game.legal_burns());
+        let mut can_act = false;
+        for (slot, building) in game.board().slots().iter().enumerate() {
+            let row = self.board_row(slot);
+            let Some(building) = building else {
+                draw_text("(empty until the next round)", row.x + row.h * 1.2, row.y + row.h * 0.7, size, board_gui::ACCENT_COLOR);
+                continue;
+            };
+            tile_art::draw_tile(Tile::new(Types::Town, 0), row.x, row.y, row.h, 1.0);
+            tile_art::draw_building(building, row.x, row.y, row.h);
+            draw_text(&describe(building), row.x + row.h * 1.2, row.y + row.h * 0.7, size, WHITE);
+
+            let can_build = builds.iter().any(|b| b.0 == slot);
+            let can_burn = burns.contains(&slot);
+            can_act |= can_build || can_burn;
+            self.draw_button("Build", self.build_button(slot), can_build);
+            self.draw_button(&format!("Burn ({})", DRAGON_COST), self.burn_button(slot), can_burn);
+        }
+
+        let note = if !self.is_local(game.active_player()) {
+            "Wait for your turn to build or burn"
+        } else if can_act {
+            "Build picks a building to put on a town tile. One building per turn, the queen takes a coin off."
+        } else {
+            "Nothing to build or burn right now: it takes coins, a free town tile, and one building per turn"
+        };
+        draw_text(note, rect.x, self.board_row(game.board().slots().len()).y + size, size, board_gui::ACCENT_COLOR);
+    }
+
+    /// A button of the builders' board, grayed out when it can't be pressed
+    fn draw_button(&self, label: &str, button: Rect, enabled: bool) {
+        let hovered = enabled && button.contains(mouse_position().0, mouse_position().1);
+        let color = if enabled { WHITE } else { board_gui::ACCENT_COLOR };
+        if hovered {
+            draw_rectangle(button.x, button.y, button.w, button.h, board_gui::ACCENT_COLOR);
+        }
+        draw_rectangle_lines(button.x, button.y, button.w, button.h, 2.0, color);
+        let text = measure_text(label, None, self.layout.text_size as u16, 1.0);
+        draw_text(label, button.center().0 - text.width/2.0, button.center().1 + text.offset_y/2.0, self.layout.text_size, color);
+    }
+
+    /// Where a slot of the builders' board is drawn in its panel
+    fn board_row(&self, slot: usize) -> Rect {
+        let rect = self.layout.panel().shrink(20.0);
+        let size = self.layout.text_size * 1.2;
+        Rect::new(rect.x, rect.y + size * (4.0 + slot as f32 * 2.2), rect.w, size * 1.8)
+    }
+
+    fn build_button(&self, slot: usize) -> Rect {
+        let row = self.board_row(slot);
+        Rect::new(row.right() - row.h * 7.0, row.y, row.h * 3.2, row.h)
+    }
+
+    fn burn_button(&self, slot: usize) -> Rect {
+        let row = self.board_row(slot);
+        Rect::new(row.right() - row.h * 3.5, row.y, row.h * 3.5, row.h)
+    }
+}
+
+
+/// What a building costs and what it brings, in a few words
+fn describe(building: &Building) -> String {
+    let mut brings = Vec::new();
+    for (count, what) in [(building.crowns as u32, "crown"), (building.knights as u32, "knight"), (building.towers as u32, "tower"),
+                          (building.points, "point")] {
+        if count > 0 {
+            brings.push(format!("{} {}{}", count, what, if count == 1 { "" } else { "s" }));
+        }
+    }
+    format!("{} coins: {}", building.cost, brings.join(", "))
+}
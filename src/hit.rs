@@ -0,0 +1,62 @@
+use crate::layout::Rect;
+
+// What a click lands on. The gui registers a region for everything clickable as it draws it,
+// and clicks are looked up here, so a click always matches what was on screen that frame.
+
+
+/// Something on screen that does something when clicked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// A domino in the pick draft that can still be picked
+    DraftSlot(usize),
+    /// The domino in the place draft the active player is placing, which can be dragged onto their kingdom
+    PlaceSlot(usize),
+    /// Where the first tile of the held domino would go, in kingdom grid coordinates
+    Socket { x: u8, y: u8 },
+    /// Queendomino: a town tile the building chosen on the builders' board can go on, in kingdom grid coordinates
+    Town { x: u8, y: u8 },
+    Button(Button),
+}
+
+/// On-screen buttons
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Button {
+    /// Turns the domino a quarter turn, like the R key
+    Rotate,
+}
+
+
+/// The clickable regions of one frame
+#[derive(Debug, Clone, Default)]
+pub struct HitMap {
+    regions: Vec<(Rect, Target)>,
+}
+
+impl HitMap {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forgets last frame's regions
+    pub fn clear(&mut self) {
+        self.regions.clear();
+    }
+
+    /// Adds a region. Regions added later are drawn on top, so they win where they overlap.
+    pub fn register(&mut self, rect: Rect, target: Target) {
+        self.regions.push((rect, target));
+    }
+
+    /// What is under the point, if anything
+    pub fn target_at(&self, x: f32, y: f32) -> Option<Target> {
+        self.regions.iter().rev().find(|(rect, _)| rect.contains(x, y)).map(|&(_, target)| target)
+    }
+
+    /// Where the target is on screen, if it was registered
+    pub fn rect_of(&self, target: Target) -> Option<Rect> {
+        self.regions.iter().rev().find(|(_, t)| *t == target).map(|&(rect, _)| rect)
+    }
+
+    pub fn regions(&self) -> &[(Rect, Target)] {&self.regions}
+}
@@ -0,0 +1,243 @@
+// Where everything goes on screen, worked out from the window size every frame so that any window
+// works, from a small laptop window to an ultra-wide monitor. Nothing in here draws or reads input,
+// it is all plain functions of the window size so it can be tested without a window.
+//
+//  +---------+------------------+------------------+
+//  | draft   |                  |                  |
+//  |---------|     kingdom 1    |     kingdom 2    |
+//  | advice  |                  |                  |
+//  |---------+------------------+------------------+
+//  | scores  |                  |                  |
+//  |         |     kingdom 3    |     kingdom 4    |
+//  +---------+------------------+------------------+
+
+
+/// Size of the window when not fullscreen
+pub const DEFAULT_WIDTH: i32 = 1280;
+pub const DEFAULT_HEIGHT: i32 = 800;
+
+/// How much of the window height each sidebar section takes
+const DRAFT_SHARE: f32 = 0.42;
+const ADVICE_SHARE: f32 = 0.14;
+
+/// Sidebar width, as a share of the window width and then clamped
+const SIDEBAR_SHARE: f32 = 1.0 / 3.0;
+const SIDEBAR_MIN: f32 = 240.0;
+const SIDEBAR_MAX: f32 = 520.0;
+
+/// Kingdoms are at most 5 tiles wide, plus a ring of sockets around them and a little margin
+const KINGDOM_SPAN: f32 = 7.5;
+
+/// Biggest a draft tile gets, so the sidebar does not turn into a poster on huge screens
+const MAX_DRAFT_TILE: f32 = 64.0;
+
+/// Room between pane borders and what is drawn inside them
+pub const PANE_MARGIN: f32 = 5.0;
+
+
+/// An axis aligned rectangle in screen pixels, (x, y) being the top left corner
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl Rect {
+
+    pub const fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    pub fn right(&self) -> f32 {self.x + self.w}
+
+    pub fn bottom(&self) -> f32 {self.y + self.h}
+
+    pub fn center(&self) -> (f32, f32) {(self.x + self.w/2.0, self.y + self.h/2.0)}
+
+    /// True if the point is inside, edges included
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.x && x <= self.right() && y >= self.y && y <= self.bottom()
+    }
+
+    /// The same rectangle with `margin` taken off every side
+    pub fn shrink(&self, margin: f32) -> Self {
+        let margin = margin.min(self.w/2.0).min(self.h/2.0);
+        Self::new(self.x + margin, self.y + margin, self.w - 2.0 * margin, self.h - 2.0 * margin)
+    }
+}
+
+
+/// Which column of the draft: the dominoes being picked, or the ones picked last round waiting to be placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DraftColumn {
+    Pick,
+    Place,
+}
+
+
+/// Everything's place for one window size
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    /// The whole left column
+    pub sidebar: Rect,
+    /// Top of the sidebar, the two draft columns
+    pub draft: Rect,
+    /// Middle of the sidebar, what to do now and whose turn it is
+    pub advice: Rect,
+    /// Bottom of the sidebar, one row per player
+    pub scores: Rect,
+    /// One kingdom pane per player, in seat order
+    pub panes: Vec<Rect>,
+    /// Size of a draft tile, a draft domino being two of them side by side
+    pub draft_tile: f32,
+    /// Size of a kingdom tile, the same in every pane
+    pub kingdom_tile: f32,
+    /// Font size for the advice text
+    pub text_size: f32,
+    draft_size: usize,
+}
+
+impl Layout {
+
+    /// The layout of a window of this size, for this many players and dominoes per draft
+    pub fn new(width: f32, height: f32, players: usize, draft_size: usize) -> Self {
+        let (width, height) = (width.max(1.0), height.max(1.0));
+        let players = players.max(1);
+        let draft_size = draft_size.max(1);
+
+        let sidebar_w = (width * SIDEBAR_SHARE).clamp(SIDEBAR_MIN.min(width / 2.0), SIDEBAR_MAX);
+        let sidebar = Rect::new(0.0, 0.0, sidebar_w, height);
+        let draft = Rect::new(0.0, 0.0, sidebar_w, height * DRAFT_SHARE);
+        let advice = Rect::new(0.0, draft.bottom(), sidebar_w, height * ADVICE_SHARE);
+        let scores = Rect::new(0.0, advice.bottom(), sidebar_w, height - advice.bottom());
+
+        // A scroll, a gap, the pick column, a gap, the place column: about 7 tiles across.
+        // Each domino row is 1.2 tiles tall, plus a little room above and below.
+        let draft_tile = (draft.w / 7.0).min(draft.h / (draft_size as f32 * 1.2 + 0.8)).min(MAX_DRAFT_TILE);
+
+        let panes = Self::panes(Rect::new(sidebar_w, 0.0, width - sidebar_w, height), players);
+        let kingdom_tile = panes.iter()
+            .map(|pane| pane.w.min(pane.h) / KINGDOM_SPAN)
+            .fold(f32::INFINITY, f32::min);
+
+        let text_size = (advice.h / 8.0).clamp(10.0, 20.0);
+
+        Self { sidebar, draft, advice, scores, panes, draft_tile, kingdom_tile, text_size, draft_size }
+    }
+
+    /// How many columns and rows of panes a table of this many players gets
+    pub fn pane_grid(players: usize) -> (usize, usize) {
+        let rows = if players <= 2 { 1 } else { 2 };
+        (players.div_ceil(rows), rows)
+    }
+
+    /// Splits the area into a pane per player, filled row by row. A short last row gets wider panes.
+    fn panes(area: Rect, players: usize) -> Vec<Rect> {
+        let (cols, rows) = Self::pane_grid(players);
+        let h = area.h / rows as f32;
+        (0..players)
+            .map(|seat| {
+                let (row, col) = (seat / cols, seat % cols);
+                let in_row = cols.min(players - row * cols);
+                let w = area.w / in_row as f32;
+                Rect::new(area.x + col as f32 * w, area.y + row as f32 * h, w, h)
+            })
+            .collect()
+    }
+
+    /// Where the domino in this slot of the draft goes: two draft tiles wide, one tall
+    pub fn draft_slot(&self, column: DraftColumn, slot: usize) -> Rect {
+        let t = self.draft_tile;
+        let rows_h = self.draft_size as f32 * 1.2 * t - 0.2 * t;
+        let top = self.draft.y + (self.draft.h - rows_h) / 2.0;
+        let x = match column {
+            DraftColumn::Pick => self.draft.x + 2.0 * t,
+            DraftColumn::Place => self.draft.x + 4.4 * t,
+        };
+        Rect::new(x, top + slot as f32 * 1.2 * t, 2.0 * t, t)
+    }
+
+    /// The scroll drawn next to the draft
+    pub fn draft_scroll(&self) -> Rect {
+        let size = 1.5 * self.draft_tile;
+        Rect::new(self.draft.x + 0.2 * self.draft_tile, self.draft.center().1 - size/2.0, size, size)
+    }
+
+    /// The scroll drawn next to the scores
+    pub fn score_scroll(&self) -> Rect {
+        let size = 1.5 * self.draft_tile;
+        Rect::new(self.scores.x + 0.2 * self.draft_tile, self.scores.center().1 - size/2.0, size, size)
+    }
+
+    /// Age of Giants: where quest `idx` lies, the first above the draft scroll and the second below it
+    pub fn quest_tile(&self, idx: usize) -> Rect {
+        let scroll = self.draft_scroll();
+        let (size, gap) = (1.2 * self.draft_tile, 0.2 * self.draft_tile);
+        let x = scroll.center().0 - size/2.0;
+        let step = (idx / 2) as f32 * (size + gap);
+        if idx.is_multiple_of(2) {
+            Rect::new(x, scroll.y - gap - size - step, size, size)
+        } else {
+            Rect::new(x, scroll.bottom() + gap + step, size, size)
+        }
+    }
+
+    /// Age of Giants: the count of giants left in the supply, under the quest below the draft scroll
+    pub fn giant_supply(&self) -> Rect {
+        let scroll = self.draft_scroll();
+        Rect::new(scroll.x, self.quest_tile(1).bottom() + 0.05 * self.draft_tile, scroll.w, 0.45 * self.draft_tile)
+    }
+
+    /// Where the king of the player whose turn it is stands, at the right end of the advice box
+    pub fn advice_king(&self) -> Rect {
+        let size = (self.advice.h * 0.4).min(self.advice.w / 6.0);
+        Rect::new(self.advice.right() - size * 1.5, self.advice.center().1 - size/2.0, size, size)
+    }
+
+    /// One row of the score box: the king of player `seat` (0 based) and their score to the right of it
+    pub fn score_row(&self, seat: usize, players: usize) -> Rect {
+        let players = players.max(1);
+        let row_h = (self.scores.h * 0.9 / players as f32).min(2.0 * self.draft_tile);
+        let top = self.scores.center().1 - row_h * players as f32 / 2.0;
+        let x = self.score_scroll().right() + 0.3 * self.draft_tile;
+        Rect::new(x, top + seat as f32 * row_h, self.scores.right() - x, row_h)
+    }
+
+    /// The rotate button, in the bottom right corner of the pane whose player is placing
+    pub fn rotate_button(&self, pane: Rect) -> Rect {
+        let h = self.text_size * 1.6;
+        let w = h * 4.0;
+        Rect::new(pane.right() - w - 3.0 * PANE_MARGIN, pane.bottom() - h - 3.0 * PANE_MARGIN, w, h)
+    }
+
+    /// The pop-up panel (draft analysis, deck tracker) drawn over the middle of the kingdoms
+    pub fn panel(&self) -> Rect {
+        let area = Rect::new(self.sidebar.right(), 0.0, self.panes.iter().map(Rect::right).fold(0.0, f32::max) - self.sidebar.right(),
+                             self.sidebar.h);
+        let (w, h) = (area.w * 0.8, area.h * 0.8);
+        Rect::new(area.center().0 - w/2.0, area.center().1 - h/2.0, w, h)
+    }
+
+    /// Where cell (0, 0) of a kingdom grid is drawn, so the placed tiles (bounds as Grid::bounds
+    /// gives them, inclusive) sit in the middle of the pane
+    pub fn kingdom_origin(&self, pane: Rect, bounds: (usize, usize, usize, usize)) -> (f32, f32) {
+        let (x_low, x_high, y_low, y_high) = bounds;
+        let (cx, cy) = pane.center();
+        (cx - (x_high + x_low + 1) as f32 / 2.0 * self.kingdom_tile,
+         cy - (y_high + y_low + 1) as f32 / 2.0 * self.kingdom_tile)
+    }
+
+    /// The kingdom cell under a point, given the origin from kingdom_origin. Can be off the grid,
+    /// even negative, when the point is outside the kingdom.
+    pub fn kingdom_cell_at(&self, origin: (f32, f32), x: f32, y: f32) -> (i32, i32) {
+        (((x - origin.0) / self.kingdom_tile).floor() as i32, ((y - origin.1) / self.kingdom_tile).floor() as i32)
+    }
+
+    /// The screen rectangle of a kingdom cell, given the origin from kingdom_origin
+    pub fn kingdom_cell(&self, origin: (f32, f32), x: usize, y: usize) -> Rect {
+        Rect::new(origin.0 + x as f32 * self.kingdom_tile, origin.1 + y as f32 * self.kingdom_tile,
+                  self.kingdom_tile, self.kingdom_tile)
+    }
+}
@@ -2,3 +2,18 @@ pub mod controller;
 pub mod gui;
 pub mod components;
 pub mod assets;
+pub mod tile_art;
+pub mod layout;
+pub mod hit;
+pub mod scoreboard;
+pub mod deck_tracker;
+pub mod event_log;
+pub mod animation;
+pub mod sound;
+pub mod agent;
+pub mod tournament;
+pub mod json;
+pub mod protocol;
+pub mod external_agent;
+pub mod server;
+pub mod client;
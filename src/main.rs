@@ -1,12 +1,26 @@
 
 use macroquad::prelude::Conf;
+use std::path::Path;
+use kingdomino::animation::AnimationSpeed;
+use kingdomino::assets::AssetSource;
+use kingdomino::components::domino_set::DominoSet;
+use kingdomino::components::variant::Variant;
 use kingdomino::controller::Controller;
+use kingdomino::layout::{DEFAULT_HEIGHT, DEFAULT_WIDTH};
 
-/// The configuration of the application window
+/// Where the game's events are written unless `--log` says otherwise
+const DEFAULT_LOG: &str = "kingdomino.log";
+
+const USAGE: &str = "usage: kingdomino [--fullscreen] [--assets DIR] [--log FILE] [--animations normal|fast|off] [--variant NAME] [--players 4|5] [--set SET.json | --connect HOST:PORT [--seat Blue|Green|Red|Yellow] [--name NAME]]";
+
+/// The configuration of the application window. A resizable window unless `--fullscreen` is given.
 fn window_conf() -> Conf {
     Conf {
-        fullscreen: true,
-        window_resizable: false,
+        window_title: "Kingdomino".to_string(),
+        window_width: DEFAULT_WIDTH,
+        window_height: DEFAULT_HEIGHT,
+        fullscreen: std::env::args().any(|arg| arg == "--fullscreen"),
+        window_resizable: true,
         ..Default::default()
     }
 }
@@ -16,7 +30,107 @@ fn window_conf() -> Conf {
 #[macroquad::main(window_conf)]
 async fn main() {
 
-    let mut controller = Controller::new().await;
+    // `--connect host:port` plays one seat of a game hosted by the server binary
+    let (mut address, mut color, mut name, mut set_path, mut asset_dir) = (None, None, None, None, None);
+    let mut log_path = String::from(DEFAULT_LOG);
+    let (mut speed_name, mut variant_name, mut player_count) = (None, None, None);
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--fullscreen" {
+            continue; // already seen by window_conf
+        }
+        match (arg.as_str(), args.next()) {
+            ("--connect", Some(value)) => address = Some(value),
+            ("--seat", Some(value)) => color = Some(value),
+            ("--name", Some(value)) => name = Some(value),
+            ("--set", Some(value)) => set_path = Some(value),
+            ("--assets", Some(value)) => asset_dir = Some(value),
+            ("--log", Some(value)) => log_path = value,
+            ("--animations", Some(value)) => speed_name = Some(value),
+            ("--variant", Some(value)) => variant_name = Some(value),
+            ("--players", Some(value)) => match value.parse::<usize>() {
+                Ok(count) => player_count = Some(count),
+                Err(_) => {
+                    eprintln!("{}", USAGE);
+                    return;
+                }
+            },
+            _ => {
+                eprintln!("{}", USAGE);
+                return;
+            }
+        }
+    }
+
+    // `--animations fast` speeds them up, `off` makes everything snap into place
+    let Some(speed) = speed_name.map_or(Some(AnimationSpeed::default()), |name| AnimationSpeed::parse(&name)) else {
+        eprintln!("{}", USAGE);
+        return;
+    };
+
+    // `--variant queendomino` plays a local game of another variant than Kingdomino. The server picks a remote game's,
+    // and how many play it.
+    let variant_given = variant_name.is_some();
+    let Some(variant) = variant_name.map_or(Some(Variant::Kingdomino), |name| Variant::from_name(&name)) else {
+        eprintln!("{}", USAGE);
+        return;
+    };
+
+    // `--players 5` seats a fifth player, where the variant has room for one (Age of Giants)
+    let players = player_count.unwrap_or(4);
+    if !(4..=variant.max_players()).contains(&players) {
+        eprintln!("{} can't be played by {} players", variant.name(), players);
+        return;
+    }
+
+    // `--set file.json` deals a local game from a domino set of your own
+    let set = match set_path.map(|path| DominoSet::load(Path::new(&path)).map_err(|e| (path, e))).transpose() {
+        Ok(set) => set,
+        Err((path, e)) => {
+            eprintln!("could not load the domino set {}: {}", path, e);
+            return;
+        }
+    };
+
+    // `--assets dir` draws with the pictures in dir (laid out like res/img) wherever it has one
+    let source = AssetSource::new(asset_dir.as_deref().map(Path::new));
+
+    let mut controller = match (address, set) {
+        (Some(_), Some(_)) => {
+            eprintln!("a network game is dealt by its server, --set only deals local games");
+            return;
+        }
+        (Some(_), None) if variant_given || player_count.is_some() => {
+            eprintln!("{}", USAGE);
+            return;
+        }
+        (None, Some(set)) => match Controller::with_set(&set, variant, players, &source).await {
+            Ok(controller) => controller,
+            Err(e) => {
+                eprintln!("could not play with {}: {}", set.name(), e);
+                return;
+            }
+        },
+        (None, None) => match Controller::with_set(DominoSet::builtin(variant), variant, players, &source).await {
+            Ok(controller) => controller,
+            Err(e) => {
+                eprintln!("could not start the game: {}", e);
+                return;
+            }
+        },
+        (Some(address), None) => match Controller::connect(&address, color.as_deref(), name.as_deref(), &source).await {
+            Ok(controller) => controller,
+            Err(e) => {
+                eprintln!("could not connect to {}: {}", address, e);
+                return;
+            }
+        },
+    };
+    // the game plays on without a log file if it can't be written
+    if let Err(e) = controller.log_to(Path::new(&log_path)) {
+        eprintln!("could not write the log file {}: {}", log_path, e);
+    }
+    controller.set_animation_speed(speed);
     controller.start().await;
 
 }
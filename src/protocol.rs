@@ -0,0 +1,394 @@
+use crate::components::domino::{Domino, CASTLE_ID};
+use crate::components::draft::{Draft, MAX_DRAFT_SIZE};
+use crate::components::game::Game;
+use crate::components::giants::Mark;
+use crate::components::grid::{BuildRotation, GRID_SIZE};
+use crate::components::grid_domino::GridDomino;
+use crate::components::player::Player;
+use crate::components::queendomino::{Building, BOARD_SIZE, BUILDINGS};
+use crate::components::tile::{Tile, Types};
+use crate::controller::Phase;
+use crate::json::Json;
+
+// How the game state looks on the wire. Shared by everything that talks to the engine in JSON.
+//
+// Grids are 9 rows of 9 cells, indexed [x][y] the same way GridDomino is, with the castle at (4, 4).
+// An empty cell is null, anything else is {"terrain": "forest", "crowns": 1}.
+// A domino is {"id": 19, "tiles": [anchor tile, second tile], "mark": null}, the mark being "giant" or "footprint"
+//   on the Age of Giants dominoes that carry one.
+// A placement is {"x": 3, "y": 4, "rotation": "up"} with the anchor tile on (x, y) and the second tile on
+//   up: (x + 1, y)   down: (x - 1, y)   left: (x, y + 1)   right: (x, y - 1)
+// A draft is a list of {"slot": 0, "domino": domino, "king": seat or null}, or null when there is none.
+// A player is {"seat": 1, "color": "Blue", "score": 12, "grid": grid, "dominoes": [placement with "id" and "tiles", ...],
+//   "placing": domino or null, "coins": 7, "knights": 1, "towers": 0, "buildings": [building with "x" and "y", ...],
+//   "giants": [{"x": 3, "y": 4} or null, ...]}. The score is the one the variant counts.
+// A building is {"id": 12, "cost": 5, "crowns": 0, "knights": 0, "towers": 2, "points": 2}.
+// The deck is the list of dominoes left to draft, by id. The order they come out in is only decided as they are dealt.
+// The builders' board is a list of 6 slots, each a building or null once it was bought or burned.
+// A build is {"slot": 2, "x": 3, "y": 5}, the board slot and the town tile the building goes on.
+
+pub(crate) fn encode_tile(tile: &Tile) -> Json {
+    if tile.get_type() == Types::Null {
+        return Json::Null;
+    }
+    Json::object([
+        ("terrain", Json::from(format!("{:?}", tile.get_type()).to_lowercase())),
+        ("crowns", Json::from(tile.crowns())),
+    ])
+}
+
+pub(crate) fn encode_domino(domino: &Domino) -> Json {
+    Json::object([
+        ("id", Json::from(domino.id())),
+        ("tiles", Json::from(vec![encode_tile(&domino.get_tile(1)), encode_tile(&domino.get_tile(2))])),
+        ("mark", Json::from(domino.mark().map(|mark| mark.name()))),
+    ])
+}
+
+pub(crate) fn encode_draft(draft: &Draft) -> Json {
+    if draft.is_null() {
+        return Json::Null;
+    }
+    Json::Array((0..draft.len()).map(|slot| Json::object([
+        ("slot", Json::from(slot)),
+        ("domino", encode_domino(&draft.domino(slot))),
+        ("king", Json::from(draft.player_on(slot))),
+    ])).collect())
+}
+
+pub(crate) fn encode_building(building: &Building) -> Json {
+    Json::object([
+        ("id", Json::from(building.id)),
+        ("cost", Json::from(building.cost)),
+        ("crowns", Json::from(building.crowns)),
+        ("knights", Json::from(building.knights)),
+        ("towers", Json::from(building.towers)),
+        ("points", Json::from(building.points)),
+    ])
+}
+
+/// Reads a building back. Every building is one of BUILDINGS, so the id says which.
+pub(crate) fn decode_building(json: &Json) -> Option<Building> {
+    let id = json.get("id")?.as_u64()?;
+    BUILDINGS.iter().find(|b| b.id as u64 == id).copied()
+}
+
+/// The builders' board, slot by slot
+pub(crate) fn encode_board(game: &Game) -> Json {
+    Json::Array(game.board().slots().iter().map(|slot| slot.as_ref().map_or(Json::Null, encode_building)).collect())
+}
+
+/// A player as everyone at the table can see them. "dominoes" lists what they placed, oldest first.
+pub(crate) fn encode_player(game: &Game, player: &Player) -> Json {
+    let grid = player.grid().tile_map().iter()
+        .map(|row| Json::Array(row.iter().map(encode_tile).collect()))
+        .collect();
+    let dominoes = player.grid().domino_map().iter()
+        .filter(|gd| *gd.domino_id() != CASTLE_ID as usize)
+        .map(|gd| {
+            // The tiles are read back off the grid, so the other end does not need to know the set
+            let (x, y) = (*gd.x() as usize, *gd.y() as usize);
+            let (dx, dy) = BuildRotation::from_radians(*gd.rotation()).second_offset();
+            let tiles = [player.grid().tile_map()[x][y], player.grid().tile_map()[(x as isize + dx) as usize][(y as isize + dy) as usize]];
+
+            let mut json = encode_placement(gd);
+            if let Json::Object(pairs) = &mut json {
+                pairs.insert(0, ("id".to_string(), Json::from(*gd.domino_id())));
+                pairs.insert(1, ("tiles".to_string(), Json::Array(tiles.iter().map(encode_tile).collect())));
+            }
+            json
+        })
+        .collect();
+    Json::object([
+        ("seat", Json::from(player.id())),
+        ("color", Json::from(player.name().as_str())),
+        ("score", Json::from(game.scores()[(player.id() - 1) as usize])),
+        ("grid", Json::Array(grid)),
+        ("dominoes", Json::Array(dominoes)),
+        ("placing", if player.is_not_placing() { Json::Null } else { encode_domino(&player.placing()) }),
+        ("coins", Json::from(player.coins())),
+        ("knights", Json::from(player.knights())),
+        ("towers", Json::from(player.towers())),
+        ("buildings", Json::Array(player.buildings().iter().map(|(building, x, y)| {
+            let mut json = encode_building(building);
+            if let Json::Object(pairs) = &mut json {
+                pairs.push(("x".to_string(), Json::from(*x)));
+                pairs.push(("y".to_string(), Json::from(*y)));
+            }
+            json
+        }).collect())),
+        ("giants", Json::Array(player.giants().iter().map(|giant| match giant {
+            Some((x, y)) => Json::object([("x", Json::from(*x)), ("y", Json::from(*y))]),
+            None => Json::Null,
+        }).collect())),
+    ])
+}
+
+pub(crate) fn decode_tile(json: &Json) -> Option<Tile> {
+    if *json == Json::Null {
+        return Some(Tile::default());
+    }
+    let tile_type = match json.get("terrain")?.as_str()? {
+        "grass" => Types::Grass,
+        "wheat" => Types::Wheat,
+        "forest" => Types::Forest,
+        "swamp" => Types::Swamp,
+        "water" => Types::Water,
+        "mine" => Types::Mine,
+        "town" => Types::Town,
+        "castle" => Types::Castle,
+        _ => return None,
+    };
+    let crowns = json.get("crowns")?.as_u64()?;
+    Some(Tile::new(tile_type, u8::try_from(crowns).ok()?))
+}
+
+pub(crate) fn decode_domino(json: &Json) -> Option<Domino> {
+    let id = u8::try_from(json.get("id")?.as_u64()?).ok()?;
+    let domino = match json.get("tiles")? {
+        Json::Array(tiles) if tiles.len() == 2 => Domino::new(id, decode_tile(&tiles[0])?, decode_tile(&tiles[1])?),
+        _ => return None,
+    };
+    // placed dominoes come without a mark, their giant or footprint having done its work
+    match json.get("mark") {
+        None | Some(Json::Null) => Some(domino),
+        Some(mark) => Some(domino.with_mark(Mark::from_name(mark.as_str()?)?)),
+    }
+}
+
+pub(crate) fn decode_draft(json: &Json) -> Option<Draft> {
+    let slots = match json {
+        Json::Null => return Some(Draft::null()),
+        Json::Array(slots) if (1..=MAX_DRAFT_SIZE).contains(&slots.len()) => slots,
+        _ => return None,
+    };
+    let mut dominoes = Vec::new();
+    let mut kings = Vec::new();
+    for entry in slots {
+        dominoes.push(decode_domino(entry.get("domino")?)?);
+        kings.push(match entry.get("king")? {
+            Json::Null => None,
+            king => Some(u8::try_from(king.as_u64()?).ok()?),
+        });
+    }
+    Some(Draft::restore(&dominoes, &kings))
+}
+
+/// Rebuilds a player from what encode_player wrote, replaying their dominoes in order
+pub(crate) fn decode_player(json: &Json) -> Option<Player> {
+    let seat = u8::try_from(json.get("seat")?.as_u64()?).ok()?;
+    let color = json.get("color")?.as_str()?;
+    let placing = match json.get("placing")? {
+        Json::Null => Domino::null(),
+        domino => decode_domino(domino)?,
+    };
+    let mut placed = Vec::new();
+    match json.get("dominoes")? {
+        Json::Array(dominoes) => {
+            for entry in dominoes {
+                let domino = decode_domino(entry)?;
+                placed.push((decode_placement(entry, domino.id())?, domino));
+            }
+        }
+        _ => return None,
+    }
+    let mut player = Player::restore(seat, color, &placed, placing)?;
+    let on_grid = |x: u8, y: u8| Some((x, y)).filter(|&(x, y)| (x as usize) < GRID_SIZE && (y as usize) < GRID_SIZE);
+
+    let mut buildings = Vec::new();
+    match json.get("buildings")? {
+        Json::Array(entries) => {
+            for entry in entries {
+                let (x, y) = on_grid(small_of(entry, "x")?, small_of(entry, "y")?)?;
+                buildings.push((decode_building(entry)?, x, y));
+            }
+        }
+        _ => return None,
+    }
+    let coins = u32::try_from(json.get("coins")?.as_u64()?).ok()?;
+    player.restore_town(coins, small_of(json, "knights")?, small_of(json, "towers")?, buildings);
+
+    let mut giants = Vec::new();
+    match json.get("giants")? {
+        Json::Array(entries) => {
+            for entry in entries {
+                giants.push(match entry {
+                    Json::Null => None,
+                    spot => Some(on_grid(small_of(spot, "x")?, small_of(spot, "y")?)?),
+                });
+            }
+        }
+        _ => return None,
+    }
+    player.restore_giants(giants);
+    Some(player)
+}
+
+/// Reads a number that fits a u8, like a grid coordinate
+fn small_of(json: &Json, key: &str) -> Option<u8> {
+    u8::try_from(json.get(key)?.as_u64()?).ok()
+}
+
+/// Reads the builders' board back, slot by slot
+pub(crate) fn decode_board(json: &Json) -> Option<[Option<Building>; BOARD_SIZE]> {
+    let Json::Array(slots) = json else { return None };
+    if slots.len() != BOARD_SIZE {
+        return None;
+    }
+    let mut board = [None; BOARD_SIZE];
+    for (slot, entry) in slots.iter().enumerate() {
+        board[slot] = match entry {
+            Json::Null => None,
+            building => Some(decode_building(building)?),
+        };
+    }
+    Some(board)
+}
+
+pub(crate) fn rotation_name(rotation: BuildRotation) -> &'static str {
+    match rotation {
+        BuildRotation::UP => "up",
+        BuildRotation::DOWN => "down",
+        BuildRotation::LEFT => "left",
+        BuildRotation::RIGHT => "right",
+    }
+}
+
+pub(crate) fn encode_placement(grid_domino: &GridDomino) -> Json {
+    Json::object([
+        ("x", Json::from(*grid_domino.x())),
+        ("y", Json::from(*grid_domino.y())),
+        ("rotation", Json::from(rotation_name(BuildRotation::from_radians(*grid_domino.rotation())))),
+    ])
+}
+
+/// Reads a placement back. The domino id is not on the wire, so the caller says which domino it is.
+pub(crate) fn decode_placement(json: &Json, domino_id: u8) -> Option<GridDomino> {
+    let x = json.get("x")?.as_u64()?;
+    let y = json.get("y")?.as_u64()?;
+    let rotation = match json.get("rotation")?.as_str()? {
+        "up" => BuildRotation::UP,
+        "down" => BuildRotation::DOWN,
+        "left" => BuildRotation::LEFT,
+        "right" => BuildRotation::RIGHT,
+        _ => return None,
+    };
+    if x > u8::MAX as u64 || y > u8::MAX as u64 {
+        return None;
+    }
+    Some(GridDomino::new(x as u8, y as u8, domino_id as usize, rotation.radians()))
+}
+
+fn phase_name(phase: Option<Phase>) -> Json {
+    match phase {
+        Some(Phase::Picking) => Json::from("pick"),
+        Some(Phase::Placing) => Json::from("place"),
+        None => Json::Null,
+    }
+}
+
+pub(crate) fn encode_build(slot: usize, x: u8, y: u8) -> Json {
+    Json::object([
+        ("slot", Json::from(slot)),
+        ("x", Json::from(x)),
+        ("y", Json::from(y)),
+    ])
+}
+
+pub(crate) fn decode_build(json: &Json) -> Option<(usize, u8, u8)> {
+    let slot = json.get("slot")?.as_u64()?;
+    let x = u8::try_from(json.get("x")?.as_u64()?).ok()?;
+    let y = u8::try_from(json.get("y")?.as_u64()?).ok()?;
+    Some((slot as usize, x, y))
+}
+
+/// Queendomino: the buildings the active player could build right now
+pub(crate) fn encode_builds(game: &Game) -> Json {
+    Json::Array(game.legal_builds().into_iter().map(|(slot, x, y)| encode_build(slot, x, y)).collect())
+}
+
+/// Queendomino: the board slots the active player could have the dragon burn right now
+pub(crate) fn encode_burns(game: &Game) -> Json {
+    Json::Array(game.legal_burns().into_iter().map(Json::from).collect())
+}
+
+/// The legal moves of the active player: slots while picking, placements while placing
+fn encode_legal(game: &Game) -> Json {
+    match game.phase() {
+        Some(Phase::Picking) => Json::Array(game.legal_picks().into_iter().map(Json::from).collect()),
+        Some(Phase::Placing) => Json::Array(game.legal_placements().iter().map(encode_placement).collect()),
+        None => Json::Array(Vec::new()),
+    }
+}
+
+/// The whole table, as everyone watching it sees it. Players are listed by seat, turn_order lists
+/// seats in the order they move this round. phase and active_seat are null once the game is over.
+/// The board, the queen and the dragon only matter in Queendomino ("built" says the active king already
+/// built this turn), the giant supply and the quests only in Age of Giants.
+pub(crate) fn encode_state(game: &Game) -> Json {
+    let mut players: Vec<&Player> = game.players().iter().collect();
+    players.sort_by_key(|p| p.id());
+    let active_seat = if game.phase().is_some() { Some(game.active_player().id()) } else { None };
+
+    Json::object([
+        ("type", Json::from("state")),
+        ("phase", phase_name(game.phase())),
+        ("active_seat", Json::from(active_seat)),
+        ("turn_order", Json::Array(game.players().iter().map(|p| Json::from(p.id())).collect())),
+        ("players", Json::Array(players.into_iter().map(|player| encode_player(game, player)).collect())),
+        ("pick_draft", encode_draft(game.pick_draft())),
+        ("place_draft", encode_draft(game.place_draft())),
+        ("deck_remaining", Json::from(game.deck_len())),
+        ("deck", encode_deck(game)),
+        ("variant", Json::from(game.variant().name())),
+        ("board", encode_board(game)),
+        ("queen", Json::from(game.queen())),
+        ("built", Json::from(game.built())),
+        ("dragon_called", Json::from(game.dragon_called())),
+        ("giants_left", Json::from(game.giants_left())),
+        ("quests", Json::Array(game.quests().iter().map(|quest| Json::from(quest.name())).collect())),
+        ("legal", encode_legal(game)),
+    ])
+}
+
+/// The dominoes left to draft, by id so the list says nothing about what comes next
+fn encode_deck(game: &Game) -> Json {
+    let mut deck = game.deck().dominoes().to_vec();
+    deck.sort_by_key(Domino::id);
+    Json::Array(deck.iter().map(encode_domino).collect())
+}
+
+pub(crate) fn decode_deck(json: &Json) -> Option<Vec<Domino>> {
+    let Json::Array(dominoes) = json else { return None };
+    dominoes.iter().map(decode_domino).collect()
+}
+
+/// Everything the player in the given seat can see, plus the legal moves for their turn
+pub(crate) fn encode_observation(game: &Game, seat: u8) -> Json {
+    let you = game.players().iter().find(|p| p.id() == seat).expect("no player in that seat");
+    let opponents = game.players().iter().filter(|p| p.id() != seat).map(|player| encode_player(game, player)).collect();
+
+    let (kind, placing) = match game.phase() {
+        Some(Phase::Picking) => ("pick", Json::Null),
+        Some(Phase::Placing) => ("place", encode_domino(&you.placing())),
+        None => ("game_over", Json::Null),
+    };
+
+    Json::object([
+        ("type", Json::from(kind)),
+        ("seat", Json::from(seat)),
+        ("you", encode_player(game, you)),
+        ("opponents", Json::Array(opponents)),
+        ("pick_draft", encode_draft(game.pick_draft())),
+        ("place_draft", encode_draft(game.place_draft())),
+        ("deck_remaining", Json::from(game.deck_len())),
+        ("placing", placing),
+        ("variant", Json::from(game.variant().name())),
+        ("quests", Json::Array(game.quests().iter().map(|quest| Json::from(quest.name())).collect())),
+        ("giants_left", Json::from(game.giants_left())),
+        ("board", encode_board(game)),
+        ("queen", Json::from(game.queen())),
+        ("dragon_called", Json::from(game.dragon_called())),
+        ("legal", encode_legal(game)),
+    ])
+}
@@ -0,0 +1,102 @@
+use crate::components::game::Game;
+
+// What the score box shows: every player's score with the numbers that break ties, their rank, and the
+// score counting up to its new value after a placement instead of jumping there.
+
+
+/// How long a score takes to count up to a new value, in seconds
+pub const TICK_SECONDS: f64 = 0.8;
+
+
+/// One row of the score box
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreLine {
+    /// Player id, starting at 1
+    pub player: u8,
+    pub score: u32,
+    /// Crowns in the kingdom, the second tie breaker
+    pub crowns: u32,
+    /// Tiles in the biggest property, the first tie breaker
+    pub largest_property: usize,
+    /// 1 for the leader. Players tied on score, largest property and crowns share a rank.
+    pub rank: usize,
+}
+
+impl ScoreLine {
+
+    pub fn new(player: u8, score: u32, crowns: u32, largest_property: usize) -> Self {
+        Self { player, score, crowns, largest_property, rank: 1 }
+    }
+
+    fn standing(&self) -> (u32, usize, u32) {(self.score, self.largest_property, self.crowns)}
+}
+
+
+/// Ranks the lines: the highest score first, then the largest property, then the most crowns
+pub fn rank(lines: &mut [ScoreLine]) {
+    let standings: Vec<_> = lines.iter().map(ScoreLine::standing).collect();
+    for line in lines.iter_mut() {
+        line.rank = 1 + standings.iter().filter(|&&other| other > line.standing()).count();
+    }
+}
+
+
+/// The ranked score line of every player, by player id
+pub(crate) fn score_lines(game: &Game) -> Vec<ScoreLine> {
+    let scores = game.scores();
+    let mut lines: Vec<ScoreLine> = game.players().iter()
+        .map(|player| {
+            let grid = player.grid();
+            ScoreLine::new(player.id(), scores[player.id() as usize - 1], grid.crowns(), grid.largest_property())
+        })
+        .collect();
+    lines.sort_by_key(|line| line.player);
+    rank(&mut lines);
+    lines
+}
+
+
+/// A score shown on screen, counting from its old value to its new one when it changes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreTicker {
+    from: u32,
+    to: u32,
+    changed_at: f64,
+    duration: f64,
+}
+
+impl Default for ScoreTicker {
+    fn default() -> Self {
+        Self::with_duration(TICK_SECONDS)
+    }
+}
+
+impl ScoreTicker {
+
+    /// A ticker at 0 that takes `duration` seconds to count to each new score. 0 jumps straight there.
+    pub fn with_duration(duration: f64) -> Self {
+        Self { from: 0, to: 0, changed_at: 0.0, duration }
+    }
+
+    /// Sets the score at time `now` (in seconds). Does nothing if it has not changed.
+    pub fn set(&mut self, score: u32, now: f64) {
+        if score != self.to {
+            self.from = self.shown(now).round() as u32;
+            self.to = score;
+            self.changed_at = now;
+        }
+    }
+
+    /// The value to show at time `now`
+    pub fn shown(&self, now: f64) -> f32 {
+        let progress = self.progress(now);
+        self.from as f32 + (self.to as f32 - self.from as f32) * progress
+    }
+
+    /// How far along (0 to 1) the count to the new score is. 1 once it is done.
+    pub fn progress(&self, now: f64) -> f32 {
+        crate::animation::progress(self.changed_at, now, self.duration)
+    }
+
+    pub fn score(&self) -> u32 {self.to}
+}
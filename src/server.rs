@@ -0,0 +1,558 @@
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::thread;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tokio::task::JoinHandle;
+use crate::agent::{builtin_agent, Agent};
+use crate::components::game::Game;
+use crate::components::grid_domino::GridDomino;
+use crate::components::variant::Variant;
+use crate::controller::Phase;
+use crate::json::Json;
+use crate::protocol::{decode_build, decode_placement, encode_build, encode_placement, encode_state};
+
+// Hosts one game over TCP, of any variant and for as many players as it has room for. The server owns the only real Game, checks every move against it,
+// and tells everyone connected what happened. One JSON object per line, both ways, and a client whose
+// line runs past MAX_LINE_BYTES is hung up on.
+//
+// Client to server:
+//   {"type": "join"}                              take the first free seat, or pick one with "seat": 3
+//                                                 or "color": "Red", and optionally a "name"
+//   {"type": "name", "name": "Ann"}
+//   {"type": "ready", "ready": true}
+//   {"type": "fill", "bot": "greedy"}             host only: put a bot in every open seat
+//   {"type": "pick", "slot": 2}
+//   {"type": "place", "placement": placement}     a null placement discards the domino
+//   {"type": "build", "slot": 2, "x": 3, "y": 5}  Queendomino, at any point of your turn
+//   {"type": "burn", "slot": 4}                   Queendomino, the dragon burns what is in the slot
+// Server to client:
+//   {"type": "welcome", "seat": 3}                answer to a join
+//   {"type": "error", "message": "..."}           the message was rejected and nothing changed
+//   {"type": "move", "seat": 3, "pick": 2}        sent to everyone when a move is accepted,
+//   {"type": "move", "seat": 3, "place": placement or null}      right before the new state
+//   {"type": "move", "seat": 3, "build": build} / {"type": "move", "seat": 3, "burn": 4}
+//   {"type": "state", ..., "started": false, "host_seat": 1, "seats": [seat, ...]}
+//                                                 protocol::encode_state plus the lobby.
+//                                                 Sent on connect and to everyone after every change.
+// A lobby seat is {"seat": 1, "color": "Blue", "name": "Ann" or null, "kind": "player"/"bot"/"open",
+//   "ready": true, "away": false}. The first client to sit down is the host.
+// Play starts once every seat is filled and ready, and ends with a state whose phase is null.
+// A player who drops out mid-game leaves a bot in their seat ("away") and can join it again.
+// Placements and tiles look the way protocol.rs describes them.
+
+
+/// Port the server listens on unless told otherwise
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// The longest line a client may send, in bytes. Real messages are well under a kilobyte; a client that
+/// goes past this is cut off rather than left to fill the server's memory.
+pub const MAX_LINE_BYTES: usize = 16 * 1024;
+
+/// The bot that fills in for a player who dropped out, and fills seats unless the host asks for another
+const DEFAULT_BOT: &str = "greedy";
+
+
+enum Event {
+    Connected(usize, UnboundedSender<String>, JoinHandle<()>),
+    Line(usize, String),
+    Disconnected(usize),
+}
+
+struct Client {
+    outbox: UnboundedSender<String>,
+    writer: JoinHandle<()>,
+    seat: Option<u8>,
+}
+
+impl Client {
+    fn send(&self, message: &Json) {
+        // If this fails the client is already gone, and its Disconnected event is on the way
+        let _ = self.outbox.send(message.to_string());
+    }
+}
+
+
+/// Runs a game of the variant for this many players on the listener until it is over, and returns
+/// the final scores by seat
+pub async fn serve(listener: TcpListener, variant: Variant, players: usize) -> io::Result<Vec<u32>> {
+    let (events, mut inbox) = mpsc::unbounded_channel();
+    let acceptor = tokio::spawn(accept_clients(listener, events));
+
+    let mut host = Host::new(variant, players);
+    while !host.game.is_over() {
+        match inbox.recv().await {
+            Some(event) => host.handle(event),
+            None => return Err(io::Error::other("stopped accepting connections")),
+        }
+    }
+    acceptor.abort();
+
+    // Let every client receive the final state before hanging up
+    for (_, client) in host.clients.drain() {
+        drop(client.outbox);
+        let _ = client.writer.await;
+    }
+    Ok(host.game.scores())
+}
+
+
+/// Hosts a game on a free localhost port, on a thread of its own. For tests and games on one machine.
+pub fn spawn_local(variant: Variant, players: usize) -> io::Result<(SocketAddr, thread::JoinHandle<io::Result<Vec<u32>>>)> {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    let listener = runtime.block_on(TcpListener::bind("127.0.0.1:0"))?;
+    let address = listener.local_addr()?;
+    let host = thread::spawn(move || runtime.block_on(serve(listener, variant, players)));
+    Ok((address, host))
+}
+
+
+async fn accept_clients(listener: TcpListener, events: UnboundedSender<Event>) {
+    let mut next_id = 0;
+    loop {
+        match listener.accept().await {
+            Ok((stream, address)) => {
+                next_id += 1;
+                println!("client {} connected from {}", next_id, address);
+                // Messages are small and someone is waiting on each one
+                let _ = stream.set_nodelay(true);
+                tokio::spawn(handle_client(next_id, stream, events.clone()));
+            }
+            Err(e) => eprintln!("failed to accept a connection: {}", e),
+        }
+    }
+}
+
+
+/// Forwards every line the client sends to the host, and writes whatever the host queues for it.
+/// Hangs up on a client whose line runs past MAX_LINE_BYTES.
+async fn handle_client(id: usize, stream: TcpStream, events: UnboundedSender<Event>) {
+    let (reader, mut writer) = stream.into_split();
+
+    let (outbox, mut queue) = mpsc::unbounded_channel::<String>();
+    let writer_task = tokio::spawn(async move {
+        while let Some(mut line) = queue.recv().await {
+            line.push('\n');
+            if writer.write_all(line.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+        let _ = writer.shutdown().await;
+    });
+
+    if events.send(Event::Connected(id, outbox, writer_task)).is_err() {
+        return;
+    }
+
+    let mut reader = BufReader::new(reader);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        // read one byte past the limit, so a line of exactly MAX_LINE_BYTES can still end in its newline
+        match (&mut reader).take(MAX_LINE_BYTES as u64 + 1).read_line(&mut line).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) if !line.ends_with('\n') && line.len() > MAX_LINE_BYTES => {
+                eprintln!("client {} sent a line longer than {} bytes, hanging up", id, MAX_LINE_BYTES);
+                break;
+            }
+            Ok(_) => {}
+        }
+        let message = line.trim_end_matches(['\n', '\r']).to_string();
+        if events.send(Event::Line(id, message)).is_err() {
+            return;
+        }
+    }
+    let _ = events.send(Event::Disconnected(id));
+}
+
+
+/// Who sits in a seat. A seat with neither a client nor a bot is open.
+struct Seat {
+    client: Option<usize>,
+    name: Option<String>,
+    ready: bool,
+    bot: Option<Box<dyn Agent + Send>>,
+    away: bool, // the player dropped mid-game and a bot holds the seat until they come back
+}
+
+impl Seat {
+    fn open() -> Self {
+        Self { client: None, name: None, ready: false, bot: None, away: false }
+    }
+
+    fn is_open(&self) -> bool {
+        self.client.is_none() && self.bot.is_none()
+    }
+
+    /// A bot counts as ready, a player once they said so
+    fn is_ready(&self) -> bool {
+        self.bot.is_some() || (self.client.is_some() && self.ready)
+    }
+}
+
+
+/// The authoritative side of the table
+struct Host {
+    game: Game,
+    clients: HashMap<usize, Client>,
+    seats: Vec<Seat>, // seats[i] is the player with id i + 1
+    host: Option<usize>, // the client allowed to fill the table with bots
+    started: bool,
+}
+
+impl Host {
+
+    /// Panics if the variant can't be played by this many players, like Game::with_players
+    fn new(variant: Variant, players: usize) -> Self {
+        Self {
+            game: Game::with_players(variant, players),
+            clients: HashMap::new(),
+            seats: (0..players).map(|_| Seat::open()).collect(),
+            host: None,
+            started: false,
+        }
+    }
+
+    fn handle(&mut self, event: Event) {
+        match event {
+            Event::Connected(id, outbox, writer) => {
+                let client = Client { outbox, writer, seat: None };
+                client.send(&self.state());
+                self.clients.insert(id, client);
+            }
+            Event::Line(id, line) => {
+                if let Err(message) = self.handle_line(id, &line) {
+                    self.send_to(id, &Json::object([
+                        ("type", Json::from("error")),
+                        ("message", Json::from(message)),
+                    ]));
+                }
+            }
+            Event::Disconnected(id) => {
+                if let Some(client) = self.clients.remove(&id) {
+                    println!("client {} disconnected", id);
+                    if let Some(seat) = client.seat {
+                        self.leave(id, seat);
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_line(&mut self, id: usize, line: &str) -> Result<(), String> {
+        let message = Json::parse(line).map_err(|e| format!("could not read message: {}", e))?;
+        match message.get("type").and_then(Json::as_str) {
+            Some("join") => self.join(id, &message),
+            Some("name") => {
+                let seat = self.clients[&id].seat.ok_or("join a seat first")?;
+                let name = read_name(message.get("name")).ok_or("a name needs 1 to 20 characters")?;
+                self.seats[(seat - 1) as usize].name = Some(name);
+                self.broadcast(&self.state());
+                Ok(())
+            }
+            Some("ready") => {
+                let seat = self.clients[&id].seat.ok_or("join a seat first")?;
+                if self.started {
+                    return Err("the game has already started".to_string());
+                }
+                self.seats[(seat - 1) as usize].ready = message.get("ready").and_then(Json::as_bool).unwrap_or(true);
+                self.broadcast(&self.state());
+                self.try_start();
+                Ok(())
+            }
+            Some("fill") => {
+                if self.host != Some(id) {
+                    return Err("only the host can fill seats with bots".to_string());
+                }
+                if self.started {
+                    return Err("the game has already started".to_string());
+                }
+                let kind = message.get("bot").and_then(Json::as_str).unwrap_or(DEFAULT_BOT);
+                builtin_agent(kind).ok_or(format!("there is no '{}' bot", kind))?;
+                for seat in self.seats.iter_mut().filter(|seat| seat.is_open()) {
+                    seat.bot = builtin_agent(kind);
+                    seat.name = Some(format!("{} bot", kind));
+                }
+                println!("the host filled the open seats with {} bots", kind);
+                self.broadcast(&self.state());
+                self.try_start();
+                Ok(())
+            }
+            Some("pick") => {
+                let seat = self.seat_to_move(id)?;
+                let slot = message.get("slot").and_then(Json::as_u64).ok_or("a pick needs a slot")?;
+                self.game.pick(slot as usize).map_err(|e| e.to_string())?;
+                self.announce_pick(seat, slot as usize);
+                self.play_bots();
+                Ok(())
+            }
+            Some("place") => {
+                let seat = self.seat_to_move(id)?;
+                let grid_domino = match message.get("placement") {
+                    None => return Err("a placement needs a placement (null to discard)".to_string()),
+                    Some(Json::Null) => None,
+                    Some(placement) => {
+                        let domino_id = self.game.active_player().placing().id();
+                        Some(decode_placement(placement, domino_id).ok_or("could not read the placement")?)
+                    }
+                };
+                self.game.place(grid_domino).map_err(|e| e.to_string())?;
+                self.announce_place(seat, grid_domino);
+                self.play_bots();
+                Ok(())
+            }
+            Some("build") => {
+                let seat = self.seat_to_move(id)?;
+                let (slot, x, y) = decode_build(&message).ok_or("a build needs a slot, x and y")?;
+                self.game.build(slot, x, y).map_err(|e| e.to_string())?;
+                self.announce_build(seat, slot, x, y);
+                Ok(())
+            }
+            Some("burn") => {
+                let seat = self.seat_to_move(id)?;
+                let slot = message.get("slot").and_then(Json::as_u64).ok_or("a burn needs a slot")?;
+                self.game.burn(slot as usize).map_err(|e| e.to_string())?;
+                self.announce_burn(seat, slot as usize);
+                Ok(())
+            }
+            Some(other) => Err(format!("unknown message type '{}'", other)),
+            None => Err("the message has no type".to_string()),
+        }
+    }
+
+    /// Sits the client down in the requested seat (by number or color), or the first one they can have.
+    /// Before the game that is an open seat or one a bot is filling in. After it started, only a seat
+    /// someone dropped out of can be taken back.
+    fn join(&mut self, id: usize, message: &Json) -> Result<(), String> {
+        if let Some(seat) = self.clients[&id].seat {
+            return Err(format!("you already sit in seat {}", seat));
+        }
+        let name = match message.get("name") {
+            None | Some(Json::Null) => None,
+            name => Some(read_name(name).ok_or("a name needs 1 to 20 characters")?),
+        };
+
+        let available = |seat: &Seat| if self.started { seat.away } else { seat.client.is_none() };
+        let seat = match (message.get("seat"), message.get("color").and_then(Json::as_str)) {
+            (Some(json), _) if *json != Json::Null => {
+                let count = self.seats.len() as u64;
+                json.as_u64().filter(|s| (1..=count).contains(s)).ok_or(format!("seats go from 1 to {}", count))? as u8
+            }
+            (_, Some(color)) => self.seat_of_color(color).ok_or(format!("there is no {} seat", color))?,
+            _ => {
+                // Rather an open seat than kicking out a bot
+                let first = |want_open: bool| (1..=self.seats.len() as u8)
+                    .find(|&s| available(&self.seats[(s - 1) as usize]) && (!want_open || self.seats[(s - 1) as usize].is_open()));
+                first(true).or(first(false)).ok_or(if self.started { "the game has started without you" } else { "every seat is taken" })?
+            }
+        };
+        if !available(&self.seats[(seat - 1) as usize]) {
+            return Err(format!("seat {} is taken", seat));
+        }
+
+        let color = self.color(seat).to_string();
+        let taken = &mut self.seats[(seat - 1) as usize];
+        if taken.away {
+            println!("client {} is back in seat {}, taking over from the bot", id, seat);
+            taken.away = false;
+        } else {
+            println!("client {} sits in seat {}", id, seat);
+            taken.ready = false;
+            taken.name = None;
+        }
+        taken.client = Some(id);
+        taken.bot = None;
+        taken.name = name.or(taken.name.take()).or(Some(color));
+
+        self.clients.get_mut(&id).unwrap().seat = Some(seat);
+        if self.host.is_none() {
+            self.host = Some(id);
+        }
+        self.send_to(id, &Json::object([
+            ("type", Json::from("welcome")),
+            ("seat", Json::from(seat)),
+        ]));
+        self.broadcast(&self.state());
+        Ok(())
+    }
+
+    /// Frees the seat of a client that left. Mid-game a bot takes over until they come back.
+    fn leave(&mut self, id: usize, seat: u8) {
+        let playing = self.started && !self.game.is_over();
+        let left = &mut self.seats[(seat - 1) as usize];
+        if playing {
+            println!("a bot takes over seat {} until its player comes back", seat);
+            left.client = None;
+            left.bot = builtin_agent(DEFAULT_BOT);
+            left.away = true;
+        } else {
+            *left = Seat::open();
+        }
+
+        if self.host == Some(id) {
+            self.host = self.clients.iter().filter(|(_, c)| c.seat.is_some()).map(|(&id, _)| id).min();
+        }
+        self.broadcast(&self.state());
+        if playing {
+            self.play_bots();
+        }
+    }
+
+    /// Starts the game once every seat is filled and every player in it is ready
+    fn try_start(&mut self) {
+        if self.started || !self.seats.iter().all(Seat::is_ready) {
+            return;
+        }
+        println!("every seat is ready, starting the game");
+        self.started = true;
+        self.broadcast(&self.state());
+        self.play_bots();
+    }
+
+    /// Lets the bots move for as long as it is their turn
+    fn play_bots(&mut self) {
+        while self.started && let Some(phase) = self.game.phase() {
+            let seat = self.game.active_player().id();
+            let Some(bot) = self.seats[(seat - 1) as usize].bot.as_mut() else {
+                return;
+            };
+            if self.game.variant() == Variant::Queendomino {
+                // a build or burn the rules refuse is skipped, the bot still has its move to make
+                if let Some((slot, x, y)) = bot.build(&self.game) && self.game.build(slot, x, y).is_ok() {
+                    self.announce_build(seat, slot, x, y);
+                }
+                let bot = self.seats[(seat - 1) as usize].bot.as_mut().expect("the bot is still seated");
+                if let Some(slot) = bot.burn(&self.game) && self.game.burn(slot).is_ok() {
+                    self.announce_burn(seat, slot);
+                }
+            }
+            let bot = self.seats[(seat - 1) as usize].bot.as_mut().expect("the bot is still seated");
+            match phase {
+                Phase::Picking => {
+                    let slot = bot.pick(&self.game);
+                    self.game.pick(slot).expect("a built in bot picked an unpickable domino");
+                    self.announce_pick(seat, slot);
+                }
+                Phase::Placing => {
+                    let grid_domino = bot.place(&self.game);
+                    self.game.place(grid_domino).expect("a built in bot made an illegal placement");
+                    self.announce_place(seat, grid_domino);
+                }
+            }
+        }
+    }
+
+    fn announce_pick(&self, seat: u8, slot: usize) {
+        println!("seat {} picked slot {}", seat, slot);
+        self.broadcast(&Json::object([
+            ("type", Json::from("move")),
+            ("seat", Json::from(seat)),
+            ("pick", Json::from(slot)),
+        ]));
+        self.broadcast(&self.state());
+    }
+
+    fn announce_place(&self, seat: u8, grid_domino: Option<GridDomino>) {
+        println!("seat {} placed their domino", seat);
+        self.broadcast(&Json::object([
+            ("type", Json::from("move")),
+            ("seat", Json::from(seat)),
+            ("place", grid_domino.as_ref().map_or(Json::Null, encode_placement)),
+        ]));
+        self.broadcast(&self.state());
+    }
+
+    fn announce_build(&self, seat: u8, slot: usize, x: u8, y: u8) {
+        println!("seat {} built from slot {}", seat, slot);
+        self.broadcast(&Json::object([
+            ("type", Json::from("move")),
+            ("seat", Json::from(seat)),
+            ("build", encode_build(slot, x, y)),
+        ]));
+        self.broadcast(&self.state());
+    }
+
+    fn announce_burn(&self, seat: u8, slot: usize) {
+        println!("seat {} had the dragon burn slot {}", seat, slot);
+        self.broadcast(&Json::object([
+            ("type", Json::from("move")),
+            ("seat", Json::from(seat)),
+            ("burn", Json::from(slot)),
+        ]));
+        self.broadcast(&self.state());
+    }
+
+    /// Returns the seat of the client if it is their turn to move
+    fn seat_to_move(&self, id: usize) -> Result<u8, String> {
+        let seat = self.clients[&id].seat.ok_or("join a seat first")?;
+        if !self.started {
+            return Err("waiting for every seat to be filled and ready".to_string());
+        }
+        if self.game.is_over() {
+            return Err("the game is over".to_string());
+        }
+        if self.game.active_player().id() != seat {
+            return Err("it is not your turn".to_string());
+        }
+        Ok(seat)
+    }
+
+    /// The color of the seat, the way Game names its players
+    fn color(&self, seat: u8) -> &str {
+        self.game.players().iter().find(|p| p.id() == seat).expect("no player in that seat").name()
+    }
+
+    fn seat_of_color(&self, color: &str) -> Option<u8> {
+        (1..=self.seats.len() as u8).find(|&seat| self.color(seat).eq_ignore_ascii_case(color))
+    }
+
+    fn state(&self) -> Json {
+        let mut state = encode_state(&self.game);
+        let seats = (1..=self.seats.len() as u8).map(|number| {
+            let seat = &self.seats[(number - 1) as usize];
+            let kind = match (seat.client, &seat.bot) {
+                (Some(_), _) => "player",
+                (None, Some(_)) => "bot",
+                (None, None) => "open",
+            };
+            Json::object([
+                ("seat", Json::from(number)),
+                ("color", Json::from(self.color(number))),
+                ("name", Json::from(seat.name.clone())),
+                ("kind", Json::from(kind)),
+                ("ready", Json::from(seat.is_ready())),
+                ("away", Json::from(seat.away)),
+            ])
+        }).collect();
+        let host = self.host.and_then(|id| self.clients.get(&id)).and_then(|client| client.seat);
+
+        if let Json::Object(pairs) = &mut state {
+            pairs.push(("started".to_string(), Json::from(self.started)));
+            pairs.push(("host_seat".to_string(), Json::from(host)));
+            pairs.push(("seats".to_string(), Json::Array(seats)));
+        }
+        state
+    }
+
+    fn send_to(&self, id: usize, message: &Json) {
+        if let Some(client) = self.clients.get(&id) {
+            client.send(message);
+        }
+    }
+
+    fn broadcast(&self, message: &Json) {
+        for client in self.clients.values() {
+            client.send(message);
+        }
+    }
+}
+
+
+/// Names are trimmed and have to be 1 to 20 characters long
+fn read_name(json: Option<&Json>) -> Option<String> {
+    let name = json?.as_str()?.trim();
+    let length = name.chars().count();
+    if (1..=20).contains(&length) { Some(name.to_string()) } else { None }
+}
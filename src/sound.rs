@@ -0,0 +1,210 @@
+use crate::event_log::Event;
+
+// What the game sounds like: an effect for each thing worth hearing, how loud each kind of sound is played,
+// and the built-in effects, which are short tones made up here so the binary needs no sound files.
+// A theme pack can replace any of them, and add music, with .wav or .ogg files in its sounds folder.
+
+
+/// Sample rate of the built-in effects
+const SAMPLE_RATE: u32 = 22050;
+
+/// The theme pack sound that loops in the background, if there is one. There is no built-in music.
+pub const MUSIC: &str = "sounds/music";
+
+
+/// A sound effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Pick,
+    Rotate,
+    Place,
+    /// A move that is not allowed, like dropping a domino where it does not fit
+    Illegal,
+    RoundStart,
+    GameEnd,
+}
+
+impl Effect {
+
+    pub const ALL: [Effect; 6] = [Effect::Pick, Effect::Rotate, Effect::Place, Effect::Illegal, Effect::RoundStart, Effect::GameEnd];
+
+    /// The asset name of the effect, without the file extension ("sounds/pick")
+    pub fn name(&self) -> &'static str {
+        match self {
+            Effect::Pick => "sounds/pick",
+            Effect::Rotate => "sounds/rotate",
+            Effect::Place => "sounds/place",
+            Effect::Illegal => "sounds/illegal",
+            Effect::RoundStart => "sounds/round_start",
+            Effect::GameEnd => "sounds/game_end",
+        }
+    }
+
+    /// The effect an event of the game is heard with, if any
+    pub fn for_event(event: &Event) -> Option<Effect> {
+        match event {
+            Event::Picked { .. } => Some(Effect::Pick),
+            Event::Placed { .. } | Event::Built { .. } | Event::Burned { .. } => Some(Effect::Place),
+            Event::NewRound { .. } => Some(Effect::RoundStart),
+            Event::GameOver { .. } => Some(Effect::GameEnd),
+            Event::Discarded { .. } | Event::Score { .. } | Event::Undo { .. } => None,
+        }
+    }
+
+    /// The notes of the built-in effect, as (frequency in Hz, seconds)
+    fn notes(&self) -> &'static [(f32, f32)] {
+        match self {
+            Effect::Pick => &[(660.0, 0.06), (880.0, 0.1)],
+            Effect::Rotate => &[(1200.0, 0.04)],
+            Effect::Place => &[(220.0, 0.14)],
+            Effect::Illegal => &[(160.0, 0.1), (120.0, 0.16)],
+            Effect::RoundStart => &[(523.0, 0.09), (659.0, 0.09), (784.0, 0.16)],
+            Effect::GameEnd => &[(523.0, 0.12), (659.0, 0.12), (784.0, 0.12), (1047.0, 0.4)],
+        }
+    }
+}
+
+
+/// The built-in sound of the given name, as a .wav file. None for names the game has no sound of its own for.
+pub fn built_in(name: &str) -> Option<Vec<u8>> {
+    Effect::ALL.iter().find(|effect| effect.name() == name).map(|effect| synthesize(effect.notes()))
+}
+
+
+/// A mono 16 bit .wav file playing the notes one after another, each fading out like a plucked string
+pub fn synthesize(notes: &[(f32, f32)]) -> Vec<u8> {
+    let mut samples: Vec<i16> = Vec::new();
+    for &(frequency, seconds) in notes {
+        let count = (seconds * SAMPLE_RATE as f32) as usize;
+        for i in 0..count {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let attack = (i as f32 / 64.0).min(1.0); // no click at the start of the note
+            let decay = (-5.0 * t / seconds).exp();
+            let wave = (t * frequency * std::f32::consts::TAU).sin();
+            samples.push((wave * attack * decay * 0.6 * i16::MAX as f32) as i16);
+        }
+    }
+
+    let data_len = samples.len() as u32 * 2;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // size of the format chunk
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // channels
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // bytes per second
+    wav.extend_from_slice(&2u16.to_le_bytes()); // bytes per sample
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+
+/// Checks the bytes look like a sound the game can play: an Ogg Vorbis file, or a .wav file with one or
+/// two channels of PCM or float samples. The audio backend gives up on anything else without saying why.
+pub fn check_format(bytes: &[u8]) -> Result<(), String> {
+    if bytes.starts_with(b"OggS") {
+        return Ok(());
+    }
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(String::from("not a .wav or .ogg file"));
+    }
+
+    // walk the chunks to the format one
+    let mut at = 12;
+    while at + 8 <= bytes.len() {
+        let id = &bytes[at..at + 4];
+        let len = u32::from_le_bytes([bytes[at + 4], bytes[at + 5], bytes[at + 6], bytes[at + 7]]) as usize;
+        let body = &bytes[at + 8..];
+        if id == b"fmt " {
+            if body.len() < 16 {
+                return Err(String::from("the .wav format chunk is cut short"));
+            }
+            let format = u16::from_le_bytes([body[0], body[1]]);
+            let channels = u16::from_le_bytes([body[2], body[3]]);
+            if format != 1 && format != 3 && format != 0xFFFE {
+                return Err(format!("unsupported .wav encoding {}, use PCM or float samples", format));
+            }
+            if channels != 1 && channels != 2 {
+                return Err(format!("{} channels, only mono and stereo can be played", channels));
+            }
+            return Ok(());
+        }
+        at += 8 + len + len % 2; // chunks are padded to an even length
+    }
+    Err(String::from("the .wav file has no format chunk"))
+}
+
+
+/// One of the volume sliders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slider {
+    Master,
+    Effects,
+    Music,
+}
+
+impl Slider {
+    pub const ALL: [Slider; 3] = [Slider::Master, Slider::Effects, Slider::Music];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Slider::Master => "Volume",
+            Slider::Effects => "Effects",
+            Slider::Music => "Music",
+        }
+    }
+}
+
+
+/// How loud things are played, each between 0 and 1. Effects and music are both turned down by the master volume.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Volume {
+    master: f32,
+    effects: f32,
+    music: f32,
+    pub muted: bool,
+}
+
+impl Default for Volume {
+    fn default() -> Self {
+        Self { master: 0.8, effects: 1.0, music: 0.5, muted: false }
+    }
+}
+
+impl Volume {
+
+    pub fn get(&self, slider: Slider) -> f32 {
+        match slider {
+            Slider::Master => self.master,
+            Slider::Effects => self.effects,
+            Slider::Music => self.music,
+        }
+    }
+
+    /// Moves a slider, keeping it between 0 and 1
+    pub fn set(&mut self, slider: Slider, value: f32) {
+        let value = value.clamp(0.0, 1.0);
+        match slider {
+            Slider::Master => self.master = value,
+            Slider::Effects => self.effects = value,
+            Slider::Music => self.music = value,
+        }
+    }
+
+    /// How loud effects are played, 0 when muted
+    pub fn effects_level(&self) -> f32 {
+        if self.muted { 0.0 } else { self.master * self.effects }
+    }
+
+    /// How loud the music is played, 0 when muted
+    pub fn music_level(&self) -> f32 {
+        if self.muted { 0.0 } else { self.master * self.music }
+    }
+}
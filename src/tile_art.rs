@@ -0,0 +1,181 @@
+use macroquad::prelude::*;
+use crate::components::domino::Domino;
+use crate::components::giants::Mark;
+use crate::components::queendomino::Building;
+use crate::components::tile::{Tile, Types};
+
+// Paints dominoes straight from their tiles, for dominoes that come without a picture (custom sets,
+// Queendomino, or a res/img folder that went missing). Every terrain gets its own color and a little
+// pattern so they can be told apart, and every crown gets a crown icon.
+
+
+const CROWN_COLOR: Color = Color::new(0.96, 0.80, 0.16, 1.0);
+const LINE_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.35);
+const STONE_COLOR: Color = Color::new(0.85, 0.82, 0.74, 1.0);
+const GIANT_COLOR: Color = Color::from_rgba(70, 40, 30, 255);
+
+
+/// The flat color a terrain is painted with
+pub(crate) fn terrain_color(tile_type: Types) -> Color {
+    match tile_type {
+        Types::Grass => Color::from_rgba(126, 190, 72, 255),
+        Types::Wheat => Color::from_rgba(232, 200, 84, 255),
+        Types::Forest => Color::from_rgba(34, 110, 52, 255),
+        Types::Swamp => Color::from_rgba(120, 116, 76, 255),
+        Types::Water => Color::from_rgba(54, 124, 206, 255),
+        Types::Mine => Color::from_rgba(82, 72, 70, 255),
+        Types::Town => Color::from_rgba(176, 112, 86, 255),
+        Types::Castle => Color::from_rgba(170, 170, 180, 255),
+        Types::Null => Color::from_rgba(60, 60, 60, 255),
+    }
+}
+
+
+/// Draws a whole domino. (x, y) is the top left corner of the first tile, and `second` is where the
+/// second tile sits compared to the first, in tiles (like BuildRotation::second_offset).
+pub(crate) fn draw_domino(domino: &Domino, x: f32, y: f32, size: f32, second: (isize, isize), alpha: f32) {
+    let (second_x, second_y) = (x + second.0 as f32 * size, y + second.1 as f32 * size);
+    draw_tile(domino.get_tile(1), x, y, size, alpha);
+    draw_tile(domino.get_tile(2), second_x, second_y, size, alpha);
+
+    match domino.mark() {
+        Some(Mark::Giant) => {
+            // a big dark head in the middle of the first tile
+            draw_circle(x + size * 0.5, y + size * 0.5, size * 0.16, faded(GIANT_COLOR, alpha));
+        }
+        Some(Mark::Footprint) => {
+            draw_ellipse(x + size * 0.5, y + size * 0.55, size * 0.1, size * 0.18, 0.0, faded(GIANT_COLOR, alpha));
+        }
+        None => {}
+    }
+}
+
+
+/// Draws one tile with its top left corner at (x, y): terrain color, terrain pattern, then its crowns
+pub(crate) fn draw_tile(tile: Tile, x: f32, y: f32, size: f32, alpha: f32) {
+    let base = terrain_color(tile.get_type());
+    draw_rectangle(x, y, size, size, faded(base, alpha));
+    draw_pattern(tile.get_type(), x, y, size, faded(darker(base), alpha));
+    draw_rectangle_lines(x, y, size, size, (size / 25.0).max(1.0), faded(LINE_COLOR, alpha));
+
+    for crown in 0..tile.crowns() {
+        draw_crown(x + size * (0.08 + 0.26 * crown as f32), y + size * 0.08, size * 0.22, faded(CROWN_COLOR, alpha));
+    }
+}
+
+
+/// Draws a Queendomino building standing on a town tile whose top left corner is (x, y): a stone house with a
+/// tower for each tower it has, and its crowns above it. There are no building pictures, every building is painted.
+pub(crate) fn draw_building(building: &Building, x: f32, y: f32, size: f32) {
+    let body = Rect::new(x + size * 0.2, y + size * 0.5, size * 0.6, size * 0.38);
+    draw_rectangle(body.x, body.y, body.w, body.h, STONE_COLOR);
+    draw_triangle(vec2(x + size * 0.5, y + size * 0.3), vec2(body.x - size * 0.05, body.y),
+                  vec2(body.x + body.w + size * 0.05, body.y), darker(STONE_COLOR));
+    for tower in 0..building.towers {
+        draw_rectangle(body.x + size * 0.04 + tower as f32 * size * 0.36, y + size * 0.32, size * 0.14, size * 0.3, STONE_COLOR);
+    }
+    draw_rectangle_lines(body.x, body.y, body.w, body.h, (size / 25.0).max(1.0), LINE_COLOR);
+    draw_rectangle(x + size * 0.45, body.y + body.h * 0.45, size * 0.1, body.h * 0.55, BLACK);
+
+    for crown in 0..building.crowns {
+        draw_crown(x + size * (0.55 - 0.13 * building.crowns as f32 + 0.26 * crown as f32), y + size * 0.06, size * 0.22, CROWN_COLOR);
+    }
+}
+
+
+/// Draws an Age of Giants giant standing on the tile whose top left corner is (x, y), over the crowns it covers
+pub(crate) fn draw_giant(x: f32, y: f32, size: f32) {
+    let outline = Color::new(1.0, 1.0, 1.0, 0.8);
+    draw_circle(x + size * 0.5, y + size * 0.25, size * 0.13, GIANT_COLOR);
+    draw_triangle(vec2(x + size * 0.5, y + size * 0.3), vec2(x + size * 0.22, y + size * 0.9), vec2(x + size * 0.78, y + size * 0.9), GIANT_COLOR);
+    draw_line(x + size * 0.2, y + size * 0.45, x + size * 0.8, y + size * 0.45, size * 0.1, GIANT_COLOR);
+    draw_circle_lines(x + size * 0.5, y + size * 0.25, size * 0.13, (size / 30.0).max(1.0), outline);
+}
+
+
+/// A few strokes that say what the terrain is, even to someone who can't tell the colors apart
+fn draw_pattern(tile_type: Types, x: f32, y: f32, size: f32, color: Color) {
+    let stroke = (size / 20.0).max(1.0);
+    match tile_type {
+        Types::Grass => {
+            // tufts
+            for (tx, ty) in [(0.3, 0.55), (0.65, 0.4), (0.55, 0.8)] {
+                let (bx, by) = (x + size * tx, y + size * ty);
+                draw_line(bx, by, bx - size * 0.06, by - size * 0.12, stroke, color);
+                draw_line(bx, by, bx + size * 0.06, by - size * 0.12, stroke, color);
+            }
+        }
+        Types::Wheat => {
+            // furrows
+            for row in 1..4 {
+                let ry = y + size * (0.25 + 0.2 * row as f32);
+                draw_line(x + size * 0.15, ry, x + size * 0.85, ry, stroke, color);
+            }
+        }
+        Types::Forest => {
+            // trees
+            for (tx, ty) in [(0.3, 0.75), (0.7, 0.65), (0.5, 0.95)] {
+                let (bx, by) = (x + size * tx, y + size * ty);
+                draw_triangle(vec2(bx, by - size * 0.3), vec2(bx - size * 0.12, by), vec2(bx + size * 0.12, by), color);
+            }
+        }
+        Types::Swamp => {
+            // reeds
+            for tx in [0.3, 0.5, 0.7] {
+                let bx = x + size * tx;
+                draw_line(bx, y + size * 0.85, bx, y + size * 0.45, stroke, color);
+                draw_circle(bx, y + size * 0.45, size * 0.04, color);
+            }
+        }
+        Types::Water => {
+            // waves
+            for row in 0..3 {
+                let ry = y + size * (0.45 + 0.18 * row as f32);
+                for wave in 0..3 {
+                    let wx = x + size * (0.15 + 0.25 * wave as f32);
+                    draw_line(wx, ry, wx + size * 0.12, ry - size * 0.06, stroke, color);
+                    draw_line(wx + size * 0.12, ry - size * 0.06, wx + size * 0.24, ry, stroke, color);
+                }
+            }
+        }
+        Types::Mine => {
+            // a mountain with the mine in it
+            draw_triangle(vec2(x + size * 0.5, y + size * 0.35), vec2(x + size * 0.15, y + size * 0.9),
+                          vec2(x + size * 0.85, y + size * 0.9), color);
+            draw_rectangle(x + size * 0.44, y + size * 0.72, size * 0.12, size * 0.18, BLACK);
+        }
+        Types::Town => {
+            // a house
+            draw_rectangle(x + size * 0.3, y + size * 0.55, size * 0.4, size * 0.3, color);
+            draw_triangle(vec2(x + size * 0.5, y + size * 0.35), vec2(x + size * 0.25, y + size * 0.55),
+                          vec2(x + size * 0.75, y + size * 0.55), color);
+        }
+        Types::Castle => {
+            // keep and battlements
+            draw_rectangle(x + size * 0.25, y + size * 0.4, size * 0.5, size * 0.45, color);
+            for tower in 0..3 {
+                draw_rectangle(x + size * (0.25 + 0.2 * tower as f32), y + size * 0.3, size * 0.1, size * 0.1, color);
+            }
+        }
+        Types::Null => {}
+    }
+}
+
+/// A three pointed crown whose top left corner is (x, y)
+fn draw_crown(x: f32, y: f32, width: f32, color: Color) {
+    let height = width * 0.7;
+    draw_rectangle(x, y + height * 0.55, width, height * 0.45, color);
+    for point in 0..3 {
+        let left = x + width * point as f32 / 3.0;
+        draw_triangle(vec2(left, y + height * 0.55), vec2(left + width / 3.0, y + height * 0.55),
+                      vec2(left + width / 6.0, y), color);
+    }
+}
+
+fn darker(color: Color) -> Color {
+    Color::new(color.r * 0.7, color.g * 0.7, color.b * 0.7, color.a)
+}
+
+fn faded(color: Color, alpha: f32) -> Color {
+    Color::new(color.r, color.g, color.b, color.a * alpha)
+}
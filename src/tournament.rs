@@ -0,0 +1,246 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+use crate::agent::{agent_from_spec, play_out, Agent};
+use crate::components::domino_set::DominoSet;
+use crate::components::draft::DRAFT_SIZE;
+use crate::components::game::{Game, PLAYER_COLORS};
+use crate::components::variant::Variant;
+use crate::scoreboard::{self, ScoreLine};
+
+
+/// Rating every agent starts the tournament with
+pub const STARTING_ELO: f64 = 1500.0;
+
+/// How far a single game can move a rating
+const K_FACTOR: f64 = 32.0;
+
+
+/// How the tables of each round are put together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Every group of agents the size of a table meets once
+    RoundRobin,
+    /// Each round, agents with similar ratings sit together
+    Swiss { rounds: usize },
+}
+
+
+/// Everything needed to run a tournament
+pub struct TournamentConfig {
+    /// Agent specs, one per entrant (the same spec may appear several times).
+    /// "random", "greedy", or "exec:<command>" for a bot in another process.
+    pub agents: Vec<String>,
+    pub format: Format,
+    /// How many times each table plays every seat rotation
+    pub games_per_seating: usize,
+    /// File every game result is written to, as CSV
+    pub results_path: PathBuf,
+    /// How long an external bot gets per move before a random move is played for it
+    pub move_timeout: Duration,
+    /// Which rules every game is played by
+    pub variant: Variant,
+    /// Players per table. Four, or five if the variant allows it.
+    pub table_size: usize,
+    /// Dominoes to deal from instead of the variant's own set
+    pub domino_set: Option<DominoSet>,
+}
+
+
+/// Final line of the tournament table
+#[derive(Debug, Clone)]
+pub struct Standing {
+    pub name: String,
+    pub rating: f64,
+    pub games: usize,
+    pub wins: usize,
+    /// Games finished first together with someone else, still tied after the largest property and the crowns
+    pub draws: usize,
+}
+
+
+/// Runs the whole tournament, writing every game to the results file. Returns the standings, best first.
+pub fn run(config: &TournamentConfig) -> io::Result<Vec<Standing>> {
+    let table_size = config.table_size;
+    if !(DRAFT_SIZE..=config.variant.max_players()).contains(&table_size) {
+        let error = format!("{} is not played at tables of {}", config.variant.name(), table_size);
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, error));
+    }
+    if config.agents.len() < table_size {
+        let error = format!("a tournament needs at least {} agents to fill a table", table_size);
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, error));
+    }
+
+    let mut entrants: Vec<Box<dyn Agent>> = Vec::new();
+    for spec in &config.agents {
+        let agent = agent_from_spec(spec, config.move_timeout)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        entrants.push(agent);
+    }
+    let set = config.domino_set.as_ref().unwrap_or_else(|| DominoSet::builtin(config.variant));
+
+    let mut standings: Vec<Standing> = unique_names(&entrants).into_iter().map(|name| Standing {
+        name,
+        rating: STARTING_ELO,
+        games: 0,
+        wins: 0,
+        draws: 0,
+    }).collect();
+
+    let mut results = BufWriter::new(File::create(&config.results_path)?);
+    writeln!(results, "{}", results_header(table_size))?;
+
+    let rounds = match config.format {
+        Format::RoundRobin => 1,
+        Format::Swiss { rounds } => rounds,
+    };
+
+    let mut game_number = 0;
+    for round in 1..=rounds {
+        let tables = match config.format {
+            Format::RoundRobin => round_robin_tables(entrants.len(), table_size),
+            Format::Swiss { .. } => swiss_tables(&standings, table_size),
+        };
+
+        for table in tables {
+            for _ in 0..config.games_per_seating {
+                for rotation in 0..table_size {
+                    // Rotating the table gives every entrant the first pick (Prio1) in round one exactly once
+                    let seating: Vec<usize> = (0..table_size).map(|seat| table[(seat + rotation) % table_size]).collect();
+                    let lines = play_game(&mut entrants, &seating, config.variant, set)?;
+                    game_number += 1;
+
+                    let seat_names: Vec<&str> = seating.iter().map(|&e| standings[e].name.as_str()).collect();
+                    let seat_scores: Vec<String> = lines.iter().map(|line| line.score.to_string()).collect();
+                    writeln!(results, "{},{},{},{}", game_number, round, seat_names.join(","), seat_scores.join(","))?;
+
+                    update_standings(&mut standings, &seating, &lines);
+                }
+            }
+        }
+    }
+    results.flush()?;
+
+    standings.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+    Ok(standings)
+}
+
+
+/// Plays one headless game. seating[i] is the entrant sitting in seat i (player id i + 1).
+/// Returns every seat's ranked score line, in seat order.
+fn play_game(entrants: &mut [Box<dyn Agent>], seating: &[usize], variant: Variant, set: &DominoSet) -> io::Result<Vec<ScoreLine>> {
+    let mut by_entrant: Vec<Option<&mut Box<dyn Agent>>> = entrants.iter_mut().map(Some).collect();
+    let mut seats: Vec<&mut dyn Agent> = Vec::new();
+    for &entrant in seating {
+        seats.push(by_entrant[entrant].take().expect("entrant seated twice at one table").as_mut());
+    }
+
+    let mut game = Game::with_set(variant, seating.len(), set)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+    play_out(&mut game, &mut seats);
+    Ok(scoreboard::score_lines(&game))
+}
+
+
+/// The results file columns: who sat in each seat, then what they scored
+fn results_header(table_size: usize) -> String {
+    let colors: Vec<String> = PLAYER_COLORS[..table_size].iter().map(|color| color.to_lowercase()).collect();
+    let scores: Vec<String> = colors.iter().map(|color| format!("{}_score", color)).collect();
+    format!("game,round,{},{}", colors.join(","), scores.join(","))
+}
+
+
+/// Every group of table_size entrants, once
+fn round_robin_tables(entrants: usize, table_size: usize) -> Vec<Vec<usize>> {
+    let mut tables = Vec::new();
+    let mut table = Vec::new();
+    add_tables(&mut tables, &mut table, 0, entrants, table_size);
+    tables
+}
+
+/// Fills the rest of the table with every combination of the entrants from next on
+fn add_tables(tables: &mut Vec<Vec<usize>>, table: &mut Vec<usize>, next: usize, entrants: usize, table_size: usize) {
+    if table.len() == table_size {
+        tables.push(table.clone());
+        return;
+    }
+    for entrant in next..entrants {
+        table.push(entrant);
+        add_tables(tables, table, entrant + 1, entrants, table_size);
+        table.pop();
+    }
+}
+
+
+/// Seats entrants with neighbouring ratings together. If the last table is short it borrows
+/// the lowest rated entrants of the table above, who then play twice this round.
+fn swiss_tables(standings: &[Standing], table_size: usize) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..standings.len()).collect();
+    order.sort_by(|&a, &b| standings[b].rating.total_cmp(&standings[a].rating));
+
+    let mut tables: Vec<Vec<usize>> = order.chunks_exact(table_size).map(|chunk| chunk.to_vec()).collect();
+
+    let leftover = order.len() % table_size;
+    if leftover != 0 {
+        tables.push(order[order.len() - table_size..].to_vec());
+    }
+    tables
+}
+
+
+/// Updates ratings and records after a game, from the ranked score line of each seat (see scoreboard::rank).
+/// A game of n players counts as n·(n−1)/2 head to head results, one per pair, scaled so a game moves ratings
+/// about as much as a single match would. Ties are broken like the rules say, by the largest property and
+/// then the most crowns. Players still tied draw, and a first place they share is a draw rather than a win.
+pub fn update_standings(standings: &mut [Standing], seating: &[usize], lines: &[ScoreLine]) {
+    let ratings: Vec<f64> = seating.iter().map(|&entrant| standings[entrant].rating).collect();
+    let firsts = lines.iter().filter(|line| line.rank == 1).count();
+
+    for seat in 0..seating.len() {
+        let mut delta = 0.0;
+        for other in 0..seating.len() {
+            if seat == other {
+                continue;
+            }
+            let actual = match lines[other].rank.cmp(&lines[seat].rank) {
+                Ordering::Greater => 1.0,
+                Ordering::Equal => 0.5,
+                Ordering::Less => 0.0,
+            };
+            let expected = 1.0 / (1.0 + 10f64.powf((ratings[other] - ratings[seat]) / 400.0));
+            delta += actual - expected;
+        }
+
+        let standing = &mut standings[seating[seat]];
+        standing.rating += K_FACTOR * delta / (seating.len() - 1) as f64;
+        standing.games += 1;
+        match (lines[seat].rank, firsts) {
+            (1, 1) => standing.wins += 1,
+            (1, _) => standing.draws += 1,
+            _ => {}
+        }
+    }
+}
+
+
+/// Names entrants for the results. Repeated agents get numbered, e.g. greedy#1 and greedy#2.
+fn unique_names(entrants: &[Box<dyn Agent>]) -> Vec<String> {
+    let mut totals: HashMap<&str, usize> = HashMap::new();
+    for agent in entrants {
+        *totals.entry(agent.name()).or_default() += 1;
+    }
+
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    entrants.iter().map(|agent| {
+        let name = agent.name();
+        if totals[name] == 1 {
+            return name.to_string();
+        }
+        let count = seen.entry(name).or_default();
+        *count += 1;
+        format!("{}#{}", name, count)
+    }).collect()
+}
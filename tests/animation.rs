@@ -0,0 +1,31 @@
+use kingdomino::animation::{ease_out, lerp, progress, AnimationSpeed};
+use kingdomino::scoreboard::ScoreTicker;
+
+
+#[test]
+/// Animations run from 0 to 1 over their length, faster when sped up and not at all when turned off.
+fn speeds_scale_animations() {
+    assert_eq!(AnimationSpeed::parse("Fast"), Some(AnimationSpeed::Fast));
+    assert_eq!(AnimationSpeed::parse("slow"), None);
+
+    let normal = AnimationSpeed::Normal.scale(0.5);
+    assert!(AnimationSpeed::Fast.scale(0.5) < normal);
+    assert_eq!(progress(1.0, 1.25, normal), 0.5);
+    assert_eq!(progress(1.0, 0.5, normal), 0.0); // not started yet
+    assert_eq!(progress(1.0, 1.0, AnimationSpeed::Off.scale(0.5)), 1.0);
+
+    // with animations off, scores jump straight to their new value
+    let mut ticker = ScoreTicker::with_duration(AnimationSpeed::Off.scale(0.8));
+    ticker.set(12, 3.0);
+    assert_eq!(ticker.shown(3.0), 12.0);
+}
+
+
+#[test]
+/// Things start and end where they should, and land gently.
+fn easing_and_paths() {
+    assert_eq!(ease_out(0.0), 0.0);
+    assert_eq!(ease_out(1.0), 1.0);
+    assert!(ease_out(0.5) > 0.5);
+    assert_eq!(lerp((0.0, 10.0), (100.0, 20.0), 0.25), (25.0, 12.5));
+}
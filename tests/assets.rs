@@ -0,0 +1,43 @@
+use std::path::Path;
+use kingdomino::assets::{AssetError, AssetSource};
+
+
+#[test]
+/// Every picture compiled into the binary decodes, without needing res/img at run time.
+fn embedded_assets_decode() {
+    let source = AssetSource::default();
+    assert!(source.check().is_ok());
+    for name in AssetSource::embedded_names() {
+        let image = source.image(name).unwrap_or_else(|e| panic!("{}", e));
+        assert!(image.width > 0 && image.height > 0, "{}", name);
+    }
+    assert!(matches!(source.image("dominoes/domino_200.png"), Err(AssetError::Missing(_))));
+}
+
+
+#[test]
+/// A theme pack replaces only the pictures it has, and a broken one is reported by path.
+fn override_dir_replaces_pictures() {
+    let dir = std::env::temp_dir().join("kingdomino_theme_pack_test");
+    std::fs::create_dir_all(dir.join("kings")).unwrap();
+    std::fs::copy("res/img/kings/king_2.png", dir.join("kings/king_1.png")).unwrap();
+    std::fs::write(dir.join("hand.png"), b"not a png").unwrap();
+
+    let source = AssetSource::new(Some(&dir));
+    assert!(source.check().is_ok());
+
+    let built_in = AssetSource::default();
+    assert_eq!(source.image("kings/king_1.png").unwrap().bytes, built_in.image("kings/king_2.png").unwrap().bytes);
+    assert_eq!(source.image("socket.png").unwrap().bytes, built_in.image("socket.png").unwrap().bytes);
+    match source.image("hand.png") {
+        Err(e @ AssetError::Malformed(..)) => assert!(e.to_string().contains("hand.png")),
+        other => panic!("expected a malformed hand, got {:?}", other.map(|_| ())),
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    // A folder that isn't there is reported, but the built-in pictures still load
+    let missing = AssetSource::new(Some(Path::new("no/such/theme")));
+    assert!(matches!(missing.check(), Err(AssetError::NoOverrideDir(_))));
+    assert!(missing.image("socket.png").is_ok());
+}
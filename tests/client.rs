@@ -0,0 +1,275 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+use kingdomino::client::{RemoteGame, SeatKind};
+use kingdomino::components::variant::Variant;
+use kingdomino::json::Json;
+use kingdomino::server;
+
+/// Polls until the condition holds, failing the test if the server takes too long
+fn wait_for(remote: &mut RemoteGame, condition: impl Fn(&RemoteGame) -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while !condition(remote) {
+        assert!(Instant::now() < deadline, "timed out waiting on the server");
+        remote.poll();
+        thread::sleep(Duration::from_millis(2));
+    }
+}
+
+/// Makes the first legal move whenever it is our turn, until the game is over. Returns the final scores.
+/// In Queendomino that starts with the first building we can afford, whenever there is one.
+fn play(mut remote: RemoteGame) -> Vec<u32> {
+    loop {
+        wait_for(&mut remote, |r| r.is_over() || r.is_my_turn());
+        if remote.is_over() {
+            return remote.scores().unwrap();
+        }
+        if let Some(&(slot, x, y)) = remote.legal_builds().first() {
+            remote.build(slot, x, y).unwrap();
+            continue;
+        }
+        let first_legal = remote.legal_moves().first().cloned();
+        let result = match first_legal.as_ref().and_then(|legal| legal.as_u64()) {
+            Some(slot) => remote.pick(slot as usize),
+            None => remote.place(first_legal.as_ref()),
+        };
+        result.unwrap();
+    }
+}
+
+#[test]
+/// Four clients play a whole game against a server hosted in this process, and see the same final scores.
+fn four_remote_games_play_a_full_game() {
+    let (address, host) = server::spawn_local(Variant::Kingdomino, 4).unwrap();
+    let address = address.to_string();
+
+    let mut remotes = Vec::new();
+    for seat in 1..=4 {
+        let mut remote = RemoteGame::connect(&address, None, None).unwrap();
+        wait_for(&mut remote, |r| r.seat().is_some());
+        assert_eq!(remote.seat(), Some(seat));
+        remote.set_ready(true).unwrap();
+        remotes.push(remote);
+    }
+    for remote in remotes.iter_mut() {
+        wait_for(remote, RemoteGame::has_started);
+    }
+
+    // Blue picks first. Nobody else can move, and the client does not even ask
+    assert!(remotes[0].is_my_turn());
+    assert!(!remotes[1].is_my_turn());
+    assert_eq!(remotes[1].pick(0), Err("it is not your turn".to_string()));
+    assert!(remotes[0].place(None).is_err());
+
+    let players: Vec<_> = remotes.into_iter().map(|remote| thread::spawn(move || play(remote))).collect();
+
+    let scores = host.join().unwrap().unwrap();
+    for player in players {
+        assert_eq!(player.join().unwrap(), scores);
+    }
+}
+
+#[test]
+/// One player claims Red by color, the host fills the rest with bots and the game plays out around them.
+fn host_fills_the_lobby_with_bots() {
+    let (address, host) = server::spawn_local(Variant::Kingdomino, 4).unwrap();
+    let address = address.to_string();
+
+    let mut red = RemoteGame::connect(&address, Some("red"), Some("Ann")).unwrap();
+    wait_for(&mut red, RemoteGame::is_host);
+    assert_eq!(red.seat(), Some(3));
+
+    // Red is taken, so the next player gets the first open seat instead
+    let mut blue = RemoteGame::connect(&address, None, None).unwrap();
+    wait_for(&mut blue, |r| r.seat().is_some());
+    assert_eq!(blue.seat(), Some(1));
+    assert!(!blue.is_host());
+    assert!(blue.fill_with_bots("greedy").is_err());
+    wait_for(&mut red, |r| r.lobby().iter().filter(|s| s.kind == SeatKind::Open).count() == 2);
+    drop(blue);
+
+    wait_for(&mut red, |r| r.lobby().iter().filter(|s| s.kind == SeatKind::Open).count() == 3);
+    red.fill_with_bots("random").unwrap();
+    wait_for(&mut red, |r| r.lobby().iter().all(|s| s.kind != SeatKind::Open));
+    let lobby = red.lobby().to_vec();
+    assert_eq!(lobby[2].name.as_deref(), Some("Ann"));
+    assert_eq!(lobby[2].kind, SeatKind::Player);
+    assert!(lobby.iter().filter(|s| s.kind == SeatKind::Bot).all(|s| s.ready));
+    assert!(!red.has_started());
+
+    red.set_ready(true).unwrap();
+    let scores = play(red);
+    assert_eq!(host.join().unwrap().unwrap(), scores);
+}
+
+#[test]
+/// A player who drops out mid-game is replaced by a bot, and gets their seat back by joining again.
+fn bot_holds_the_seat_of_a_player_who_left() {
+    let (address, host) = server::spawn_local(Variant::Kingdomino, 4).unwrap();
+    let address = address.to_string();
+
+    let mut green = RemoteGame::connect(&address, Some("Green"), None).unwrap();
+    wait_for(&mut green, RemoteGame::is_host);
+    let mut yellow = RemoteGame::connect(&address, Some("Yellow"), None).unwrap();
+    wait_for(&mut yellow, |r| r.seat().is_some());
+    green.fill_with_bots("greedy").unwrap();
+    green.set_ready(true).unwrap();
+    yellow.set_ready(true).unwrap();
+    wait_for(&mut green, RemoteGame::has_started);
+
+    // Yellow leaves without a word, the game goes on without waiting for them
+    drop(yellow);
+    wait_for(&mut green, |r| r.lobby()[3].away);
+    assert_eq!(green.lobby()[3].kind, SeatKind::Bot);
+
+    let mut yellow = RemoteGame::connect(&address, None, None).unwrap();
+    wait_for(&mut yellow, |r| r.seat().is_some());
+    assert_eq!(yellow.seat(), Some(4));
+    wait_for(&mut yellow, |r| !r.lobby()[3].away);
+    assert_eq!(yellow.lobby()[3].kind, SeatKind::Player);
+
+    let players = [thread::spawn(move || play(green)), thread::spawn(move || play(yellow))];
+    let scores = host.join().unwrap().unwrap();
+    for player in players {
+        assert_eq!(player.join().unwrap(), scores);
+    }
+}
+
+#[test]
+/// A fifth player sits down at an Age of Giants table, and the giants and quests come across the wire.
+fn five_seat_age_of_giants_table() {
+    let (address, host) = server::spawn_local(Variant::AgeOfGiants, 5).unwrap();
+    let address = address.to_string();
+
+    let mut purple = RemoteGame::connect(&address, Some("Purple"), None).unwrap();
+    wait_for(&mut purple, RemoteGame::is_host);
+    assert_eq!(purple.seat(), Some(5));
+    assert_eq!(purple.lobby().len(), 5);
+    assert_eq!(purple.variant(), Some(Variant::AgeOfGiants));
+
+    purple.fill_with_bots("greedy").unwrap();
+    purple.set_ready(true).unwrap();
+    let scores = play(purple);
+    assert_eq!(scores.len(), 5);
+    assert_eq!(host.join().unwrap().unwrap(), scores);
+}
+
+#[test]
+/// A Queendomino player builds over the network, and sees the same variant scores the server counts.
+fn remote_queendomino_builds() {
+    let (address, host) = server::spawn_local(Variant::Queendomino, 4).unwrap();
+    let address = address.to_string();
+
+    let mut blue = RemoteGame::connect(&address, None, None).unwrap();
+    wait_for(&mut blue, RemoteGame::is_host);
+    assert_eq!(blue.variant(), Some(Variant::Queendomino));
+    // no table to build on before the game started
+    assert!(blue.build(0, 4, 5).is_err());
+
+    blue.fill_with_bots("greedy").unwrap();
+    blue.set_ready(true).unwrap();
+    let scores = play(blue);
+    assert_eq!(host.join().unwrap().unwrap(), scores);
+}
+
+/// The state a real server sends a player who just sat down at a table of this variant
+fn lobby_state(variant: Variant) -> Json {
+    let (address, _host) = server::spawn_local(variant, 4).unwrap();
+    let mut real = TcpStream::connect(address).unwrap();
+    real.write_all(b"{\"type\":\"join\",\"color\":null,\"name\":null}\n").unwrap();
+    let state = BufReader::new(real).lines().map(Result::unwrap)
+        .find(|line| line.contains("\"type\":\"state\""))
+        .unwrap();
+    Json::parse(&state).unwrap()
+}
+
+/// The state marked as started, so taking it would show, with `edit` applied to the value under `key`
+fn edited(state: &Json, key: &str, edit: impl FnOnce(&mut Json)) -> String {
+    let mut state = state.clone();
+    let Json::Object(pairs) = &mut state else { panic!("the state is an object") };
+    for (name, value) in pairs.iter_mut() {
+        if name == "started" {
+            *value = Json::Bool(true);
+        }
+    }
+    edit(&mut pairs.iter_mut().find(|(name, _)| name == key).unwrap().1);
+    state.to_string()
+}
+
+/// Connects to a stand-in server that seats us and sends these states, and returns once the client read them all
+fn read_by_client(states: Vec<String>) -> RemoteGame {
+    let stand_in = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = stand_in.local_addr().unwrap().to_string();
+    let server = thread::spawn(move || {
+        let (mut stream, _) = stand_in.accept().unwrap();
+        let mut lines = vec![r#"{"type":"welcome","seat":1}"#.to_string()];
+        lines.extend(states);
+        lines.push(r#"{"type":"error","message":"done"}"#.to_string());
+        for line in lines {
+            stream.write_all(format!("{}\n", line).as_bytes()).unwrap();
+        }
+        stream
+    });
+
+    let mut remote = RemoteGame::connect(&address, None, None).unwrap();
+    wait_for(&mut remote, |r| r.last_error() == Some("done"));
+    drop(server.join().unwrap());
+    remote
+}
+
+#[test]
+/// A state placing a domino off the 9x9 map, or with its second tile past the edge, is refused instead of crashing
+/// the client. The placements are planted in a real lobby state.
+fn placements_off_the_map_are_refused() {
+    let state = lobby_state(Variant::Kingdomino);
+    let tiles = r#"[{"terrain":"wheat","crowns":0},{"terrain":"wheat","crowns":0}]"#;
+    let mut states = vec![state.to_string()];
+    for (x, y, rotation) in [(200, 4, "up"), (8, 4, "up"), (0, 4, "down"), (4, 0, "right"), (4, 8, "left")] {
+        let placed = Json::parse(&format!(r#"{{"id":1,"tiles":{},"x":{},"y":{},"rotation":"{}"}}"#, tiles, x, y, rotation)).unwrap();
+        states.push(edited(&state, "players", |players| {
+            let Json::Array(players) = players else { panic!("the players are a list") };
+            let Json::Object(player) = &mut players[0] else { panic!("a player is an object") };
+            player.iter_mut().find(|(key, _)| key == "dominoes").unwrap().1 = Json::Array(vec![placed]);
+        }));
+    }
+
+    let remote = read_by_client(states);
+    assert_eq!(remote.lobby().len(), 4);
+    assert!(!remote.has_started());
+}
+
+#[test]
+/// A giant or a footprint on a draft domino is read back, and a mark Age of Giants doesn't have is refused.
+fn draft_marks_are_read_back() {
+    let state = lobby_state(Variant::AgeOfGiants);
+    let marked = |mark: &'static str| edited(&state, "pick_draft", |draft| {
+        let Json::Array(slots) = draft else { panic!("the draft is a list") };
+        let Json::Object(slot) = &mut slots[0] else { panic!("a slot is an object") };
+        let Json::Object(domino) = &mut slot.iter_mut().find(|(key, _)| key == "domino").unwrap().1 else { panic!("a domino is an object") };
+        domino.iter_mut().find(|(key, _)| key == "mark").expect("dominoes say whether they have a mark").1 = Json::from(mark);
+    });
+
+    for mark in ["giant", "footprint"] {
+        assert!(read_by_client(vec![state.to_string(), marked(mark)]).has_started(), "{} refused", mark);
+    }
+    assert!(!read_by_client(vec![state.to_string(), marked("dragon")]).has_started());
+}
+
+#[test]
+/// The deck is the one the server says is left, not the variant's own set, so games dealt from a set of
+/// their own count their dominoes right. A deck that can't be read is refused with the rest of the state.
+fn deck_comes_from_the_server() {
+    let state = lobby_state(Variant::Kingdomino);
+    let remaining = state.get("deck_remaining").and_then(Json::as_u64).unwrap() as usize;
+    assert_eq!(read_by_client(vec![state.to_string()]).deck_len(), Some(remaining));
+
+    let own_set: Vec<Json> = (201..=204)
+        .map(|id| Json::parse(&format!(r#"{{"id":{},"tiles":[{{"terrain":"mine","crowns":3}},{{"terrain":"swamp","crowns":0}}],"mark":null}}"#, id)).unwrap())
+        .collect();
+    let custom = edited(&state, "deck", |deck| *deck = Json::Array(own_set.clone()));
+    assert_eq!(read_by_client(vec![custom]).deck_len(), Some(4));
+
+    let broken = edited(&state, "deck", |deck| *deck = Json::Array(vec![Json::from("not a domino")]));
+    assert!(!read_by_client(vec![state.to_string(), broken]).has_started());
+}
@@ -0,0 +1,19 @@
+use kingdomino::deck_tracker::chance_in_draft;
+
+
+#[test]
+/// The odds of seeing a kind of domino in the next draft follow drawing without putting back.
+fn draft_chances() {
+    assert_eq!(chance_in_draft(48, 0, 4), 0.0);
+    assert_eq!(chance_in_draft(0, 0, 4), 0.0);
+    assert_eq!(chance_in_draft(48, 48, 4), 1.0);
+    // the last draft takes the whole deck
+    assert_eq!(chance_in_draft(4, 1, 4), 1.0);
+
+    assert!((chance_in_draft(10, 1, 1) - 0.1).abs() < 1e-9);
+    assert!((chance_in_draft(10, 2, 2) - (1.0 - 8.0 / 10.0 * 7.0 / 9.0)).abs() < 1e-9);
+
+    // more of them left means better odds
+    let odds: Vec<f64> = (0..=8).map(|matching| chance_in_draft(40, matching, 4)).collect();
+    assert!(odds.windows(2).all(|pair| pair[0] < pair[1]));
+}
@@ -0,0 +1,93 @@
+use kingdomino::components::domino_set::{DominoSet, SetError};
+use kingdomino::components::variant::Variant;
+use kingdomino::external_agent::DEFAULT_MOVE_TIMEOUT;
+use kingdomino::tournament::{self, Format, TournamentConfig};
+
+
+/// A set of `count` dominoes, every one of them half wheat and half forest with a crown on the forest
+fn tiny_set(count: u8) -> String {
+    let dominoes: Vec<String> = (1..=count).map(|id| format!(
+        r#"{{"id": {}, "tiles": [{{"terrain": "wheat", "crowns": 0}}, {{"terrain": "forest", "crowns": 1}}]}}"#, id
+    )).collect();
+    format!(r#"{{"name": "Tiny", "dominoes": [{}]}}"#, dominoes.join(","))
+}
+
+fn invalid_reason(text: &str) -> String {
+    match DominoSet::parse(text) {
+        Err(SetError::Invalid(reason)) => reason,
+        other => panic!("expected the set to be rejected, got {:?}", other),
+    }
+}
+
+
+#[test]
+/// The sets that ship with the game are all readable and the right size.
+fn builtin_sets_load() {
+    assert_eq!(DominoSet::builtin(Variant::Kingdomino).len(), 48);
+    assert_eq!(DominoSet::builtin(Variant::Queendomino).len(), 48);
+    assert_eq!(DominoSet::builtin(Variant::AgeOfGiants).len(), 54);
+    assert_eq!(DominoSet::builtin(Variant::AgeOfGiants).name(), "Age of Giants");
+}
+
+
+#[test]
+/// A small hand-written set parses, and a broken one says what is wrong with it.
+fn sets_are_validated() {
+    let set = DominoSet::parse(&tiny_set(8)).unwrap();
+    assert_eq!((set.name(), set.len()), ("Tiny", 8));
+
+    let tile = r#"{"terrain": "wheat", "crowns": 0}"#;
+    let with = |domino: &str| format!(r#"{{"dominoes": [{}]}}"#, domino);
+
+    let twice = format!(r#"{{"dominoes": [{{"id": 3, "tiles": [{0}, {0}]}}, {{"id": 3, "tiles": [{0}, {0}]}}]}}"#, tile);
+    assert!(invalid_reason(&twice).contains("used twice"));
+
+    let castle = with(&format!(r#"{{"id": 49, "tiles": [{0}, {0}]}}"#, tile));
+    assert!(invalid_reason(&castle).contains("id 49"));
+
+    let one_tile = with(&format!(r#"{{"id": 1, "tiles": [{}]}}"#, tile));
+    assert!(invalid_reason(&one_tile).contains("two \"tiles\""));
+
+    let lava = with(&format!(r#"{{"id": 1, "tiles": [{}, {{"terrain": "lava", "crowns": 0}}]}}"#, tile));
+    assert!(invalid_reason(&lava).contains("lava"));
+
+    let crowned = with(&format!(r#"{{"id": 1, "tiles": [{}, {{"terrain": "mine", "crowns": 4}}]}}"#, tile));
+    assert!(invalid_reason(&crowned).contains("4 crowns"));
+
+    let marked = with(&format!(r#"{{"id": 1, "tiles": [{0}, {0}], "mark": "dragon"}}"#, tile));
+    assert!(invalid_reason(&marked).contains("mark"));
+
+    assert!(invalid_reason(r#"{"dominoes": []}"#).contains("no dominoes"));
+    assert!(matches!(DominoSet::parse("{\"dominoes\": ["), Err(SetError::Parse(_))));
+}
+
+
+#[test]
+/// A tournament dealt from a tiny set plays its two rounds to the end.
+fn tournament_plays_a_tiny_set() {
+    let path = std::env::temp_dir().join("kingdomino_tiny_set_test.csv");
+    let mut config = TournamentConfig {
+        agents: ["greedy", "random", "random", "greedy"].iter().map(|s| s.to_string()).collect(),
+        format: Format::RoundRobin,
+        games_per_seating: 1,
+        results_path: path.clone(),
+        move_timeout: DEFAULT_MOVE_TIMEOUT,
+        variant: Variant::Kingdomino,
+        table_size: 4,
+        domino_set: Some(DominoSet::parse(&tiny_set(8)).unwrap()),
+    };
+    let standings = tournament::run(&config).unwrap();
+    assert_eq!(standings.iter().map(|s| s.games).sum::<usize>(), 4 * 4);
+
+    // Two dominoes each, and every one of them has a crown, so nobody scores nothing
+    let results = std::fs::read_to_string(&path).unwrap();
+    for line in results.lines().skip(1) {
+        assert!(line.split(',').skip(6).all(|score| score.parse::<u32>().unwrap() > 0), "{}", line);
+    }
+
+    // Not even one draft's worth
+    config.domino_set = Some(DominoSet::parse(&tiny_set(3)).unwrap());
+    assert!(tournament::run(&config).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,36 @@
+use kingdomino::event_log::{Event, EventLog};
+
+
+#[test]
+/// Events read as plain sentences, with where a domino went and which way it faces.
+fn events_read_as_sentences() {
+    let placed = Event::Placed { player: "Blue".to_string(), domino: 12, x: 5, y: 4, second: (0, -1) };
+    assert_eq!(placed.to_string(), "Blue placed domino 12 at (5, 4), second tile above");
+    let discarded = Event::Discarded { player: "Red".to_string(), domino: 40 };
+    assert_eq!(discarded.to_string(), "Red had no room for domino 40 and discarded it");
+    let over = Event::GameOver { scores: vec![("Blue".to_string(), 31), ("Red".to_string(), 18)] };
+    assert_eq!(over.to_string(), "Game over: Blue 31, Red 18");
+    assert!(Event::NewRound { deck_left: 0, last: true }.to_string().starts_with("Last round"));
+    let built = Event::Built { player: "Yellow".to_string(), building: 3, x: 6, y: 2 };
+    assert_eq!(built.to_string(), "Yellow built building 3 at (6, 2)");
+    assert_eq!(Event::Burned { player: "Red".to_string(), building: 9 }.to_string(), "Red had the dragon burn building 9");
+}
+
+
+#[test]
+/// Every event is kept for the panel and written to the log file as it comes, one stamped line each.
+fn log_writes_every_event_to_its_file() {
+    let path = std::env::temp_dir().join("kingdomino_event_log_test.log");
+    let mut log = EventLog::new();
+    log.write_to(&path).unwrap();
+    log.record(Event::Picked { player: "Green".to_string(), domino: 7 });
+    log.record(Event::Score { player: "Green".to_string(), from: 0, to: 4 });
+
+    assert_eq!(log.lines().len(), 2);
+    assert!(log.lines()[0].starts_with("[00:00] "));
+    assert!(log.lines()[0].ends_with("Green picked domino 7"));
+
+    let written = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(written.lines().collect::<Vec<_>>(), log.lines());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+use kingdomino::tournament::{self, Format, TournamentConfig};
+use kingdomino::components::variant::Variant;
+
+#[test]
+#[cfg(unix)]
+/// Seats a bot that always answers with illegal moves and one that never answers at all.
+/// Both should get random moves played for them and every game should still finish.
+fn misbehaving_bots_fall_back_to_random_moves() {
+    let path = std::env::temp_dir().join("kingdomino_external_agent_test.csv");
+    let illegal = r#"exec:while read line; do echo '{"pick": 9, "place": {"x": 0, "y": 0, "rotation": "up"}}'; done"#;
+    let silent = "exec:cat > /dev/null";
+
+    let config = TournamentConfig {
+        agents: vec![illegal.to_string(), silent.to_string(), "greedy".to_string(), "random".to_string()],
+        format: Format::RoundRobin,
+        games_per_seating: 1,
+        results_path: path.clone(),
+        move_timeout: Duration::from_millis(10),
+        variant: Variant::Kingdomino,
+        table_size: 4,
+        domino_set: None,
+    };
+    let standings = tournament::run(&config).unwrap();
+
+    assert!(standings.iter().all(|s| s.games == 4));
+    let results = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(results.lines().count(), 1 + 4);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+/// Unknown agents and bots that can't be started are reported instead of panicking
+fn bad_agent_specs_are_errors() {
+    let config = TournamentConfig {
+        agents: vec!["greedy".to_string(), "random".to_string(), "random".to_string(), "clever".to_string()],
+        format: Format::RoundRobin,
+        games_per_seating: 1,
+        results_path: std::env::temp_dir().join("kingdomino_bad_spec_test.csv"),
+        move_timeout: Duration::from_millis(10),
+        variant: Variant::Kingdomino,
+        table_size: 4,
+        domino_set: None,
+    };
+    let error = tournament::run(&config).unwrap_err();
+    assert!(error.to_string().contains("clever"));
+}
+
+#[test]
+#[cfg(unix)]
+/// A Queendomino bot is asked to build whenever it can afford something, and what it builds stays built.
+/// This one always takes the first build on offer and leaves the rest of its moves to the fallback.
+fn external_bots_build_in_queendomino() {
+    let path = std::env::temp_dir().join("kingdomino_external_build_test.csv");
+    let log = std::env::temp_dir().join("kingdomino_external_build_test.log");
+    let _ = std::fs::remove_file(&log);
+    let builder = format!(
+        r#"exec:while read line; do echo "$line" >> {}; case "$line" in *'"type":"build"'*) echo "{{\"build\": $(echo "$line" | sed 's/.*"legal":\[\({{[^}}]*}}\).*/\1/')}}";; *'"type":"burn"'*) echo '{{"burn": null}}';; *) echo '{{}}';; esac; done"#,
+        log.display(),
+    );
+
+    let config = TournamentConfig {
+        agents: vec![builder, "greedy".to_string(), "random".to_string(), "random".to_string()],
+        format: Format::RoundRobin,
+        games_per_seating: 1,
+        results_path: path.clone(),
+        move_timeout: Duration::from_secs(2),
+        variant: Variant::Queendomino,
+        table_size: 4,
+        domino_set: None,
+    };
+    tournament::run(&config).unwrap();
+
+    let seen = std::fs::read_to_string(&log).unwrap();
+    assert!(seen.lines().any(|line| line.contains(r#""type":"build""#)), "the bot was never asked to build");
+    // "you" comes before "opponents", so the first buildings listed are the bot's own
+    let built = seen.lines()
+        .filter(|line| line.starts_with(r#"{"type":"game_over""#))
+        .any(|line| line.split(r#""opponents""#).next().unwrap().contains(r#""buildings":[{"#));
+    assert!(built, "the bot's buildings never showed up");
+    assert!(seen.contains(r#""board":["#) && seen.contains(r#""coins":"#));
+
+    std::fs::remove_file(&path).unwrap();
+    std::fs::remove_file(&log).unwrap();
+}
@@ -0,0 +1,42 @@
+use kingdomino::hit::{Button, HitMap, Target};
+use kingdomino::layout::{DraftColumn, Layout, Rect};
+
+
+#[test]
+/// A click resolves to whatever region is under it, the one registered last winning where they overlap.
+fn clicks_resolve_to_targets() {
+    let mut hits = HitMap::new();
+    hits.register(Rect::new(0.0, 0.0, 100.0, 50.0), Target::DraftSlot(0));
+    hits.register(Rect::new(0.0, 60.0, 100.0, 50.0), Target::DraftSlot(1));
+    hits.register(Rect::new(300.0, 300.0, 50.0, 50.0), Target::Socket { x: 3, y: 4 });
+    hits.register(Rect::new(320.0, 320.0, 80.0, 30.0), Target::Button(Button::Rotate));
+
+    assert_eq!(hits.target_at(50.0, 25.0), Some(Target::DraftSlot(0)));
+    assert_eq!(hits.target_at(50.0, 55.0), None); // the gap between two slots
+    assert_eq!(hits.target_at(50.0, 100.0), Some(Target::DraftSlot(1)));
+    assert_eq!(hits.target_at(310.0, 310.0), Some(Target::Socket { x: 3, y: 4 }));
+    assert_eq!(hits.target_at(330.0, 330.0), Some(Target::Button(Button::Rotate)));
+    assert_eq!(hits.rect_of(Target::Socket { x: 3, y: 4 }), Some(Rect::new(300.0, 300.0, 50.0, 50.0)));
+
+    hits.clear();
+    assert_eq!(hits.target_at(50.0, 25.0), None);
+    assert!(hits.regions().is_empty());
+}
+
+
+#[test]
+/// Registering the draft the way the gui draws it, the middle of every slot clicks that slot.
+fn draft_regions_match_the_layout() {
+    let layout = Layout::new(1280.0, 800.0, 4, 4);
+    let mut hits = HitMap::new();
+    for slot in 0..4 {
+        hits.register(layout.draft_slot(DraftColumn::Pick, slot), Target::DraftSlot(slot));
+    }
+    for slot in 0..4 {
+        let (x, y) = layout.draft_slot(DraftColumn::Pick, slot).center();
+        assert_eq!(hits.target_at(x, y), Some(Target::DraftSlot(slot)));
+        // the place column is not clickable
+        let (x, y) = layout.draft_slot(DraftColumn::Place, slot).center();
+        assert_eq!(hits.target_at(x, y), None);
+    }
+}
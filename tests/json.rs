@@ -0,0 +1,89 @@
+use kingdomino::json::{Json, MAX_DEPTH};
+
+
+#[test]
+/// Escapes survive a round trip, and whatever is written stays on one line.
+fn strings_escape_and_unescape() {
+    let text = "quote \" backslash \\ slash / tab \t newline \n return \r bell \u{7}";
+    let written = Json::from(text).to_string();
+    assert!(!written.contains('\n'));
+    assert_eq!(written, r#""quote \" backslash \\ slash / tab \t newline \n return \r bell \u0007""#);
+    assert_eq!(Json::parse(&written).unwrap(), Json::from(text));
+
+    assert_eq!(Json::parse(r#""\/\b\f""#).unwrap(), Json::from("/\u{8}\u{c}"));
+    assert_eq!(Json::parse(r#""crème brûlée, 王""#).unwrap(), Json::from("crème brûlée, 王"));
+}
+
+
+#[test]
+/// \u escapes read basic plane characters directly and the rest from surrogate pairs. A lone surrogate is replaced.
+fn unicode_escapes_and_surrogate_pairs() {
+    assert_eq!(Json::parse(r#""\u00e9\u738B""#).unwrap(), Json::from("é王"));
+    assert_eq!(Json::parse(r#""\ud83d\udc51 crown""#).unwrap(), Json::from("\u{1F451} crown"));
+    assert_eq!(Json::parse(r#""\ud83d alone""#).unwrap(), Json::from("\u{FFFD} alone"));
+    assert!(Json::parse(r#""\u12""#).is_err());
+    assert!(Json::parse(r#""\uzzzz""#).is_err());
+}
+
+
+#[test]
+/// Negative, fractional and exponent numbers parse. Whole numbers are written without a decimal point.
+fn numbers_parse_and_print() {
+    assert_eq!(Json::parse("-12").unwrap(), Json::Number(-12.0));
+    assert_eq!(Json::parse("3.25").unwrap(), Json::Number(3.25));
+    assert_eq!(Json::parse("-1.5e2").unwrap(), Json::Number(-150.0));
+    assert_eq!(Json::parse(" [0, 2E-1] ").unwrap(), Json::Array(vec![Json::Number(0.0), Json::Number(0.2)]));
+
+    assert_eq!(Json::Number(-12.0).to_string(), "-12");
+    assert_eq!(Json::Number(0.25).to_string(), "0.25");
+    assert_eq!(Json::parse("1e400").unwrap().to_string(), "null");
+
+    // only whole, non negative numbers are counts
+    assert_eq!(Json::Number(7.0).as_u64(), Some(7));
+    assert_eq!(Json::Number(-7.0).as_u64(), None);
+    assert_eq!(Json::Number(7.5).as_u64(), None);
+}
+
+
+#[test]
+/// Objects keep their key order, and a written document reads back the same.
+fn documents_round_trip() {
+    let message = Json::object([
+        ("type", "place".into()),
+        ("legal", Json::Array(vec![Json::Array(vec![5u8.into(), 4u8.into()]), Json::Null])),
+        ("ready", true.into()),
+        ("seat", Json::from(None::<u8>)),
+        ("nested", Json::object([("empty", Json::Array(Vec::new())), ("none", Json::Object(Vec::new()))])),
+    ]);
+    let written = message.to_string();
+    assert_eq!(written, r#"{"type":"place","legal":[[5,4],null],"ready":true,"seat":null,"nested":{"empty":[],"none":{}}}"#);
+    assert_eq!(Json::parse(&written).unwrap(), message);
+    assert_eq!(message.get("ready").and_then(Json::as_bool), Some(true));
+    assert_eq!(message.get("missing"), None);
+    assert_eq!(Json::parse(" { \"a\" : [ 1 , 2 ] } ").unwrap().get("a"), Some(&Json::Array(vec![1u8.into(), 2u8.into()])));
+}
+
+
+#[test]
+/// Up to MAX_DEPTH levels of nesting parse, one more is refused instead of recursing further.
+fn nesting_is_capped() {
+    let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    assert!(Json::parse(&nested(MAX_DEPTH)).is_ok());
+    assert!(Json::parse(&nested(MAX_DEPTH + 1)).is_err());
+    assert!(Json::parse(&"[".repeat(100_000)).is_err());
+    let objects = format!("{}1{}", "{\"a\":".repeat(MAX_DEPTH + 1), "}".repeat(MAX_DEPTH + 1));
+    assert!(Json::parse(&objects).is_err());
+}
+
+
+#[test]
+/// Broken input is an error, never a panic.
+fn malformed_input_is_an_error() {
+    let broken = [
+        "", "   ", "nul", "tru", "[1,", "[1 2]", "{\"a\" 1}", "{\"a\":1,}", "{1:2}", "[1]]", "\"open", "\"bad \\x escape\"",
+        "\"\\", "-", "1.2.3", "+", "NaN", "Infinity", "{\"a\":}", "}", "]", "[,]", "\"\\u\"", "\"\\ud83d\\u\"",
+    ];
+    for text in broken {
+        assert!(Json::parse(text).is_err(), "{:?} parsed", text);
+    }
+}
@@ -0,0 +1,146 @@
+use kingdomino::layout::{DraftColumn, Layout, Rect};
+
+/// Window sizes the game should look right on: small laptop window, full HD, ultra-wide, portrait
+const WINDOWS: [(f32, f32); 4] = [(800.0, 600.0), (1920.0, 1080.0), (3440.0, 1440.0), (900.0, 1400.0)];
+
+fn inside(inner: Rect, outer: Rect) -> bool {
+    let eps = 1e-3;
+    inner.x >= outer.x - eps && inner.y >= outer.y - eps && inner.right() <= outer.right() + eps && inner.bottom() <= outer.bottom() + eps
+}
+
+fn overlap(a: Rect, b: Rect) -> bool {
+    a.x < b.right() - 1e-3 && b.x < a.right() - 1e-3 && a.y < b.bottom() - 1e-3 && b.y < a.bottom() - 1e-3
+}
+
+
+#[test]
+/// The sidebar and the panes fill the window between them, without overlapping.
+fn panes_fill_the_window() {
+    for (width, height) in WINDOWS {
+        for players in [4, 5] {
+            let layout = Layout::new(width, height, players, players);
+            let window = Rect::new(0.0, 0.0, width, height);
+            assert_eq!(layout.panes.len(), players);
+
+            let pane_area: f32 = layout.panes.iter().map(|pane| pane.w * pane.h).sum();
+            let sidebar_area = layout.sidebar.w * layout.sidebar.h;
+            assert!((pane_area + sidebar_area - width * height).abs() < 1.0, "{}x{}", width, height);
+
+            for (i, pane) in layout.panes.iter().enumerate() {
+                assert!(inside(*pane, window));
+                assert!(!overlap(*pane, layout.sidebar));
+                assert!(layout.panes[i + 1..].iter().all(|other| !overlap(*pane, *other)));
+            }
+            assert_eq!(layout.draft.h + layout.advice.h + layout.scores.h, height);
+            assert!(inside(layout.panel(), window) && !overlap(layout.panel(), layout.sidebar));
+        }
+    }
+    assert_eq!(Layout::pane_grid(4), (2, 2));
+    assert_eq!(Layout::pane_grid(5), (3, 2));
+}
+
+
+#[test]
+/// Every draft slot sits inside the draft area, the columns don't touch, and a point hits only its own slot.
+fn draft_slots_fit_and_hit() {
+    for (width, height) in WINDOWS {
+        let layout = Layout::new(width, height, 4, 4);
+        let slots: Vec<Rect> = [DraftColumn::Pick, DraftColumn::Place].iter()
+            .flat_map(|&column| (0..4).map(move |slot| (column, slot)))
+            .map(|(column, slot)| layout.draft_slot(column, slot))
+            .collect();
+        for (i, slot) in slots.iter().enumerate() {
+            assert!(inside(*slot, layout.draft), "{}x{}: {:?}", width, height, slot);
+            assert!(slots[i + 1..].iter().all(|other| !overlap(*slot, *other)));
+            assert!(!overlap(*slot, layout.draft_scroll()));
+            assert_eq!(slot.w, 2.0 * slot.h);
+        }
+
+        let third = layout.draft_slot(DraftColumn::Pick, 2);
+        let (x, y) = third.center();
+        let hits: Vec<usize> = (0..4).filter(|&slot| layout.draft_slot(DraftColumn::Pick, slot).contains(x, y)).collect();
+        assert_eq!(hits, vec![2]);
+    }
+}
+
+
+#[test]
+/// A full 5x5 kingdom, plus its ring of sockets, fits its pane on every window, centered on the pane.
+fn kingdoms_fit_their_panes() {
+    for (width, height) in WINDOWS {
+        let layout = Layout::new(width, height, 4, 4);
+        for pane in &layout.panes {
+            // castle in the corner of a full kingdom, so sockets could stick out one past every edge
+            let origin = layout.kingdom_origin(*pane, (2, 6, 4, 8));
+            let top_left = layout.kingdom_cell(origin, 1, 3);
+            let bottom_right = layout.kingdom_cell(origin, 7, 9);
+            let ring = Rect::new(top_left.x, top_left.y, bottom_right.right() - top_left.x, bottom_right.bottom() - top_left.y);
+            assert!(inside(ring, *pane), "{}x{}", width, height);
+
+            // a lone castle sits right in the middle
+            let castle = layout.kingdom_cell(layout.kingdom_origin(*pane, (4, 4, 4, 4)), 4, 4);
+            let (cx, cy) = castle.center();
+            assert!((cx - pane.center().0).abs() < 1e-3 && (cy - pane.center().1).abs() < 1e-3);
+        }
+    }
+
+    // Bigger windows get bigger tiles
+    let small = Layout::new(800.0, 600.0, 4, 4);
+    let big = Layout::new(1920.0, 1080.0, 4, 4);
+    assert!(big.kingdom_tile > small.kingdom_tile);
+    assert!(big.draft_tile >= small.draft_tile);
+}
+
+
+#[test]
+/// One score row per player, stacked inside the score box.
+fn score_rows_stack() {
+    for (width, height) in WINDOWS {
+        let layout = Layout::new(width, height, 5, 5);
+        let rows: Vec<Rect> = (0..5).map(|seat| layout.score_row(seat, 5)).collect();
+        for pair in rows.windows(2) {
+            assert!(pair[0].bottom() <= pair[1].y + 1e-3);
+        }
+        assert!(rows.iter().all(|row| inside(*row, layout.scores)));
+        assert!(inside(layout.advice_king(), layout.advice));
+    }
+}
+
+
+#[test]
+/// Age of Giants: the two quests and the giant supply fit in the draft area beside the scroll, clear of the draft slots.
+fn quests_fit_beside_the_draft() {
+    for (width, height) in WINDOWS {
+        for players in [4, 5] {
+            let layout = Layout::new(width, height, players, players);
+            let shown = [layout.quest_tile(0), layout.quest_tile(1), layout.giant_supply()];
+            let slots: Vec<Rect> = [DraftColumn::Pick, DraftColumn::Place].iter()
+                .flat_map(|&column| (0..players).map(move |slot| (column, slot)))
+                .map(|(column, slot)| layout.draft_slot(column, slot))
+                .collect();
+            for (i, rect) in shown.iter().enumerate() {
+                assert!(inside(*rect, layout.draft), "{}x{} with {} players", width, height, players);
+                assert!(!overlap(*rect, layout.draft_scroll()));
+                assert!(slots.iter().all(|slot| !overlap(*rect, *slot)));
+                assert!(shown[i + 1..].iter().all(|other| !overlap(*rect, *other)));
+            }
+        }
+    }
+}
+
+
+#[test]
+/// The cell under the cursor is the cell drawn there, and points left of or above the grid give negative cells.
+fn cursor_snaps_to_cells() {
+    let layout = Layout::new(1280.0, 800.0, 4, 4);
+    let origin = layout.kingdom_origin(layout.panes[0], (4, 4, 4, 4));
+    for (x, y) in [(0, 0), (4, 4), (8, 3)] {
+        let cell = layout.kingdom_cell(origin, x, y);
+        let (cx, cy) = cell.center();
+        assert_eq!(layout.kingdom_cell_at(origin, cx, cy), (x as i32, y as i32));
+        // just inside the top left corner is the cell, just past the bottom right one is the next
+        assert_eq!(layout.kingdom_cell_at(origin, cell.x + 0.01, cell.y + 0.01), (x as i32, y as i32));
+        assert_eq!(layout.kingdom_cell_at(origin, cell.right() + 0.01, cell.bottom() + 0.01), (x as i32 + 1, y as i32 + 1));
+    }
+    assert_eq!(layout.kingdom_cell_at(origin, origin.0 - 1.0, origin.1 - 1.0), (-1, -1));
+}
@@ -0,0 +1,41 @@
+use kingdomino::scoreboard::{rank, ScoreLine, ScoreTicker, TICK_SECONDS};
+
+
+#[test]
+/// Higher scores rank first, ties go to the largest property and then the crowns, and full ties share a rank.
+fn ranks_break_ties_by_property_then_crowns() {
+    let mut lines = [
+        ScoreLine::new(1, 20, 4, 6),
+        ScoreLine::new(2, 35, 5, 5),
+        ScoreLine::new(3, 20, 6, 6),
+        ScoreLine::new(4, 20, 9, 4),
+    ];
+    rank(&mut lines);
+    let ranks: Vec<usize> = lines.iter().map(|line| line.rank).collect();
+    assert_eq!(ranks, [3, 1, 2, 4]);
+
+    let mut tied = [ScoreLine::new(1, 10, 2, 3), ScoreLine::new(2, 10, 2, 3), ScoreLine::new(3, 0, 0, 0)];
+    rank(&mut tied);
+    let ranks: Vec<usize> = tied.iter().map(|line| line.rank).collect();
+    assert_eq!(ranks, [1, 1, 3]);
+}
+
+
+#[test]
+/// A new score counts up from the old one, and stays put once it gets there.
+fn ticker_counts_up_to_new_scores() {
+    let mut ticker = ScoreTicker::default();
+    ticker.set(0, 1.0);
+    assert_eq!(ticker.shown(1.0), 0.0);
+
+    ticker.set(10, 2.0);
+    assert_eq!(ticker.shown(2.0), 0.0);
+    assert_eq!(ticker.shown(2.0 + TICK_SECONDS / 2.0), 5.0);
+    assert_eq!(ticker.shown(2.0 + TICK_SECONDS), 10.0);
+    assert_eq!(ticker.progress(100.0), 1.0);
+
+    // setting the same score again does not restart the count
+    ticker.set(10, 50.0);
+    assert_eq!(ticker.shown(50.0), 10.0);
+    assert_eq!(ticker.score(), 10);
+}
@@ -0,0 +1,181 @@
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use kingdomino::json::Json;
+use kingdomino::components::variant::Variant;
+use kingdomino::server;
+
+struct TestClient {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+}
+
+impl TestClient {
+    async fn connect(address: std::net::SocketAddr) -> Self {
+        let (reader, writer) = TcpStream::connect(address).await.unwrap().into_split();
+        Self { lines: BufReader::new(reader).lines(), writer }
+    }
+
+    async fn send(&mut self, message: &str) {
+        self.writer.write_all(format!("{}\n", message).as_bytes()).await.unwrap();
+    }
+
+    /// Reads messages until one of the given type shows up
+    async fn expect(&mut self, kind: &str) -> Json {
+        loop {
+            let line = self.lines.next_line().await.unwrap().expect("server hung up");
+            let message = Json::parse(&line).unwrap();
+            if message.get("type").and_then(Json::as_str) == Some(kind) {
+                return message;
+            }
+        }
+    }
+
+    /// Makes the first legal move whenever it is our turn, until the game is over. Returns the final scores.
+    async fn play(self, seat: u64) -> Vec<u64> {
+        self.play_watching(seat, |_| {}).await
+    }
+
+    /// Like play, showing every state to `watch` as it comes
+    async fn play_watching(mut self, seat: u64, mut watch: impl FnMut(&Json)) -> Vec<u64> {
+        loop {
+            let state = self.expect("state").await;
+            watch(&state);
+            let players = match state.get("players") {
+                Some(Json::Array(players)) => players.clone(),
+                _ => panic!("state without players"),
+            };
+            if state.get("phase") == Some(&Json::Null) {
+                return players.iter().map(|p| p.get("score").and_then(Json::as_u64).unwrap()).collect();
+            }
+
+            if state.get("started") != Some(&Json::Bool(true)) || state.get("active_seat").and_then(Json::as_u64) != Some(seat) {
+                continue;
+            }
+            let first_legal = match state.get("legal") {
+                Some(Json::Array(legal)) => legal.first().cloned(),
+                _ => None,
+            };
+            match state.get("phase").and_then(Json::as_str) {
+                Some("pick") => self.send(&format!(r#"{{"type": "pick", "slot": {}}}"#, first_legal.unwrap())).await,
+                Some("place") => self.send(&format!(r#"{{"type": "place", "placement": {}}}"#, first_legal.unwrap_or(Json::Null))).await,
+                _ => panic!("unknown phase"),
+            }
+        }
+    }
+}
+
+#[tokio::test]
+/// Four clients join on localhost and play a whole game through the server, which rejects moves out of turn.
+async fn four_clients_play_a_full_game() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = tokio::spawn(server::serve(listener, Variant::Kingdomino, 4));
+
+    let mut clients = Vec::new();
+    for expected_seat in 1..=4 {
+        let mut client = TestClient::connect(address).await;
+        client.expect("state").await;
+
+        // Nobody can move before the table is full
+        if expected_seat == 1 {
+            client.send(r#"{"type": "join"}"#).await;
+            client.expect("welcome").await;
+            client.send(r#"{"type": "pick", "slot": 0}"#).await;
+            let error = client.expect("error").await;
+            assert!(error.get("message").and_then(Json::as_str).unwrap().contains("waiting"));
+        } else {
+            client.send(&format!(r#"{{"type": "join", "seat": {}}}"#, expected_seat)).await;
+            let welcome = client.expect("welcome").await;
+            assert_eq!(welcome.get("seat").and_then(Json::as_u64), Some(expected_seat));
+        }
+        client.send(r#"{"type": "ready"}"#).await;
+        clients.push(client);
+    }
+
+    // A fifth client can watch, but not sit down
+    let mut spectator = TestClient::connect(address).await;
+    spectator.send(r#"{"type": "join"}"#).await;
+    spectator.expect("error").await;
+
+    // Blue picks first, so Green moving now is out of turn
+    while clients[1].expect("state").await.get("started") != Some(&Json::Bool(true)) {}
+    clients[1].send(r#"{"type": "pick", "slot": 0}"#).await;
+    let error = clients[1].expect("error").await;
+    assert_eq!(error.get("message").and_then(Json::as_str), Some("it is not your turn"));
+
+    let players: Vec<_> = clients.into_iter().enumerate()
+        .map(|(i, client)| tokio::spawn(client.play(i as u64 + 1)))
+        .collect();
+
+    let scores = server.await.unwrap().unwrap();
+    for player in players {
+        let seen: Vec<u64> = player.await.unwrap();
+        assert_eq!(seen, scores.iter().map(|&s| s as u64).collect::<Vec<_>>());
+    }
+}
+
+
+#[tokio::test]
+/// Deeply nested JSON is refused with an error instead of overflowing the parser's stack, and a client
+/// sending a line longer than the limit is hung up on while the server keeps serving everyone else.
+async fn hostile_input_is_refused() {
+    let nested = format!("{}{}", "[".repeat(10_000), "]".repeat(10_000));
+    assert!(Json::parse(&nested).unwrap_err().contains("nested"));
+    let deepest_allowed = format!("{}{}", "[".repeat(kingdomino::json::MAX_DEPTH), "]".repeat(kingdomino::json::MAX_DEPTH));
+    assert!(Json::parse(&deepest_allowed).is_ok());
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(server::serve(listener, Variant::Kingdomino, 4));
+
+    let mut client = TestClient::connect(address).await;
+    client.expect("state").await;
+    client.send(&"[".repeat(server::MAX_LINE_BYTES - 1)).await;
+    let error = client.expect("error").await;
+    assert!(error.get("message").and_then(Json::as_str).unwrap().contains("nested"));
+
+    client.send(&"x".repeat(server::MAX_LINE_BYTES + 1)).await;
+    assert!(matches!(client.lines.next_line().await, Ok(None) | Err(_)), "the server should hang up");
+
+    // everyone else is still served
+    let mut other = TestClient::connect(address).await;
+    other.expect("state").await;
+    other.send(r#"{"type": "join"}"#).await;
+    other.expect("welcome").await;
+}
+
+
+#[tokio::test]
+/// Age of Giants dominoes keep their giant or footprint on the wire, the same as in the set they were dealt from.
+/// The base game's dominoes that set is played with have none.
+async fn giant_marks_cross_the_wire() {
+    let set = Json::parse(include_str!("../res/sets/age_of_giants.json")).unwrap();
+    let Some(Json::Array(dominoes)) = set.get("dominoes") else { panic!("the set lists its dominoes") };
+    let marks: Vec<(u64, Json)> = dominoes.iter()
+        .map(|domino| (domino.get("id").and_then(Json::as_u64).unwrap(), domino.get("mark").cloned().unwrap_or(Json::Null)))
+        .collect();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(server::serve(listener, Variant::AgeOfGiants, 4));
+
+    let mut client = TestClient::connect(address).await;
+    client.send(r#"{"type": "join"}"#).await;
+    client.expect("welcome").await;
+    client.send(r#"{"type": "fill", "bot": "random"}"#).await;
+    client.send(r#"{"type": "ready"}"#).await;
+
+    let mut giants = 0;
+    client.play_watching(1, |state| {
+        let Some(Json::Array(draft)) = state.get("pick_draft") else { return };
+        for domino in draft.iter().filter_map(|slot| slot.get("domino")) {
+            let id = domino.get("id").and_then(Json::as_u64).unwrap();
+            let mark = domino.get("mark").expect("every domino says whether it has a mark");
+            let expected = marks.iter().find(|(set_id, _)| *set_id == id).map_or(&Json::Null, |(_, mark)| mark);
+            assert_eq!(mark, expected, "domino {}", id);
+            giants += usize::from(mark.as_str() == Some("giant"));
+        }
+    }).await;
+    assert!(giants > 0, "a whole game deals the giant dominoes");
+}
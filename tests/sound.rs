@@ -0,0 +1,61 @@
+use kingdomino::assets::{AssetError, AssetSource};
+use kingdomino::event_log::Event;
+use kingdomino::sound::{self, check_format, Effect, Slider, Volume};
+
+
+#[test]
+/// Every effect has a built-in sound the backend can play, and a theme pack can swap it (or add music).
+fn effects_load_like_pictures() {
+    let built_in = AssetSource::default();
+    for effect in Effect::ALL {
+        let bytes = built_in.sound(effect.name()).unwrap_or_else(|e| panic!("{}", e));
+        assert!(check_format(&bytes).is_ok(), "{}", effect.name());
+        assert!(bytes.len() > 44, "{} is silent", effect.name());
+    }
+    assert!(matches!(built_in.sound(sound::MUSIC), Err(AssetError::Missing(_))));
+
+    let dir = std::env::temp_dir().join("kingdomino_sound_pack_test");
+    std::fs::create_dir_all(dir.join("sounds")).unwrap();
+    let tune = sound::synthesize(&[(440.0, 0.5)]);
+    std::fs::write(dir.join("sounds/music.wav"), &tune).unwrap();
+    std::fs::write(dir.join("sounds/pick.ogg"), b"not a sound").unwrap();
+
+    let pack = AssetSource::new(Some(&dir));
+    assert_eq!(pack.sound(sound::MUSIC).unwrap(), tune);
+    match pack.sound("sounds/pick") {
+        Err(e @ AssetError::Malformed(..)) => assert!(e.to_string().contains("pick.ogg")),
+        other => panic!("expected a malformed pick, got {:?}", other.map(|_| ())),
+    }
+    assert_eq!(pack.sound("sounds/place").unwrap(), built_in.sound("sounds/place").unwrap());
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    // a stereo float .wav is fine, a 6 channel one is not
+    let mut surround = sound::synthesize(&[(440.0, 0.1)]);
+    surround[22] = 6;
+    assert!(check_format(&surround).is_err());
+    surround[22] = 2;
+    surround[20] = 3;
+    assert!(check_format(&surround).is_ok());
+}
+
+
+#[test]
+/// Sliders stay between 0 and 1, the master volume turns everything down and muting silences it all.
+fn volume_and_events() {
+    let mut volume = Volume::default();
+    volume.set(Slider::Master, 0.5);
+    volume.set(Slider::Effects, 1.5);
+    volume.set(Slider::Music, -1.0);
+    assert_eq!(volume.get(Slider::Effects), 1.0);
+    assert_eq!(volume.effects_level(), 0.5);
+    assert_eq!(volume.music_level(), 0.0);
+
+    volume.set(Slider::Music, 0.4);
+    volume.muted = true;
+    assert_eq!((volume.effects_level(), volume.music_level()), (0.0, 0.0));
+
+    let picked = Event::Picked { player: "Blue".to_string(), domino: 12 };
+    assert_eq!(Effect::for_event(&picked), Some(Effect::Pick));
+    assert_eq!(Effect::for_event(&Event::GameOver { scores: vec![] }), Some(Effect::GameEnd));
+    assert_eq!(Effect::for_event(&Event::Undo { player: "Blue".to_string() }), None);
+}
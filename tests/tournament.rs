@@ -0,0 +1,125 @@
+use kingdomino::components::variant::Variant;
+use kingdomino::external_agent::DEFAULT_MOVE_TIMEOUT;
+use kingdomino::scoreboard::{self, ScoreLine};
+use kingdomino::tournament::{self, Format, Standing, TournamentConfig, STARTING_ELO};
+
+#[test]
+/// Runs a small round robin and a Swiss tournament, checking every game lands in the results file.
+fn tournament_records_every_game() {
+    let path = std::env::temp_dir().join("kingdomino_tournament_test.csv");
+    let agents: Vec<String> = ["greedy", "random", "random", "greedy", "random"].iter().map(|s| s.to_string()).collect();
+
+    let config = TournamentConfig {
+        agents: agents.clone(),
+        format: Format::RoundRobin,
+        games_per_seating: 1,
+        results_path: path.clone(),
+        move_timeout: DEFAULT_MOVE_TIMEOUT,
+        variant: Variant::Kingdomino,
+        table_size: 4,
+        domino_set: None,
+    };
+    let standings = tournament::run(&config).unwrap();
+
+    // 5 tables of four, each played from all four seats
+    let results = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(results.lines().count(), 1 + 5 * 4);
+    assert_eq!(standings.len(), 5);
+    assert!(standings.windows(2).all(|w| w[0].rating >= w[1].rating));
+
+    // Elo is zero sum
+    let total: f64 = standings.iter().map(|s| s.rating).sum();
+    assert!((total - 5.0 * STARTING_ELO).abs() < 1e-6);
+
+    let config = TournamentConfig { format: Format::Swiss { rounds: 2 }, ..config };
+    let standings = tournament::run(&config).unwrap();
+    let results = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(results.lines().count(), 1 + 2 * 2 * 4);
+    assert_eq!(standings.iter().map(|s| s.games).sum::<usize>(), 2 * 2 * 4 * 4);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+
+#[test]
+/// Bots play Queendomino to the end, and the coins and buildings show up in the scores.
+fn queendomino_tournament_runs() {
+    let path = std::env::temp_dir().join("kingdomino_queendomino_test.csv");
+    let config = TournamentConfig {
+        agents: ["greedy", "greedy", "random", "random"].iter().map(|s| s.to_string()).collect(),
+        format: Format::RoundRobin,
+        games_per_seating: 1,
+        results_path: path.clone(),
+        move_timeout: DEFAULT_MOVE_TIMEOUT,
+        variant: Variant::from_name("Queendomino").unwrap(),
+        table_size: 4,
+        domino_set: None,
+    };
+    let standings = tournament::run(&config).unwrap();
+    assert_eq!(standings.iter().map(|s| s.games).sum::<usize>(), 4 * 4);
+
+    let results = std::fs::read_to_string(&path).unwrap();
+    for line in results.lines().skip(1) {
+        let scores: Vec<u32> = line.split(',').skip(6).map(|s| s.parse().unwrap()).collect();
+        // Terrain alone scores something, the buildings and coins only add to it
+        assert!(scores.iter().all(|&score| score > 0), "{}", line);
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+
+#[test]
+/// Five bots share an Age of Giants table, each seat shows up in the results, and the giants don't break scoring.
+fn age_of_giants_tournament_seats_five() {
+    let path = std::env::temp_dir().join("kingdomino_giants_test.csv");
+    let config = TournamentConfig {
+        agents: ["greedy", "greedy", "random", "random", "random"].iter().map(|s| s.to_string()).collect(),
+        format: Format::RoundRobin,
+        games_per_seating: 1,
+        results_path: path.clone(),
+        move_timeout: DEFAULT_MOVE_TIMEOUT,
+        variant: Variant::from_name("age-of-giants").unwrap(),
+        table_size: 5,
+        domino_set: None,
+    };
+    let standings = tournament::run(&config).unwrap();
+    assert_eq!(standings.iter().map(|s| s.games).sum::<usize>(), 5 * 5);
+
+    let results = std::fs::read_to_string(&path).unwrap();
+    let mut lines = results.lines();
+    assert!(lines.next().unwrap().ends_with("yellow_score,purple_score"));
+    assert!(lines.all(|line| line.split(',').count() == 2 + 5 + 5));
+
+    // Base Kingdomino still seats four
+    let config = TournamentConfig { variant: Variant::Kingdomino, ..config };
+    assert!(tournament::run(&config).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+
+#[test]
+/// Equal scores are settled by the largest property, then the crowns. A first place still shared after that is a draw.
+fn tied_scores_use_the_tie_breakers() {
+    let fresh = |count: usize| -> Vec<Standing> {
+        (0..count).map(|i| Standing { name: format!("bot{}", i), rating: STARTING_ELO, games: 0, wins: 0, draws: 0 }).collect()
+    };
+
+    // all on 30: seat 1 has the largest property, seats 2 and 3 tie on it and seat 2 has more crowns
+    let mut lines = vec![ScoreLine::new(1, 30, 5, 9), ScoreLine::new(2, 30, 6, 7), ScoreLine::new(3, 30, 4, 7), ScoreLine::new(4, 12, 8, 9)];
+    scoreboard::rank(&mut lines);
+    let mut standings = fresh(4);
+    tournament::update_standings(&mut standings, &[0, 1, 2, 3], &lines);
+    assert_eq!(standings.iter().map(|s| s.wins).collect::<Vec<_>>(), [1, 0, 0, 0]);
+    assert!(standings.windows(2).all(|w| w[0].rating > w[1].rating));
+
+    // tied on everything: nobody wins, the two leaders draw and neither rating moves past the other
+    let mut lines = vec![ScoreLine::new(1, 30, 5, 9), ScoreLine::new(2, 30, 5, 9), ScoreLine::new(3, 20, 5, 9), ScoreLine::new(4, 10, 5, 9)];
+    scoreboard::rank(&mut lines);
+    let mut standings = fresh(4);
+    tournament::update_standings(&mut standings, &[0, 1, 2, 3], &lines);
+    assert_eq!(standings.iter().map(|s| (s.wins, s.draws)).collect::<Vec<_>>(), [(0, 1), (0, 1), (0, 0), (0, 0)]);
+    assert_eq!(standings[0].rating, standings[1].rating);
+    assert!(standings[1].rating > standings[2].rating);
+}
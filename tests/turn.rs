@@ -0,0 +1,131 @@
+use kingdomino::components::turn::{Turn, TurnState};
+use kingdomino::controller::Phase;
+
+/// Walks the state machine through a whole four king game with a deck that deals the given number of drafts.
+/// Returns every state the game passes through after the deal.
+fn play_through(drafts: usize) -> Vec<TurnState> {
+    play_through_with(4, drafts)
+}
+
+/// Same as play_through, for any number of kings
+fn play_through_with(kings: usize, drafts: usize) -> Vec<TurnState> {
+    let mut drafts_left = drafts - 1; // the first one is dealt during setup
+    let mut state = TurnState::Setup.next(kings, drafts_left == 0);
+    let mut states = Vec::new();
+    while !state.is_over() {
+        states.push(state);
+        // The last pick of a round deals the next draft, if there is one
+        let dealt = state.is_last_pick(kings) && drafts_left > 0;
+        if dealt {
+            drafts_left -= 1;
+        }
+        state = state.next(kings, state.is_last_pick(kings) && !dealt);
+        assert!(states.len() < 1000, "the game never ends");
+    }
+    states
+}
+
+#[test]
+/// Every king picks once in the first round, and nobody places
+fn first_round_only_picks() {
+    let states = play_through(12);
+    let first_round: Vec<_> = states.iter().take(4).collect();
+    assert_eq!(first_round, [
+        &TurnState::FirstRound(Turn::Prio1),
+        &TurnState::FirstRound(Turn::Prio2),
+        &TurnState::FirstRound(Turn::Prio3),
+        &TurnState::FirstRound(Turn::Prio4),
+    ]);
+    assert!(first_round.iter().all(|s| s.phase() == Some(Phase::Picking)));
+    assert!(states[3].is_last_pick(4));
+}
+
+#[test]
+/// After the first round each king places and then picks, in place draft order
+fn kings_place_then_pick() {
+    let states = play_through(12);
+    let round: Vec<_> = states[4..12].iter().map(|s| (s.king().unwrap(), s.phase().unwrap())).collect();
+    assert_eq!(round, [
+        (Turn::Prio1, Phase::Placing), (Turn::Prio1, Phase::Picking),
+        (Turn::Prio2, Phase::Placing), (Turn::Prio2, Phase::Picking),
+        (Turn::Prio3, Phase::Placing), (Turn::Prio3, Phase::Picking),
+        (Turn::Prio4, Phase::Placing), (Turn::Prio4, Phase::Picking),
+    ]);
+    assert_eq!(states.iter().filter(|s| s.is_last_pick(4)).count(), 12);
+}
+
+#[test]
+/// A 48 domino deck is 12 drafts: 48 picks and 48 placements, ending with a round that only places
+fn full_game_picks_and_places_every_domino() {
+    let states = play_through(12);
+    let picks = states.iter().filter(|s| s.phase() == Some(Phase::Picking)).count();
+    let places = states.iter().filter(|s| s.phase() == Some(Phase::Placing)).count();
+    assert_eq!((picks, places), (48, 48));
+
+    let last_round = &states[states.len() - 4..];
+    assert_eq!(last_round, [
+        TurnState::FinalRound(Turn::Prio1),
+        TurnState::FinalRound(Turn::Prio2),
+        TurnState::FinalRound(Turn::Prio3),
+        TurnState::FinalRound(Turn::Prio4),
+    ]);
+    assert_eq!(last_round[3].next(4, true), TurnState::GameOver);
+}
+
+#[test]
+/// With five kings every round runs through Prio5, and a 50 domino deck is ten drafts
+fn five_kings_take_turns() {
+    let states = play_through_with(5, 10);
+    let first_round: Vec<_> = states.iter().take(5).map(|s| s.king().unwrap()).collect();
+    assert_eq!(first_round, [Turn::Prio1, Turn::Prio2, Turn::Prio3, Turn::Prio4, Turn::Prio5]);
+    assert!(!states[3].is_last_pick(5));
+    assert!(states[4].is_last_pick(5));
+
+    let picks = states.iter().filter(|s| s.phase() == Some(Phase::Picking)).count();
+    let places = states.iter().filter(|s| s.phase() == Some(Phase::Placing)).count();
+    assert_eq!((picks, places), (50, 50));
+    assert_eq!(states.last(), Some(&TurnState::FinalRound(Turn::Prio5)));
+}
+
+#[test]
+/// Only one draft means the first round goes straight into the final round
+fn single_draft_game() {
+    let states = play_through(1);
+    assert_eq!(states.len(), 8);
+    assert_eq!(states[4], TurnState::FinalRound(Turn::Prio1));
+}
+
+#[test]
+/// Nothing moves before the deal or after the end, and the end stays the end
+fn setup_and_game_over_have_no_moves() {
+    assert_eq!(TurnState::default(), TurnState::Setup);
+    assert_eq!(TurnState::Setup.phase(), None);
+    assert_eq!(TurnState::Setup.king(), None);
+    assert_eq!(TurnState::GameOver.phase(), None);
+    assert_eq!(TurnState::GameOver.next(4, false), TurnState::GameOver);
+}
+
+#[test]
+/// The place draft hides the dominoes of kings who already placed this round
+fn placed_slots_follow_the_king() {
+    let placing = TurnState::Round { king: Turn::Prio3, placed: false };
+    assert_eq!((0..4).map(|slot| placing.has_placed(slot)).collect::<Vec<_>>(), [true, true, false, false]);
+
+    let picking = TurnState::Round { king: Turn::Prio3, placed: true };
+    assert_eq!((0..4).map(|slot| picking.has_placed(slot)).collect::<Vec<_>>(), [true, true, true, false]);
+
+    assert!(!TurnState::FirstRound(Turn::Prio4).has_placed(0));
+    assert!(TurnState::FinalRound(Turn::Prio2).has_placed(0));
+    assert!(!TurnState::FinalRound(Turn::Prio2).has_placed(1));
+}
+
+#[test]
+/// A state rebuilt from what a client can see is the state the server is in
+fn resume_matches_every_state() {
+    for state in play_through(12) {
+        let has_place_draft = !matches!(state, TurnState::FirstRound(_));
+        let has_pick_draft = !matches!(state, TurnState::FinalRound(_));
+        assert_eq!(TurnState::resume(state.phase(), state.king().unwrap(), has_place_draft, has_pick_draft), state);
+    }
+    assert_eq!(TurnState::resume(None, Turn::Prio1, true, false), TurnState::GameOver);
+}